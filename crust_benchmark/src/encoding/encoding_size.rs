@@ -0,0 +1,74 @@
+use crust_core::{
+    command::{CounterInnerCommand, CrdtInnerCommand},
+    error::CrustError,
+    r#type::CrdtType,
+};
+use crust_network::{encoding::Encoding, message::NetworkMessage};
+use serde::Serialize;
+
+pub struct EncodingSize {
+    pub name: String,
+    pub json: usize,
+    pub cbor: usize,
+}
+
+impl EncodingSize {
+    pub fn measure<T>(name: String, value: &T) -> Result<Self, CrustError>
+    where
+        T: Serialize,
+    {
+        Ok(EncodingSize {
+            name,
+            json: Encoding::Json.encode(value)?.len(),
+            cbor: Encoding::Cbor.encode(value)?.len(),
+        })
+    }
+
+    pub fn ratio(&self) -> f64 {
+        self.cbor as f64 / self.json as f64
+    }
+}
+
+pub fn counter_state(replica_count: usize) -> Result<CrdtType<String>, CrustError> {
+    let mut crdt = CrdtType::new("gcounter".to_string())?;
+    for replica in 0..replica_count {
+        crdt.apply_command(&CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+            value: format!("replica-{replica}"),
+        }))?;
+    }
+    Ok(crdt)
+}
+
+pub fn compare_encodings(replica_counts: &[usize]) -> Result<Vec<EncodingSize>, CrustError> {
+    let mut sizes = Vec::new();
+    for replica_count in replica_counts {
+        let crdt = counter_state(*replica_count)?;
+        sizes.push(EncodingSize::measure(
+            format!("gcounter delta ({replica_count} replicas)"),
+            &NetworkMessage::<String>::Delta {
+                payload: crdt.generate_delta()?,
+                sender_pod_name: "replica-0".to_string(),
+            },
+        )?);
+        sizes.push(EncodingSize::measure(
+            format!("gcounter state ({replica_count} replicas)"),
+            &NetworkMessage::State {
+                payload: crdt,
+                sender_pod_name: "replica-0".to_string(),
+            },
+        )?);
+    }
+    Ok(sizes)
+}
+
+pub fn report_encoding_sizes(sizes: &[EncodingSize]) {
+    for size in sizes {
+        println!(
+            "{}: json {} bytes, cbor {} bytes ({:.2})",
+            size.name,
+            size.json,
+            size.cbor,
+            size.ratio()
+        );
+    }
+}
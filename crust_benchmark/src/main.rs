@@ -1,9 +1,14 @@
 pub mod collector;
 pub mod config;
+pub mod encoding;
 pub mod metrics;
 pub mod reporter;
 pub mod runner;
 pub mod workload;
 
+use encoding::encoding_size::{compare_encodings, report_encoding_sizes};
+
 #[tokio::main]
-async fn main() {}
+async fn main() {
+    report_encoding_sizes(&compare_encodings(&[10, 100, 1000]).unwrap());
+}
@@ -1,16 +1,12 @@
 use std::time::Instant;
 
-use crust_core::{
-    command::{CounterInnerCommand, CrdtInnerCommand},
-    r#type::{CrdtType, CrdtTypeVariant},
-};
+use crust_core::r#type::CrdtType;
 
 use crate::{
     collector::metrics_collector::{BenchmarkMetricsCollector, MetricsCollector},
     config::benchmark_config::BenchmarkConfig,
-    metrics::metrics_definition::MetricType,
     reporter::benchmark_report::{BenchmarkReporter, BenchmarkResults},
-    workload::{self, workload_generation::generate_workload},
+    workload::workload_generation::generate_workload,
 };
 
 pub enum BenchmarkError {
@@ -44,7 +40,7 @@ impl BenchmarkRunner {
 
     pub fn setup(&mut self) -> Result<(), BenchmarkError> {
         for _ in 0..self.config.replica_count {
-            let replica = CrdtType::new("GCounter".to_string()).unwrap();
+            let replica = CrdtType::new("gcounter".to_string()).unwrap();
             self.replicas.push(replica);
         }
 
@@ -55,35 +51,14 @@ impl BenchmarkRunner {
         self.start_time = Some(Instant::now());
 
         let workload = generate_workload(
-            CrdtType::new("GCounter".to_string()).unwrap(),
+            CrdtType::new("gcounter".to_string()).unwrap(),
             self.config.command_count,
-        );
-
-        for operation in workload {
-            match operation {
-                CrdtInnerCommand::Counter(cmd) => {
-                    for replica in &mut self.replicas {
-                        match cmd {
-                            CounterInnerCommand::Increment { ref value } => {
-                                replica.apply_command(&CrdtInnerCommand::Counter(
-                                    CounterInnerCommand::Increment {
-                                        value: value.to_string(),
-                                    },
-                                ));
-                            }
-                            CounterInnerCommand::Decrement { ref value } => {
-                                replica.apply_command(&CrdtInnerCommand::Counter(
-                                    CounterInnerCommand::Decrement {
-                                        value: value.to_string(),
-                                    },
-                                ));
-                            }
-                        }
-                    }
-                }
-                CrdtInnerCommand::Graph(graph_inner_command) => todo!(),
-                CrdtInnerCommand::Set(set_inner_command) => todo!(),
-                CrdtInnerCommand::Text(text_inner_command) => todo!(),
+        )
+        .map_err(|_| BenchmarkError::WorkloadError)?;
+
+        for command in workload {
+            for replica in &mut self.replicas {
+                let _ = replica.apply_command(&command);
             }
         }
 
@@ -97,7 +72,7 @@ impl BenchmarkRunner {
             let duration = start_time.elapsed();
             let results = BenchmarkResults {
                 name: "GCounter".to_string(),
-                crdt_type: CrdtType::new("GCounter".to_string()).unwrap(),
+                crdt_type: CrdtType::new("gcounter".to_string()).unwrap(),
                 duration,
             };
             self.reporter.add_result(results);
@@ -1,24 +1,32 @@
 use crust_core::{
-    command::{CounterInnerCommand, CrdtInnerCommand},
-    r#type::{CrdtType, CrdtTypeVariant},
+    command::{CounterInnerCommand, CrdtInnerCommand, MapInnerCommand, SetInnerCommand},
+    error::CrustError,
+    r#type::CrdtType,
 };
 use rand::{rng, seq::IndexedRandom, Rng};
 
 pub fn generate_workload(
     crdt_type: CrdtType<String>,
     operation_count: usize,
-) -> Vec<CrdtInnerCommand<String>> {
+) -> Result<Vec<CrdtInnerCommand<String>>, CrustError> {
     let mut workload = Vec::new();
 
     let valid_commands = crdt_type.validate_command("".to_string());
 
     for _ in 0..operation_count {
-        let command = match crdt_type.variant {
-            CrdtTypeVariant::GCounter(_) => generate_counter_command(valid_commands.clone()),
+        let command = match crdt_type.name().as_str() {
+            "gcounter" | "pncounter" => generate_counter_command(valid_commands.clone()),
+            "orset" => generate_set_command(valid_commands.clone()),
+            "mvmap" => generate_map_command(valid_commands.clone()),
+            name => {
+                return Err(CrustError::InvalidOperation(format!(
+                    "no workload generator for CRDT type `{name}`"
+                )))
+            }
         };
         workload.push(command);
     }
-    workload
+    Ok(workload)
 }
 
 pub fn generate_counter_command(
@@ -45,3 +53,34 @@ pub fn generate_counter_command(
         }),
     }
 }
+
+pub fn generate_set_command(
+    valid_commands: Vec<CrdtInnerCommand<String>>,
+) -> CrdtInnerCommand<String> {
+    let mut rng = rng();
+    let value = rng.random_range(1..=10).to_string();
+
+    match valid_commands.choose(&mut rng) {
+        Some(CrdtInnerCommand::Set(SetInnerCommand::Remove { .. })) => {
+            CrdtInnerCommand::Set(SetInnerCommand::Remove { value })
+        }
+        _ => CrdtInnerCommand::Set(SetInnerCommand::Add { value }),
+    }
+}
+
+pub fn generate_map_command(
+    valid_commands: Vec<CrdtInnerCommand<String>>,
+) -> CrdtInnerCommand<String> {
+    let mut rng = rng();
+    let key = rng.random_range(1..=10).to_string();
+
+    match valid_commands.choose(&mut rng) {
+        Some(CrdtInnerCommand::Map(MapInnerCommand::Remove { .. })) => {
+            CrdtInnerCommand::Map(MapInnerCommand::Remove { key })
+        }
+        _ => {
+            let value = rng.random_range(1..=100).to_string();
+            CrdtInnerCommand::Map(MapInnerCommand::Put { key, value })
+        }
+    }
+}
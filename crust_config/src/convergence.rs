@@ -0,0 +1,105 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    time::{Duration, Instant},
+};
+
+use crust_core::digest::StateDigest;
+use futures::future::join_all;
+
+use crate::instance::get_digest_from_instance;
+
+#[derive(Clone, Debug)]
+pub struct ConvergenceReport {
+    pub object: String,
+    pub converged: bool,
+    pub attempts: usize,
+    pub elapsed: Duration,
+    pub digests: BTreeMap<String, StateDigest>,
+    pub errors: BTreeMap<String, String>,
+}
+
+impl ConvergenceReport {
+    pub fn distinct_digests(&self) -> usize {
+        self.digests
+            .values()
+            .map(|digest| digest.digest.as_str())
+            .collect::<BTreeSet<&str>>()
+            .len()
+    }
+
+    pub fn lagging_instances(&self) -> Vec<String> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for digest in self.digests.values() {
+            *counts.entry(digest.digest.as_str()).or_insert(0) += 1;
+        }
+        let majority = counts
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map(|(digest, _)| *digest);
+        self.digests
+            .iter()
+            .filter(|(_, digest)| Some(digest.digest.as_str()) != majority)
+            .map(|(instance_id, _)| instance_id.clone())
+            .chain(self.errors.keys().cloned())
+            .collect()
+    }
+}
+
+pub async fn poll_digests(
+    object: &str,
+    service_base_urls: &HashMap<String, String>,
+) -> (BTreeMap<String, StateDigest>, BTreeMap<String, String>) {
+    let responses = join_all(
+        service_base_urls
+            .iter()
+            .map(|(instance_id, url)| async move {
+                (
+                    instance_id.clone(),
+                    get_digest_from_instance(object, url).await,
+                )
+            }),
+    )
+    .await;
+    let mut digests = BTreeMap::new();
+    let mut errors = BTreeMap::new();
+    for (instance_id, response) in responses {
+        match response {
+            Ok(digest) => {
+                digests.insert(instance_id, digest);
+            }
+            Err(error) => {
+                errors.insert(instance_id, error);
+            }
+        }
+    }
+    (digests, errors)
+}
+
+pub async fn wait_for_convergence(
+    object: &str,
+    service_base_urls: &HashMap<String, String>,
+    timeout: Duration,
+    poll_interval: Duration,
+) -> ConvergenceReport {
+    let started = Instant::now();
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let (digests, errors) = poll_digests(object, service_base_urls).await;
+        let mut report = ConvergenceReport {
+            object: object.to_string(),
+            converged: false,
+            attempts,
+            elapsed: started.elapsed(),
+            digests,
+            errors,
+        };
+        report.converged = !report.digests.is_empty()
+            && report.errors.is_empty()
+            && report.distinct_digests() == 1;
+        if report.converged || started.elapsed() + poll_interval > timeout {
+            return report;
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
@@ -6,6 +6,8 @@ use std::{
 
 use crust_core::{
     command::CrdtInnerCommand,
+    digest::StateDigest,
+    error::CrustError,
     r#type::CrdtType,
     sync::{SyncMode, SyncType},
 };
@@ -66,28 +68,28 @@ impl DeploymentConfig {
         sync_mode_str: &str,
         batch_times: Option<usize>,
         batch_interval: Option<Duration>,
-    ) -> Self {
-        DeploymentConfig {
+    ) -> Result<Self, CrustError> {
+        Ok(DeploymentConfig {
             num_replicas,
-            crdt_type: CrdtType::new(crdt_type_str.to_string()).unwrap(),
-            sync_type: SyncType::new(sync_type_str.to_string()),
-            sync_mode: SyncMode::new(sync_mode_str.to_string()),
+            crdt_type: CrdtType::new(crdt_type_str.to_string())?,
+            sync_type: SyncType::new(sync_type_str.to_string())?,
+            sync_mode: SyncMode::new(sync_mode_str.to_string())?,
             batch_times,
             batch_interval,
             network_scenario: None,
-        }
+        })
     }
 }
 
 pub async fn setup_remote_test_environement(config: &DeploymentConfig) -> HashMap<String, String> {
-    let mut service_base_urls: HashMap<String, String> = HashMap::new();
+    let service_base_urls: HashMap<String, String> = HashMap::new();
     let instance_ids = (1..=config.num_replicas)
         .map(|i| format!("replica-{}", i))
         .collect::<Vec<String>>();
     let manifest_content = get_crdt_service_yaml();
 
     let kubectl_apply_output = Command::new("kubectl")
-        .args(&["apply", "-f", manifest_content])
+        .args(["apply", "-f", manifest_content])
         .stderr(Stdio::piped())
         .output()
         .expect("failed to execute kubectl apply");
@@ -101,7 +103,7 @@ pub async fn setup_remote_test_environement(config: &DeploymentConfig) -> HashMa
 
     for id in &instance_ids {
         let replica_num = id.trim_start_matches("replica").parse::<i32>().unwrap();
-        let service_name = format!("crdt-network-service");
+        let service_name = "crdt-network-service".to_string();
         let deployment_name = format!("crdt-network-{}", replica_num);
 
         println!(
@@ -109,7 +111,7 @@ pub async fn setup_remote_test_environement(config: &DeploymentConfig) -> HashMa
             deployment_name
         );
         let kubectl_wait_output = Command::new("kubectl")
-            .args(&["wait", "--for=condition=available", "--timeout=5m"])
+            .args(["wait", "--for=condition=available", "--timeout=5m"])
             .arg(format!("deployment/{}", deployment_name))
             .stderr(Stdio::piped())
             .output()
@@ -124,7 +126,7 @@ pub async fn setup_remote_test_environement(config: &DeploymentConfig) -> HashMa
 
         println!("Waiting for Service '{}' to be ready...", service_name);
         let kubectl_wait_output = Command::new("kubectl")
-            .args(&["wait", "--for=condition=ready", "--timeout=5m"])
+            .args(["wait", "--for=condition=ready", "--timeout=5m"])
             .arg(format!("service/{}", service_name))
             .stderr(Stdio::piped())
             .output()
@@ -157,11 +159,11 @@ pub async fn setup_remote_test_environement(config: &DeploymentConfig) -> HashMa
     service_base_urls
 }
 
-pub async fn teardown_remote_test_environment(config: &DeploymentConfig) {
+pub async fn teardown_remote_test_environment(_config: &DeploymentConfig) {
     let manifest_content = get_crdt_service_yaml();
 
     let kubectl_delete_output = Command::new("kubectl")
-        .args(&["delete", "-f", manifest_content])
+        .args(["delete", "-f", manifest_content])
         .stderr(Stdio::piped())
         .output()
         .expect("failed to execute kubectl delete");
@@ -175,7 +177,7 @@ pub async fn teardown_remote_test_environment(config: &DeploymentConfig) {
 }
 
 pub async fn send_command_to_instance(
-    instance_id: &str,
+    _instance_id: &str,
     crdt_type_str: &str,
     sync_type_str: &str,
     sync_mode_str: &str,
@@ -207,7 +209,7 @@ pub async fn send_command_to_instance(
 }
 
 pub async fn send_command_to_instance_with_loss(
-    instance_id: &str,
+    _instance_id: &str,
     crdt_type_str: &str,
     sync_type_str: &str,
     sync_mode_str: &str,
@@ -248,8 +250,8 @@ pub async fn send_command_to_instance_with_loss(
 }
 
 pub async fn get_state_from_instance(
-    instance_id: &str,
-    crdt_type: &CrdtType<String>,
+    _instance_id: &str,
+    _crdt_type: &CrdtType<String>,
     service_base_url: &str,
 ) -> Result<CrdtType<String>, String> {
     let client = Client::new();
@@ -277,6 +279,35 @@ pub async fn get_state_from_instance(
     }
 }
 
+pub async fn get_digest_from_instance(
+    object: &str,
+    service_base_url: &str,
+) -> Result<StateDigest, String> {
+    let client = Client::new();
+    let url = format!("{}/digest/{}", service_base_url, object);
+
+    let response_result = client.get(&url).send().await;
+
+    match response_result {
+        Ok(response) => {
+            if response.status().is_success() {
+                let digest = response.json::<StateDigest>().await;
+                match digest {
+                    Ok(digest) => Ok(digest),
+                    Err(e) => Err(format!("Error parsing response body: {}", e)),
+                }
+            } else {
+                Err(format!(
+                    "HTTP request failed with status: {}, Response Body: {:?}",
+                    response.status(),
+                    response.text().await
+                ))
+            }
+        }
+        Err(e) => Err(format!("Error sending HTTP request: {}", e)),
+    }
+}
+
 pub async fn update_replicas(
     client: kube::Client,
     namespace: &str,
@@ -5,7 +5,7 @@ pub async fn get_replica_pod_names() -> Result<Vec<String>, Error> {
     let client = Client::try_default().await?;
     let endpoints_api: Api<Pod> = Api::namespaced(client.clone(), "default");
     let lp = ListParams::default().labels("app=crust-network");
-    let pod_list = endpoints_api.list(&Default::default()).await?;
+    let pod_list = endpoints_api.list(&lp).await?;
     let replica_pod_names = pod_list
         .iter()
         .map(|pod| {
@@ -1,3 +1,4 @@
+pub mod convergence;
 pub mod instance;
 pub mod k8s_discovery;
 
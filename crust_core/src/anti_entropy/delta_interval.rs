@@ -0,0 +1,113 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum DeltaIntervalMessage<D, S> {
+    Delta { delta: D, sequence: u64 },
+    State { state: S, sequence: u64 },
+    Ack { sequence: u64 },
+}
+
+#[derive(Clone, Debug)]
+pub struct DeltaInterval<P, D>
+where
+    P: Eq + Hash,
+{
+    sequence: u64,
+    log: BTreeMap<u64, D>,
+    acks: HashMap<P, u64>,
+}
+
+impl<P, D> Default for DeltaInterval<P, D>
+where
+    P: Eq + Hash + Clone,
+    D: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P, D> DeltaInterval<P, D>
+where
+    P: Eq + Hash + Clone,
+    D: Clone,
+{
+    pub fn new() -> Self {
+        DeltaInterval {
+            sequence: 0,
+            log: BTreeMap::new(),
+            acks: HashMap::new(),
+        }
+    }
+
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    pub fn log_len(&self) -> usize {
+        self.log.len()
+    }
+
+    pub fn acknowledged(&self, peer: &P) -> u64 {
+        self.acks.get(peer).copied().unwrap_or(0)
+    }
+
+    pub fn record_delta(&mut self, delta: D) -> u64 {
+        self.log.insert(self.sequence, delta);
+        self.sequence += 1;
+        self.sequence
+    }
+
+    pub fn prepare<S, J, F>(
+        &self,
+        peer: &P,
+        join: J,
+        full_state: F,
+    ) -> Option<DeltaIntervalMessage<D, S>>
+    where
+        J: FnOnce(Vec<D>) -> Option<D>,
+        F: FnOnce() -> S,
+    {
+        let acknowledged = self.acknowledged(peer);
+        if acknowledged >= self.sequence {
+            return None;
+        }
+        match self.log.keys().next() {
+            Some(first) if *first <= acknowledged => {
+                let deltas = self
+                    .log
+                    .range(acknowledged..)
+                    .map(|(_, delta)| delta.clone())
+                    .collect();
+                join(deltas).map(|delta| DeltaIntervalMessage::Delta {
+                    delta,
+                    sequence: self.sequence,
+                })
+            }
+            _ => Some(DeltaIntervalMessage::State {
+                state: full_state(),
+                sequence: self.sequence,
+            }),
+        }
+    }
+
+    pub fn receive_ack(&mut self, peer: P, sequence: u64) {
+        let acknowledged = self.acks.entry(peer).or_insert(0);
+        *acknowledged = (*acknowledged).max(sequence.min(self.sequence));
+    }
+
+    pub fn garbage_collect(&mut self, peers: &[P]) {
+        let stable = peers
+            .iter()
+            .map(|peer| self.acknowledged(peer))
+            .min()
+            .unwrap_or(self.sequence);
+        self.log.retain(|sequence, _| *sequence >= stable);
+        self.acks.retain(|peer, _| peers.contains(peer));
+    }
+}
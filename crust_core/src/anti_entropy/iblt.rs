@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    anti_entropy::merkle::{hash_bytes, mix},
+    error::CrustError,
+};
+
+pub const DEFAULT_IBLT_CELLS: usize = 96;
+pub const MAX_IBLT_CELLS: usize = 1 << 16;
+pub const IBLT_HASH_COUNT: usize = 3;
+
+const CHECK_SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IbltCell {
+    pub count: i64,
+    pub key_sum: u64,
+    pub check_sum: u64,
+}
+
+impl IbltCell {
+    fn is_empty(&self) -> bool {
+        self.count == 0 && self.key_sum == 0 && self.check_sum == 0
+    }
+
+    fn is_pure(&self) -> bool {
+        (self.count == 1 || self.count == -1) && self.check_sum == check(self.key_sum)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IbltDifference {
+    pub local: Vec<u64>,
+    pub remote: Vec<u64>,
+}
+
+impl IbltDifference {
+    pub fn is_empty(&self) -> bool {
+        self.local.is_empty() && self.remote.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Iblt {
+    cells: Vec<IbltCell>,
+}
+
+fn check(key: u64) -> u64 {
+    mix(key ^ CHECK_SEED)
+}
+
+impl Iblt {
+    pub fn new(cells: usize) -> Result<Self, CrustError> {
+        if cells == 0 || cells > MAX_IBLT_CELLS {
+            return Err(CrustError::InvalidOperation(format!(
+                "an iblt needs between 1 and {MAX_IBLT_CELLS} cells, got {cells}"
+            )));
+        }
+        let cells = cells.div_ceil(IBLT_HASH_COUNT) * IBLT_HASH_COUNT;
+        Ok(Iblt {
+            cells: vec![IbltCell::default(); cells],
+        })
+    }
+
+    pub fn from_items<I>(cells: usize, items: I) -> Result<Self, CrustError>
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        let mut iblt = Iblt::new(cells)?;
+        for item in items {
+            iblt.insert(item);
+        }
+        Ok(iblt)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.iter().all(IbltCell::is_empty)
+    }
+
+    fn indices(&self, key: u64) -> [usize; IBLT_HASH_COUNT] {
+        let width = self.cells.len() / IBLT_HASH_COUNT;
+        std::array::from_fn(|seed| {
+            let mut bytes = key.to_le_bytes().to_vec();
+            bytes.push(seed as u8);
+            seed * width + (mix(hash_bytes(&bytes)) % width as u64) as usize
+        })
+    }
+
+    fn update(&mut self, key: u64, count: i64) {
+        let check = check(key);
+        for index in self.indices(key) {
+            let cell = &mut self.cells[index];
+            cell.count += count;
+            cell.key_sum ^= key;
+            cell.check_sum ^= check;
+        }
+    }
+
+    pub fn insert(&mut self, key: u64) {
+        self.update(key, 1);
+    }
+
+    pub fn remove(&mut self, key: u64) {
+        self.update(key, -1);
+    }
+
+    pub fn subtract(&self, other: &Iblt) -> Result<Iblt, CrustError> {
+        if self.cells.len() != other.cells.len() {
+            return Err(CrustError::InvalidOperation(format!(
+                "cannot subtract an iblt of {} cells from one of {} cells",
+                other.cells.len(),
+                self.cells.len()
+            )));
+        }
+        Ok(Iblt {
+            cells: self
+                .cells
+                .iter()
+                .zip(&other.cells)
+                .map(|(left, right)| IbltCell {
+                    count: left.count - right.count,
+                    key_sum: left.key_sum ^ right.key_sum,
+                    check_sum: left.check_sum ^ right.check_sum,
+                })
+                .collect(),
+        })
+    }
+
+    pub fn decode(&self) -> Option<IbltDifference> {
+        let mut iblt = self.clone();
+        let mut difference = IbltDifference::default();
+        let mut pure: Vec<usize> = (0..iblt.cells.len())
+            .filter(|index| iblt.cells[*index].is_pure())
+            .collect();
+        while let Some(index) = pure.pop() {
+            let cell = &iblt.cells[index];
+            if !cell.is_pure() {
+                continue;
+            }
+            let (key, count) = (cell.key_sum, cell.count);
+            if count > 0 {
+                difference.local.push(key);
+            } else {
+                difference.remote.push(key);
+            }
+            iblt.update(key, -count);
+            pure.extend(
+                iblt.indices(key)
+                    .into_iter()
+                    .filter(|index| iblt.cells[*index].is_pure()),
+            );
+        }
+        iblt.is_empty().then_some(difference)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum ReconcileMessage<E, S> {
+    Sketch {
+        sketch: Iblt,
+    },
+    Entries {
+        keys: Vec<String>,
+        entries: E,
+        wanted: Option<Vec<u64>>,
+    },
+    Fallback {
+        state: S,
+        reply: bool,
+    },
+}
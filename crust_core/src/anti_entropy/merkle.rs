@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::CrustError;
+
+pub const DEFAULT_MERKLE_DEPTH: u32 = 8;
+pub const MAX_MERKLE_DEPTH: u32 = 16;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+pub fn hash_bytes(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+pub fn hash_value<T>(value: &T) -> u64
+where
+    T: Serialize,
+{
+    hash_bytes(&serde_json::to_vec(value).unwrap_or_default())
+}
+
+pub fn entry_key<T>(key: &T) -> String
+where
+    T: Serialize,
+{
+    serde_json::to_string(key).unwrap_or_default()
+}
+
+pub fn entry_hash(key: &str, digest: u64) -> u64 {
+    let mut bytes = key.as_bytes().to_vec();
+    bytes.extend_from_slice(&digest.to_le_bytes());
+    hash_bytes(&bytes)
+}
+
+pub fn hash_unordered<I>(hashes: I) -> u64
+where
+    I: IntoIterator<Item = u64>,
+{
+    hashes
+        .into_iter()
+        .fold(0, |combined, hash| combined.wrapping_add(mix(hash)))
+}
+
+pub fn mix(hash: u64) -> u64 {
+    let hash = (hash ^ (hash >> 33)).wrapping_mul(0xff51_afd7_ed55_8ccd);
+    let hash = (hash ^ (hash >> 33)).wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^ (hash >> 33)
+}
+
+pub fn merkle_bucket(depth: u32, key: &str) -> usize {
+    match depth {
+        0 => 0,
+        depth => (hash_bytes(key.as_bytes()) >> (64 - depth)) as usize,
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum MerkleMessage<E> {
+    Digest {
+        depth: u32,
+        level: u32,
+        nodes: Vec<(usize, u64)>,
+    },
+    Entries {
+        depth: u32,
+        buckets: Vec<usize>,
+        entries: E,
+        reply: bool,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MerkleStep {
+    Converged,
+    Descend {
+        level: u32,
+        nodes: Vec<(usize, u64)>,
+    },
+    Divergent {
+        buckets: Vec<usize>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleTree {
+    depth: u32,
+    levels: Vec<Vec<u64>>,
+}
+
+impl MerkleTree {
+    pub fn new<I, S>(depth: u32, entries: I) -> Result<Self, CrustError>
+    where
+        I: IntoIterator<Item = (S, u64)>,
+        S: AsRef<str>,
+    {
+        if depth > MAX_MERKLE_DEPTH {
+            return Err(CrustError::InvalidOperation(format!(
+                "merkle depth {depth} exceeds the maximum of {MAX_MERKLE_DEPTH}"
+            )));
+        }
+        let mut leaves = vec![0u64; 1 << depth];
+        for (key, hash) in entries {
+            let key = key.as_ref();
+            let leaf = &mut leaves[merkle_bucket(depth, key)];
+            *leaf = leaf.wrapping_add(mix(entry_hash(key, hash)));
+        }
+        let mut levels = vec![leaves];
+        while levels[0].len() > 1 {
+            let parents = levels[0]
+                .chunks(2)
+                .map(|children| match children {
+                    [0, 0] => 0,
+                    [left, right] => {
+                        let mut bytes = left.to_le_bytes().to_vec();
+                        bytes.extend_from_slice(&right.to_le_bytes());
+                        hash_bytes(&bytes)
+                    }
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.insert(0, parents);
+        }
+        Ok(MerkleTree { depth, levels })
+    }
+
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    pub fn root(&self) -> u64 {
+        self.levels[0][0]
+    }
+
+    pub fn hash(&self, level: u32, index: usize) -> Option<u64> {
+        self.levels.get(level as usize)?.get(index).copied()
+    }
+
+    pub fn digest<E>(&self) -> MerkleMessage<E> {
+        MerkleMessage::Digest {
+            depth: self.depth,
+            level: 0,
+            nodes: vec![(0, self.root())],
+        }
+    }
+
+    pub fn compare(&self, level: u32, nodes: &[(usize, u64)]) -> MerkleStep {
+        let divergent: Vec<usize> = nodes
+            .iter()
+            .filter(|(index, hash)| self.hash(level, *index).is_some_and(|local| local != *hash))
+            .map(|(index, _)| *index)
+            .collect();
+        if divergent.is_empty() {
+            return MerkleStep::Converged;
+        }
+        if level >= self.depth {
+            return MerkleStep::Divergent { buckets: divergent };
+        }
+        let level = level + 1;
+        MerkleStep::Descend {
+            level,
+            nodes: divergent
+                .iter()
+                .flat_map(|index| [index * 2, index * 2 + 1])
+                .filter_map(|index| Some((index, self.hash(level, index)?)))
+                .collect(),
+        }
+    }
+}
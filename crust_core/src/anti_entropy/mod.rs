@@ -0,0 +1,3 @@
+pub mod delta_interval;
+pub mod iblt;
+pub mod merkle;
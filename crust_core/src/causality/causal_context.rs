@@ -0,0 +1,119 @@
+use std::{collections::HashSet, hash::Hash};
+
+use serde::{Deserialize, Serialize};
+
+use crate::causality::{dot::Dot, version_vector::VersionVector, CausalOrdering};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CausalContext<K>
+where
+    K: Eq + Hash,
+{
+    pub version_vector: VersionVector<K>,
+    pub dot_cloud: HashSet<Dot<K>>,
+}
+
+impl<K> Default for CausalContext<K>
+where
+    K: Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> CausalContext<K>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn new() -> Self {
+        CausalContext {
+            version_vector: VersionVector::new(),
+            dot_cloud: HashSet::new(),
+        }
+    }
+
+    pub fn from_version_vector(version_vector: VersionVector<K>) -> Self {
+        CausalContext {
+            version_vector,
+            dot_cloud: HashSet::new(),
+        }
+    }
+
+    pub fn contains(&self, dot: &Dot<K>) -> bool {
+        self.version_vector.contains(dot) || self.dot_cloud.contains(dot)
+    }
+
+    pub fn max(&self, replica: &K) -> u64 {
+        self.dot_cloud
+            .iter()
+            .filter(|dot| dot.replica == *replica)
+            .map(|dot| dot.counter)
+            .fold(self.version_vector.get(replica), u64::max)
+    }
+
+    pub fn next_dot(&mut self, replica: K) -> Dot<K> {
+        let dot = Dot::new(replica.clone(), self.max(&replica) + 1);
+        self.insert(dot.clone());
+        dot
+    }
+
+    pub fn insert(&mut self, dot: Dot<K>) {
+        if !self.version_vector.contains(&dot) {
+            self.dot_cloud.insert(dot);
+            self.compact();
+        }
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        self.version_vector.merge(&other.version_vector);
+        self.dot_cloud.extend(other.dot_cloud.iter().cloned());
+        self.compact();
+    }
+
+    pub fn compact(&mut self) {
+        loop {
+            let mut changed = false;
+            let mut remaining = HashSet::new();
+            for dot in self.dot_cloud.drain() {
+                let current = self.version_vector.get(&dot.replica);
+                if dot.counter == current + 1 {
+                    self.version_vector.observe(&dot);
+                    changed = true;
+                } else if dot.counter > current {
+                    remaining.insert(dot);
+                }
+            }
+            self.dot_cloud = remaining;
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    pub fn dominates(&self, other: &Self) -> bool {
+        other.version_vector.clock.iter().all(|(replica, counter)| {
+            (self.version_vector.get(replica) + 1..=*counter)
+                .all(|n| self.dot_cloud.contains(&Dot::new(replica.clone(), n)))
+        }) && other.dot_cloud.iter().all(|dot| self.contains(dot))
+    }
+
+    pub fn compare(&self, other: &Self) -> CausalOrdering {
+        match (self.dominates(other), other.dominates(self)) {
+            (true, true) => CausalOrdering::Equal,
+            (true, false) => CausalOrdering::After,
+            (false, true) => CausalOrdering::Before,
+            (false, false) => CausalOrdering::Concurrent,
+        }
+    }
+
+    pub fn dots(&self) -> impl Iterator<Item = Dot<K>> + '_ {
+        self.version_vector
+            .dots()
+            .chain(self.dot_cloud.iter().cloned())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.version_vector.is_empty() && self.dot_cloud.is_empty()
+    }
+}
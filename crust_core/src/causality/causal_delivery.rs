@@ -0,0 +1,107 @@
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+use crate::causality::{dot::Dot, version_vector::VersionVector};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CausalMessage<K, T>
+where
+    K: Eq + Hash,
+{
+    pub sender: K,
+    pub clock: VersionVector<K>,
+    pub payload: T,
+}
+
+#[derive(Clone, Debug)]
+pub struct CausalDeliveryBuffer<K, T>
+where
+    K: Eq + Hash,
+{
+    replica: K,
+    delivered: VersionVector<K>,
+    pending: Vec<CausalMessage<K, T>>,
+}
+
+impl<K, T> CausalDeliveryBuffer<K, T>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn new(replica: K) -> Self {
+        CausalDeliveryBuffer {
+            replica,
+            delivered: VersionVector::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn restore(replica: K, delivered: VersionVector<K>) -> Self {
+        CausalDeliveryBuffer {
+            replica,
+            delivered,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn replica(&self) -> &K {
+        &self.replica
+    }
+
+    pub fn delivered(&self) -> &VersionVector<K> {
+        &self.delivered
+    }
+
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn prepare(&mut self, payload: T) -> CausalMessage<K, T> {
+        self.delivered.increment(self.replica.clone());
+        CausalMessage {
+            sender: self.replica.clone(),
+            clock: self.delivered.clone(),
+            payload,
+        }
+    }
+
+    pub fn is_deliverable(&self, message: &CausalMessage<K, T>) -> bool {
+        message.clock.get(&message.sender) == self.delivered.get(&message.sender) + 1
+            && message
+                .clock
+                .clock
+                .iter()
+                .filter(|(replica, _)| **replica != message.sender)
+                .all(|(replica, counter)| *counter <= self.delivered.get(replica))
+    }
+
+    fn is_duplicate(&self, message: &CausalMessage<K, T>) -> bool {
+        message.clock.get(&message.sender) <= self.delivered.get(&message.sender)
+            || self
+                .pending
+                .iter()
+                .any(|pending| pending.sender == message.sender && pending.clock == message.clock)
+    }
+
+    pub fn receive(&mut self, message: CausalMessage<K, T>) -> Vec<T> {
+        if self.is_duplicate(&message) {
+            return Vec::new();
+        }
+        self.pending.push(message);
+
+        let mut released = Vec::new();
+        while let Some(index) = self
+            .pending
+            .iter()
+            .position(|message| self.is_deliverable(message))
+        {
+            let message = self.pending.remove(index);
+            self.delivered.observe(&Dot::new(
+                message.sender.clone(),
+                message.clock.get(&message.sender),
+            ));
+            released.push(message.payload);
+        }
+        released
+    }
+}
@@ -0,0 +1,61 @@
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    causality::{causal_context::CausalContext, version_vector::VersionVector},
+    operation::OperationId,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OperationDeduplicator<K>
+where
+    K: Eq + Hash,
+{
+    seen: CausalContext<K>,
+}
+
+impl<K> Default for OperationDeduplicator<K>
+where
+    K: Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> OperationDeduplicator<K>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn new() -> Self {
+        OperationDeduplicator {
+            seen: CausalContext::new(),
+        }
+    }
+
+    pub fn is_duplicate(&self, operation_id: &OperationId<K>) -> bool {
+        self.seen.contains(operation_id)
+    }
+
+    pub fn record(&mut self, operation_id: OperationId<K>) -> bool {
+        if self.is_duplicate(&operation_id) {
+            return false;
+        }
+        self.seen.insert(operation_id);
+        true
+    }
+
+    pub fn compact(&mut self, stable: &VersionVector<K>) {
+        self.seen.version_vector.merge(stable);
+        self.seen.compact();
+    }
+
+    pub fn seen(&self) -> &CausalContext<K> {
+        &self.seen
+    }
+
+    pub fn pending_len(&self) -> usize {
+        self.seen.dot_cloud.len()
+    }
+}
@@ -0,0 +1,25 @@
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Dot<K> {
+    pub replica: K,
+    pub counter: u64,
+}
+
+impl<K> Dot<K>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn new(replica: K, counter: u64) -> Self {
+        Dot { replica, counter }
+    }
+
+    pub fn next(&self) -> Self {
+        Dot {
+            replica: self.replica.clone(),
+            counter: self.counter + 1,
+        }
+    }
+}
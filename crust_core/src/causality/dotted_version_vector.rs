@@ -0,0 +1,60 @@
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+use crate::causality::{dot::Dot, version_vector::VersionVector, CausalOrdering};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DottedVersionVector<K>
+where
+    K: Eq + Hash,
+{
+    pub dot: Dot<K>,
+    pub context: VersionVector<K>,
+}
+
+impl<K> DottedVersionVector<K>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn new(dot: Dot<K>, context: VersionVector<K>) -> Self {
+        DottedVersionVector { dot, context }
+    }
+
+    pub fn update(context: &VersionVector<K>, local: &VersionVector<K>, replica: K) -> Self {
+        let counter = context.get(&replica).max(local.get(&replica)) + 1;
+        DottedVersionVector {
+            dot: Dot::new(replica, counter),
+            context: context.clone(),
+        }
+    }
+
+    pub fn contains(&self, dot: &Dot<K>) -> bool {
+        self.dot == *dot || self.context.contains(dot)
+    }
+
+    pub fn descends(&self, other: &Self) -> bool {
+        self.contains(&other.dot)
+            && other.context.clock.iter().all(|(replica, counter)| {
+                let covered = self.context.get(replica);
+                covered >= *counter
+                    || (covered + 1 == *counter
+                        && self.contains(&Dot::new(replica.clone(), *counter)))
+            })
+    }
+
+    pub fn compare(&self, other: &Self) -> CausalOrdering {
+        match (self.descends(other), other.descends(self)) {
+            (true, true) => CausalOrdering::Equal,
+            (true, false) => CausalOrdering::After,
+            (false, true) => CausalOrdering::Before,
+            (false, false) => CausalOrdering::Concurrent,
+        }
+    }
+
+    pub fn to_version_vector(&self) -> VersionVector<K> {
+        let mut version_vector = self.context.clone();
+        version_vector.observe(&self.dot);
+        version_vector
+    }
+}
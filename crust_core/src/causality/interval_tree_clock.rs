@@ -0,0 +1,319 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{causality::CausalOrdering, error::CrustError};
+
+const GROW_PENALTY: u64 = 1000;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum IdTree {
+    Zero,
+    One,
+    Node(Box<IdTree>, Box<IdTree>),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum EventTree {
+    Leaf(u64),
+    Node(u64, Box<EventTree>, Box<EventTree>),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IntervalTreeClock {
+    pub id: IdTree,
+    pub event: EventTree,
+}
+
+impl IdTree {
+    fn node(left: IdTree, right: IdTree) -> IdTree {
+        match (left, right) {
+            (IdTree::Zero, IdTree::Zero) => IdTree::Zero,
+            (IdTree::One, IdTree::One) => IdTree::One,
+            (left, right) => IdTree::Node(Box::new(left), Box::new(right)),
+        }
+    }
+
+    fn split(&self) -> (IdTree, IdTree) {
+        match self {
+            IdTree::Zero => (IdTree::Zero, IdTree::Zero),
+            IdTree::One => (
+                IdTree::node(IdTree::One, IdTree::Zero),
+                IdTree::node(IdTree::Zero, IdTree::One),
+            ),
+            IdTree::Node(left, right) => match (left.as_ref(), right.as_ref()) {
+                (IdTree::Zero, right) => {
+                    let (right_1, right_2) = right.split();
+                    (
+                        IdTree::node(IdTree::Zero, right_1),
+                        IdTree::node(IdTree::Zero, right_2),
+                    )
+                }
+                (left, IdTree::Zero) => {
+                    let (left_1, left_2) = left.split();
+                    (
+                        IdTree::node(left_1, IdTree::Zero),
+                        IdTree::node(left_2, IdTree::Zero),
+                    )
+                }
+                (left, right) => (
+                    IdTree::node(left.clone(), IdTree::Zero),
+                    IdTree::node(IdTree::Zero, right.clone()),
+                ),
+            },
+        }
+    }
+
+    fn sum(&self, other: &IdTree) -> IdTree {
+        match (self, other) {
+            (IdTree::Zero, id) | (id, IdTree::Zero) => id.clone(),
+            (IdTree::Node(left_1, right_1), IdTree::Node(left_2, right_2)) => {
+                IdTree::node(left_1.sum(left_2), right_1.sum(right_2))
+            }
+            _ => IdTree::One,
+        }
+    }
+}
+
+impl EventTree {
+    fn node(base: u64, left: EventTree, right: EventTree) -> EventTree {
+        match (&left, &right) {
+            (EventTree::Leaf(l), EventTree::Leaf(r)) if l == r => EventTree::Leaf(base + l),
+            _ => {
+                let min = left.min().min(right.min());
+                EventTree::Node(
+                    base + min,
+                    Box::new(left.sink(min)),
+                    Box::new(right.sink(min)),
+                )
+            }
+        }
+    }
+
+    fn base(&self) -> u64 {
+        match self {
+            EventTree::Leaf(n) | EventTree::Node(n, _, _) => *n,
+        }
+    }
+
+    pub fn min(&self) -> u64 {
+        match self {
+            EventTree::Leaf(n) => *n,
+            EventTree::Node(n, left, right) => n + left.min().min(right.min()),
+        }
+    }
+
+    pub fn max(&self) -> u64 {
+        match self {
+            EventTree::Leaf(n) => *n,
+            EventTree::Node(n, left, right) => n + left.max().max(right.max()),
+        }
+    }
+
+    fn lift(&self, m: u64) -> EventTree {
+        match self {
+            EventTree::Leaf(n) => EventTree::Leaf(n + m),
+            EventTree::Node(n, left, right) => EventTree::Node(n + m, left.clone(), right.clone()),
+        }
+    }
+
+    fn sink(&self, m: u64) -> EventTree {
+        match self {
+            EventTree::Leaf(n) => EventTree::Leaf(n - m),
+            EventTree::Node(n, left, right) => EventTree::Node(n - m, left.clone(), right.clone()),
+        }
+    }
+
+    fn leq(&self, other: &EventTree) -> bool {
+        match (self, other) {
+            (EventTree::Leaf(n1), _) => *n1 <= other.base(),
+            (EventTree::Node(n1, left_1, right_1), EventTree::Leaf(n2)) => {
+                n1 <= n2
+                    && left_1.lift(*n1).leq(&EventTree::Leaf(*n2))
+                    && right_1.lift(*n1).leq(&EventTree::Leaf(*n2))
+            }
+            (EventTree::Node(n1, left_1, right_1), EventTree::Node(n2, left_2, right_2)) => {
+                n1 <= n2
+                    && left_1.lift(*n1).leq(&left_2.lift(*n2))
+                    && right_1.lift(*n1).leq(&right_2.lift(*n2))
+            }
+        }
+    }
+
+    fn join(&self, other: &EventTree) -> EventTree {
+        match (self, other) {
+            (EventTree::Leaf(n1), EventTree::Leaf(n2)) => EventTree::Leaf(*n1.max(n2)),
+            (EventTree::Leaf(n1), EventTree::Node(..)) => EventTree::Node(
+                *n1,
+                Box::new(EventTree::Leaf(0)),
+                Box::new(EventTree::Leaf(0)),
+            )
+            .join(other),
+            (EventTree::Node(..), EventTree::Leaf(n2)) => self.join(&EventTree::Node(
+                *n2,
+                Box::new(EventTree::Leaf(0)),
+                Box::new(EventTree::Leaf(0)),
+            )),
+            (EventTree::Node(n1, left_1, right_1), EventTree::Node(n2, left_2, right_2)) => {
+                if n1 > n2 {
+                    return other.join(self);
+                }
+                let lift = n2 - n1;
+                EventTree::node(
+                    *n1,
+                    left_1.join(&left_2.lift(lift)),
+                    right_1.join(&right_2.lift(lift)),
+                )
+            }
+        }
+    }
+
+    fn fill(&self, id: &IdTree) -> EventTree {
+        match (id, self) {
+            (IdTree::Zero, _) => self.clone(),
+            (IdTree::One, _) => EventTree::Leaf(self.max()),
+            (_, EventTree::Leaf(_)) => self.clone(),
+            (IdTree::Node(id_left, id_right), EventTree::Node(n, left, right)) => {
+                match (id_left.as_ref(), id_right.as_ref()) {
+                    (IdTree::One, id_right) => {
+                        let right = right.fill(id_right);
+                        let left = EventTree::Leaf(left.max().max(right.min()));
+                        EventTree::node(*n, left, right)
+                    }
+                    (id_left, IdTree::One) => {
+                        let left = left.fill(id_left);
+                        let right = EventTree::Leaf(right.max().max(left.min()));
+                        EventTree::node(*n, left, right)
+                    }
+                    (id_left, id_right) => {
+                        EventTree::node(*n, left.fill(id_left), right.fill(id_right))
+                    }
+                }
+            }
+        }
+    }
+
+    fn grow(&self, id: &IdTree) -> (EventTree, u64) {
+        match (id, self) {
+            (IdTree::One, EventTree::Leaf(n)) => (EventTree::Leaf(n + 1), 0),
+            (_, EventTree::Leaf(n)) => {
+                let (event, cost) = EventTree::Node(
+                    *n,
+                    Box::new(EventTree::Leaf(0)),
+                    Box::new(EventTree::Leaf(0)),
+                )
+                .grow(id);
+                (event, cost + GROW_PENALTY)
+            }
+            (IdTree::Node(id_left, id_right), EventTree::Node(n, left, right)) => {
+                match (id_left.as_ref(), id_right.as_ref()) {
+                    (IdTree::Zero, id_right) => {
+                        let (right, cost) = right.grow(id_right);
+                        (EventTree::Node(*n, left.clone(), Box::new(right)), cost + 1)
+                    }
+                    (id_left, IdTree::Zero) => {
+                        let (left, cost) = left.grow(id_left);
+                        (EventTree::Node(*n, Box::new(left), right.clone()), cost + 1)
+                    }
+                    (id_left, id_right) => {
+                        let (grown_left, cost_left) = left.grow(id_left);
+                        let (grown_right, cost_right) = right.grow(id_right);
+                        if cost_left < cost_right {
+                            (
+                                EventTree::Node(*n, Box::new(grown_left), right.clone()),
+                                cost_left + 1,
+                            )
+                        } else {
+                            (
+                                EventTree::Node(*n, left.clone(), Box::new(grown_right)),
+                                cost_right + 1,
+                            )
+                        }
+                    }
+                }
+            }
+            (_, EventTree::Node(..)) => (self.clone(), u64::MAX / 2),
+        }
+    }
+}
+
+impl Default for IntervalTreeClock {
+    fn default() -> Self {
+        Self::seed()
+    }
+}
+
+impl IntervalTreeClock {
+    pub fn seed() -> Self {
+        IntervalTreeClock {
+            id: IdTree::One,
+            event: EventTree::Leaf(0),
+        }
+    }
+
+    pub fn is_anonymous(&self) -> bool {
+        self.id == IdTree::Zero
+    }
+
+    pub fn fork(&self) -> (Self, Self) {
+        let (id_1, id_2) = self.id.split();
+        (
+            IntervalTreeClock {
+                id: id_1,
+                event: self.event.clone(),
+            },
+            IntervalTreeClock {
+                id: id_2,
+                event: self.event.clone(),
+            },
+        )
+    }
+
+    pub fn peek(&self) -> Self {
+        IntervalTreeClock {
+            id: IdTree::Zero,
+            event: self.event.clone(),
+        }
+    }
+
+    pub fn join(&self, other: &Self) -> Self {
+        IntervalTreeClock {
+            id: self.id.sum(&other.id),
+            event: self.event.join(&other.event),
+        }
+    }
+
+    pub fn receive(&mut self, other: &Self) {
+        self.event = self.event.join(&other.event);
+    }
+
+    pub fn event(&mut self) -> Result<(), CrustError> {
+        if self.is_anonymous() {
+            return Err(CrustError::InvalidOperation(
+                "anonymous stamp cannot register events".to_string(),
+            ));
+        }
+        let filled = self.event.fill(&self.id);
+        self.event = if filled != self.event {
+            filled
+        } else {
+            self.event.grow(&self.id).0
+        };
+        Ok(())
+    }
+
+    pub fn leq(&self, other: &Self) -> bool {
+        self.event.leq(&other.event)
+    }
+
+    pub fn compare(&self, other: &Self) -> CausalOrdering {
+        match (self.leq(other), other.leq(self)) {
+            (true, true) => CausalOrdering::Equal,
+            (true, false) => CausalOrdering::Before,
+            (false, true) => CausalOrdering::After,
+            (false, false) => CausalOrdering::Concurrent,
+        }
+    }
+
+    pub fn is_concurrent(&self, other: &Self) -> bool {
+        self.compare(other) == CausalOrdering::Concurrent
+    }
+}
@@ -0,0 +1,16 @@
+pub mod causal_context;
+pub mod causal_delivery;
+pub mod deduplication;
+pub mod dot;
+pub mod dotted_version_vector;
+pub mod interval_tree_clock;
+pub mod stability;
+pub mod version_vector;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CausalOrdering {
+    Equal,
+    Before,
+    After,
+    Concurrent,
+}
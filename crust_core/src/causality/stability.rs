@@ -0,0 +1,85 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::causality::{
+    deduplication::OperationDeduplicator, dot::Dot, version_vector::VersionVector,
+};
+
+pub trait StablePurge<K>
+where
+    K: Eq + Hash,
+{
+    fn purge_stable(&mut self, stable: &VersionVector<K>) -> usize;
+}
+
+#[derive(Clone, Debug)]
+pub struct CausalStability<K>
+where
+    K: Eq + Hash,
+{
+    replica: K,
+    observed: HashMap<K, VersionVector<K>>,
+}
+
+impl<K> CausalStability<K>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn new(replica: K) -> Self {
+        CausalStability {
+            observed: HashMap::from([(replica.clone(), VersionVector::new())]),
+            replica,
+        }
+    }
+
+    pub fn replica(&self) -> &K {
+        &self.replica
+    }
+
+    pub fn add_replica(&mut self, replica: K) {
+        self.observed.entry(replica).or_default();
+    }
+
+    pub fn remove_replica(&mut self, replica: &K) {
+        if *replica != self.replica {
+            self.observed.remove(replica);
+        }
+    }
+
+    pub fn replicas(&self) -> impl Iterator<Item = &K> {
+        self.observed.keys()
+    }
+
+    pub fn observe(&mut self, replica: K, version_vector: &VersionVector<K>) {
+        self.observed
+            .entry(replica)
+            .or_default()
+            .merge(version_vector);
+    }
+
+    pub fn observed(&self, replica: &K) -> Option<&VersionVector<K>> {
+        self.observed.get(replica)
+    }
+
+    pub fn stable(&self) -> VersionVector<K> {
+        let mut observed = self.observed.values();
+        let first = observed.next().cloned().unwrap_or_default();
+        observed.fold(first, |stable, version_vector| stable.meet(version_vector))
+    }
+
+    pub fn is_stable(&self, dot: &Dot<K>) -> bool {
+        self.observed
+            .values()
+            .all(|version_vector| version_vector.contains(dot))
+    }
+}
+
+impl<K> StablePurge<K> for OperationDeduplicator<K>
+where
+    K: Eq + Hash + Clone,
+{
+    fn purge_stable(&mut self, stable: &VersionVector<K>) -> usize {
+        let pending = self.pending_len();
+        self.compact(stable);
+        pending - self.pending_len()
+    }
+}
@@ -0,0 +1,134 @@
+use std::{cmp::Ordering, collections::HashMap, hash::Hash};
+
+use serde::{Deserialize, Serialize};
+
+use crate::causality::{dot::Dot, CausalOrdering};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VersionVector<K>
+where
+    K: Eq + Hash,
+{
+    pub clock: HashMap<K, u64>,
+}
+
+impl<K> Default for VersionVector<K>
+where
+    K: Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> VersionVector<K>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn new() -> Self {
+        VersionVector {
+            clock: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, replica: &K) -> u64 {
+        self.clock.get(replica).copied().unwrap_or(0)
+    }
+
+    pub fn increment(&mut self, replica: K) -> Dot<K> {
+        let counter = self.clock.entry(replica.clone()).or_insert(0);
+        *counter += 1;
+        Dot::new(replica, *counter)
+    }
+
+    pub fn observe(&mut self, dot: &Dot<K>) {
+        if dot.counter == 0 {
+            return;
+        }
+        let counter = self.clock.entry(dot.replica.clone()).or_insert(0);
+        *counter = (*counter).max(dot.counter);
+    }
+
+    pub fn contains(&self, dot: &Dot<K>) -> bool {
+        dot.counter <= self.get(&dot.replica)
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        for (replica, counter) in other.clock.iter().filter(|(_, counter)| **counter > 0) {
+            let current = self.clock.entry(replica.clone()).or_insert(0);
+            *current = (*current).max(*counter);
+        }
+    }
+
+    pub fn meet(&self, other: &Self) -> Self {
+        let clock = self
+            .clock
+            .iter()
+            .filter_map(|(replica, counter)| {
+                let min = (*counter).min(other.get(replica));
+                (min > 0).then(|| (replica.clone(), min))
+            })
+            .collect();
+        VersionVector { clock }
+    }
+
+    pub fn dominates(&self, other: &Self) -> bool {
+        other
+            .clock
+            .iter()
+            .all(|(replica, counter)| self.get(replica) >= *counter)
+    }
+
+    pub fn compare(&self, other: &Self) -> CausalOrdering {
+        match (self.dominates(other), other.dominates(self)) {
+            (true, true) => CausalOrdering::Equal,
+            (true, false) => CausalOrdering::After,
+            (false, true) => CausalOrdering::Before,
+            (false, false) => CausalOrdering::Concurrent,
+        }
+    }
+
+    pub fn is_concurrent(&self, other: &Self) -> bool {
+        self.compare(other) == CausalOrdering::Concurrent
+    }
+
+    pub fn dots(&self) -> impl Iterator<Item = Dot<K>> + '_ {
+        self.clock.iter().flat_map(|(replica, counter)| {
+            (1..=*counter).map(move |n| Dot::new(replica.clone(), n))
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clock.values().all(|counter| *counter == 0)
+    }
+}
+
+impl<K> PartialEq for VersionVector<K>
+where
+    K: Eq + Hash,
+{
+    fn eq(&self, other: &Self) -> bool {
+        let covers = |left: &Self, right: &Self| {
+            left.clock.iter().all(|(replica, counter)| {
+                right.clock.get(replica).copied().unwrap_or(0) == *counter
+            })
+        };
+        covers(self, other) && covers(other, self)
+    }
+}
+
+impl<K> Eq for VersionVector<K> where K: Eq + Hash {}
+
+impl<K> PartialOrd for VersionVector<K>
+where
+    K: Eq + Hash + Clone,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.compare(other) {
+            CausalOrdering::Equal => Some(Ordering::Equal),
+            CausalOrdering::Before => Some(Ordering::Less),
+            CausalOrdering::After => Some(Ordering::Greater),
+            CausalOrdering::Concurrent => None,
+        }
+    }
+}
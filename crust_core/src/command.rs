@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use crate::operation::{
+    CounterOperation, CrdtOperation, GraphOperation, MapOperation, SetOperation, TextOperation,
+};
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum CounterInnerCommand<K> {
     Increment { value: K },
@@ -13,6 +17,11 @@ pub enum GraphInnerCommand<K> {
     RemoveEdge { from: K, to: K },
 }
 #[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum MapInnerCommand<K> {
+    Put { key: K, value: K },
+    Remove { key: K },
+}
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum SetInnerCommand<K> {
     Add { value: K },
     Remove { value: K },
@@ -27,6 +36,53 @@ pub enum TextInnerCommand<K> {
 pub enum CrdtInnerCommand<K> {
     Counter(CounterInnerCommand<K>),
     Graph(GraphInnerCommand<K>),
+    Map(MapInnerCommand<K>),
     Set(SetInnerCommand<K>),
     Text(TextInnerCommand<K>),
 }
+
+impl<K> From<&CrdtOperation<K>> for CrdtInnerCommand<K>
+where
+    K: Clone,
+{
+    fn from(operation: &CrdtOperation<K>) -> Self {
+        match operation.clone() {
+            CrdtOperation::Counter(CounterOperation::Increment { value }) => {
+                CrdtInnerCommand::Counter(CounterInnerCommand::Increment { value })
+            }
+            CrdtOperation::Counter(CounterOperation::Decrement { value }) => {
+                CrdtInnerCommand::Counter(CounterInnerCommand::Decrement { value })
+            }
+            CrdtOperation::Graph(GraphOperation::AddNode { value }) => {
+                CrdtInnerCommand::Graph(GraphInnerCommand::AddNode { value })
+            }
+            CrdtOperation::Graph(GraphOperation::RemoveNode { value }) => {
+                CrdtInnerCommand::Graph(GraphInnerCommand::RemoveNode { value })
+            }
+            CrdtOperation::Graph(GraphOperation::AddEdge { from, to }) => {
+                CrdtInnerCommand::Graph(GraphInnerCommand::AddEdge { from, to })
+            }
+            CrdtOperation::Graph(GraphOperation::RemoveEdge { from, to }) => {
+                CrdtInnerCommand::Graph(GraphInnerCommand::RemoveEdge { from, to })
+            }
+            CrdtOperation::Map(MapOperation::Put { key, value }) => {
+                CrdtInnerCommand::Map(MapInnerCommand::Put { key, value })
+            }
+            CrdtOperation::Map(MapOperation::Remove { key }) => {
+                CrdtInnerCommand::Map(MapInnerCommand::Remove { key })
+            }
+            CrdtOperation::Set(SetOperation::Add { value }) => {
+                CrdtInnerCommand::Set(SetInnerCommand::Add { value })
+            }
+            CrdtOperation::Set(SetOperation::Remove { value }) => {
+                CrdtInnerCommand::Set(SetInnerCommand::Remove { value })
+            }
+            CrdtOperation::Text(TextOperation::Insert { pos, value }) => {
+                CrdtInnerCommand::Text(TextInnerCommand::Insert { pos, value })
+            }
+            CrdtOperation::Text(TextOperation::Delete { pos, value }) => {
+                CrdtInnerCommand::Text(TextInnerCommand::Delete { pos, value })
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::fmt::Debug;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::CrustError;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum ConstraintRule<K> {
+    MaxValue(i64),
+    MinValue(i64),
+    RangeValue(i64, i64),
+    MaxCardinality(usize),
+    AllowedValues(Vec<K>),
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RepairPolicy {
+    #[default]
+    Accept,
+    Reject,
+    Repair,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstraintView<K> {
+    pub value: Option<i64>,
+    pub elements: Option<Vec<K>>,
+}
+
+impl<K> Default for ConstraintView<K> {
+    fn default() -> Self {
+        ConstraintView {
+            value: None,
+            elements: None,
+        }
+    }
+}
+
+impl<K> ConstraintRule<K>
+where
+    K: PartialEq + Debug,
+{
+    pub fn applies_to(&self, view: &ConstraintView<K>) -> bool {
+        match self {
+            ConstraintRule::MaxValue(_)
+            | ConstraintRule::MinValue(_)
+            | ConstraintRule::RangeValue(_, _) => view.value.is_some(),
+            ConstraintRule::MaxCardinality(_) | ConstraintRule::AllowedValues(_) => {
+                view.elements.is_some()
+            }
+        }
+    }
+
+    pub fn check(&self, view: &ConstraintView<K>) -> Result<(), CrustError> {
+        let violation = |reason: String| Err(CrustError::ConstraintViolation(reason));
+        match (self, view) {
+            (
+                ConstraintRule::MaxValue(max),
+                ConstraintView {
+                    value: Some(value), ..
+                },
+            ) if value > max => violation(format!("value {value} exceeds maximum {max}")),
+            (
+                ConstraintRule::MinValue(min),
+                ConstraintView {
+                    value: Some(value), ..
+                },
+            ) if value < min => violation(format!("value {value} is below minimum {min}")),
+            (
+                ConstraintRule::RangeValue(min, max),
+                ConstraintView {
+                    value: Some(value), ..
+                },
+            ) if value < min || value > max => {
+                violation(format!("value {value} is outside range {min}..={max}"))
+            }
+            (
+                ConstraintRule::MaxCardinality(max),
+                ConstraintView {
+                    elements: Some(elements),
+                    ..
+                },
+            ) if elements.len() > *max => violation(format!(
+                "{} elements exceed maximum cardinality {max}",
+                elements.len()
+            )),
+            (
+                ConstraintRule::AllowedValues(allowed),
+                ConstraintView {
+                    elements: Some(elements),
+                    ..
+                },
+            ) => match elements.iter().find(|element| !allowed.contains(element)) {
+                Some(element) => violation(format!("{:?} is not an allowed value", element)),
+                None => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+pub fn check_rules<K>(
+    rules: &[ConstraintRule<K>],
+    view: &ConstraintView<K>,
+) -> Result<(), CrustError>
+where
+    K: PartialEq + Debug,
+{
+    rules.iter().try_for_each(|rule| rule.check(view))
+}
+
+pub fn value_bounds<K>(rules: &[ConstraintRule<K>]) -> (Option<i64>, Option<i64>) {
+    rules
+        .iter()
+        .fold((None, None), |(min, max), rule| match rule {
+            ConstraintRule::MinValue(value) => (min.max(Some(*value)), max),
+            ConstraintRule::MaxValue(value) => (min, tighter_max(max, *value)),
+            ConstraintRule::RangeValue(low, high) => (min.max(Some(*low)), tighter_max(max, *high)),
+            _ => (min, max),
+        })
+}
+
+pub fn excess_elements<K>(rules: &[ConstraintRule<K>], elements: &[K]) -> Vec<K>
+where
+    K: PartialEq + Clone,
+{
+    let (mut kept, mut excess): (Vec<K>, Vec<K>) = elements.iter().cloned().partition(|element| {
+        rules.iter().all(|rule| match rule {
+            ConstraintRule::AllowedValues(allowed) => allowed.contains(element),
+            _ => true,
+        })
+    });
+    let cardinality = rules
+        .iter()
+        .filter_map(|rule| match rule {
+            ConstraintRule::MaxCardinality(max) => Some(*max),
+            _ => None,
+        })
+        .min();
+    if let Some(max) = cardinality.filter(|max| kept.len() > *max) {
+        excess.extend(kept.split_off(max));
+    }
+    excess
+}
+
+fn tighter_max(current: Option<i64>, value: i64) -> Option<i64> {
+    Some(current.map_or(value, |current| current.min(value)))
+}
@@ -3,17 +3,64 @@ use std::hash::Hash;
 
 use serde::{Deserialize, Serialize};
 
+use serde_json::{json, Value};
+
 use crate::{
-    operation::CounterOperation,
-    sync::{Crdt, DeltaBased, OperationBased, StateBased},
+    causality::{dot::Dot, version_vector::VersionVector},
+    command::{CounterInnerCommand, CrdtInnerCommand},
+    constraint::{check_rules, ConstraintRule, ConstraintView},
+    error::CrustError,
+    operation::{CounterOperation, CrdtOperation},
+    registry::{from_value, to_value, Constrained, CrdtKey, CrdtObject, Retirable},
+    sync::{ConstraintEnforcing, Crdt, DeltaBased, OperationBased, StateBased},
 };
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(bound(deserialize = "K: Deserialize<'de> + Eq + Hash"))]
 pub struct GCounter<K>
 where
     K: Eq + Hash,
 {
     pub counter: HashMap<K, u64>,
+    #[serde(default)]
+    pub retirement: GCounterRetirement<K>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GCounterRetirement<K>
+where
+    K: Eq + Hash,
+{
+    pub epoch: u64,
+    pub base: u64,
+    pub retired: HashMap<K, u64>,
+    pub folded: HashMap<K, u64>,
+}
+
+pub fn migrate_gcounter_v1(mut state: Value) -> Result<Value, CrustError> {
+    if let Some(state) = state.as_object_mut() {
+        if !state.contains_key("retirement") {
+            state.insert(
+                "retirement".to_string(),
+                to_value(&GCounterRetirement::<String>::default())?,
+            );
+        }
+    }
+    Ok(state)
+}
+
+impl<K> Default for GCounterRetirement<K>
+where
+    K: Eq + Hash,
+{
+    fn default() -> Self {
+        GCounterRetirement {
+            epoch: 0,
+            base: 0,
+            retired: HashMap::new(),
+            folded: HashMap::new(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -29,8 +76,94 @@ where
     K: Eq + Hash,
 {
     pub fn increment(&mut self, key: K) {
+        let retired = self.retirement.retired.remove(&key).unwrap_or(0);
         let current_value = self.counter.entry(key).or_insert(0);
-        *current_value += 1;
+        *current_value = (*current_value).max(retired) + 1;
+    }
+}
+
+impl<K> GCounter<K>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn value(&self) -> u64 {
+        self.retirement.base
+            + self.counter.values().sum::<u64>()
+            + self.retirement.retired.values().sum::<u64>()
+    }
+
+    pub fn retire(&mut self, key: &K) -> bool {
+        match self.counter.remove(key) {
+            Some(value) => {
+                let retired = self.retirement.retired.entry(key.clone()).or_insert(0);
+                *retired = (*retired).max(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn fold_retired(&mut self) -> bool {
+        if self.retirement.retired.is_empty() {
+            return false;
+        }
+        self.retirement.base += self.retirement.retired.values().sum::<u64>();
+        self.retirement.folded = std::mem::take(&mut self.retirement.retired);
+        self.retirement.epoch += 1;
+        true
+    }
+
+    fn unfolded(&self, key: &K, value: u64, epoch: u64) -> u64 {
+        if epoch < self.retirement.epoch {
+            value.saturating_sub(self.retirement.folded.get(key).copied().unwrap_or(0))
+        } else {
+            value
+        }
+    }
+
+    fn merge_entry(&mut self, key: &K, value: u64) {
+        let current_value = match self.retirement.retired.get_mut(key) {
+            Some(retired) => retired,
+            None => self.counter.entry(key.clone()).or_insert(0),
+        };
+        *current_value = (*current_value).max(value);
+    }
+
+    fn merge_retired(&mut self, key: &K, value: u64) {
+        let counted = self.counter.remove(key).unwrap_or(0);
+        let retired = self.retirement.retired.entry(key.clone()).or_insert(0);
+        *retired = (*retired).max(counted).max(value);
+    }
+
+    fn adopt_epoch(&mut self, other: &GCounterRetirement<K>) {
+        if other.epoch <= self.retirement.epoch {
+            return;
+        }
+        for (key, folded) in &other.folded {
+            let counted = self.counter.remove(key).unwrap_or(0);
+            let retired = self.retirement.retired.remove(key);
+            let remaining = counted.max(retired.unwrap_or(0)).saturating_sub(*folded);
+            if remaining == 0 {
+                continue;
+            }
+            match retired {
+                Some(_) => self.retirement.retired.insert(key.clone(), remaining),
+                None => self.counter.insert(key.clone(), remaining),
+            };
+        }
+        self.retirement.epoch = other.epoch;
+        self.retirement.base = other.base;
+        self.retirement.folded = other.folded.clone();
+    }
+
+    fn merge_retirement(&mut self, other: &GCounterRetirement<K>) {
+        self.adopt_epoch(other);
+        for (key, value) in &other.retired {
+            let value = self.unfolded(key, *value, other.epoch);
+            if value > 0 {
+                self.merge_retired(key, value);
+            }
+        }
     }
 }
 
@@ -43,6 +176,7 @@ where
     fn new() -> Self::State {
         GCounter {
             counter: HashMap::new(),
+            retirement: GCounterRetirement::default(),
         }
     }
 
@@ -60,9 +194,12 @@ where
     K: Eq + Hash + Clone,
 {
     fn merge(&mut self, other: &Self::State) -> Self::State {
+        self.merge_retirement(&other.retirement);
         for (key, value) in &other.counter {
-            let current_value = self.counter.entry(key.clone()).or_insert(0);
-            *current_value = (*current_value).max(*value);
+            let value = self.unfolded(key, *value, other.retirement.epoch);
+            if value > 0 {
+                self.merge_entry(key, value);
+            }
         }
         self.clone()
     }
@@ -75,13 +212,13 @@ where
     type Op = CounterOperation<K>;
     fn apply(&mut self, op: &Self::Op) -> Self::State {
         match op {
-            CounterOperation::Increment { value } => {}
-            CounterOperation::Decrement { value } => {}
+            CounterOperation::Increment { value } => self.increment(value.clone()),
+            CounterOperation::Decrement { value: _ } => {}
         }
         self.clone()
     }
 
-    fn aggregate_operations(&mut self, operations: Vec<Self::Op>) -> Option<Self::Op> {
+    fn aggregate_operations(&mut self, _operations: Vec<Self::Op>) -> Option<Self::Op> {
         todo!()
     }
 }
@@ -92,13 +229,221 @@ where
 {
     type Delta = GCounterDelta<K>;
     fn generate_delta(&self) -> Self::Delta {
-        unimplemented!()
+        GCounterDelta {
+            increment_map: self.counter.clone(),
+        }
     }
     fn merge_delta(&mut self, other: &Self::Delta) -> Self::State {
-        unimplemented!()
+        for (key, value) in &other.increment_map {
+            self.merge_entry(key, *value);
+        }
+        self.clone()
     }
 
     fn aggregate_deltas(&mut self, deltas: Vec<Self::Delta>) -> Option<Self::Delta> {
-        todo!()
+        if deltas.is_empty() {
+            return None;
+        }
+        let mut increment_map: HashMap<K, u64> = HashMap::new();
+        for delta in deltas {
+            for (key, value) in delta.increment_map {
+                let current_value = increment_map.entry(key).or_insert(0);
+                *current_value = (*current_value).max(value);
+            }
+        }
+        Some(GCounterDelta { increment_map })
+    }
+}
+
+impl<K> ConstraintEnforcing<K> for GCounter<K>
+where
+    K: CrdtKey,
+{
+    fn check_constraints(&self, rules: &[ConstraintRule<K>]) -> Result<(), CrustError> {
+        check_rules(rules, &self.constraint_view())
+    }
+
+    fn repair_constraints(&mut self, _rules: &[ConstraintRule<K>]) -> Self::State {
+        self.clone()
+    }
+}
+
+impl<K> CrdtObject<K> for GCounter<K>
+where
+    K: CrdtKey,
+{
+    fn type_name(&self) -> String {
+        GCounter::<K>::name()
+    }
+
+    fn commands(&self, value: K) -> Vec<CrdtInnerCommand<K>> {
+        vec![CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+            value,
+        })]
+    }
+
+    fn apply_command(
+        &mut self,
+        command: &CrdtInnerCommand<K>,
+    ) -> Result<CrdtOperation<K>, CrustError> {
+        match command {
+            CrdtInnerCommand::Counter(CounterInnerCommand::Increment { value }) => {
+                self.increment(value.clone());
+                Ok(CrdtOperation::Counter(CounterOperation::Increment {
+                    value: value.clone(),
+                }))
+            }
+            _ => Err(CrustError::InvalidCommand {
+                crdt_type: self.type_name(),
+                command: format!("{:?}", command),
+            }),
+        }
+    }
+
+    fn apply(&mut self, operation: &CrdtOperation<K>) -> Result<(), CrustError> {
+        match operation {
+            CrdtOperation::Counter(op) => {
+                OperationBased::apply(self, op);
+                Ok(())
+            }
+            _ => Err(CrustError::InvalidOperation(format!(
+                "{:?} cannot be applied to a gcounter",
+                operation
+            ))),
+        }
+    }
+
+    fn get_state(&self) -> Value {
+        json!({
+            "value": self.value().to_string(),
+            "state": self.counter.iter()
+                .map(|(k, v)| (k.clone(), *v))
+                .collect::<HashMap<K, u64>>(),
+            "retired": self.retirement.retired,
+            "base": self.retirement.base,
+        })
+    }
+
+    fn to_state(&self) -> Result<Value, CrustError> {
+        to_value(self)
+    }
+
+    fn merge(&mut self, state: &Value) -> Result<(), CrustError> {
+        StateBased::merge(self, &from_value(state)?);
+        Ok(())
+    }
+
+    fn generate_delta(&self) -> Result<Value, CrustError> {
+        to_value(&DeltaBased::generate_delta(self))
+    }
+
+    fn merge_delta(&mut self, delta: &Value) -> Result<(), CrustError> {
+        DeltaBased::merge_delta(self, &from_value(delta)?);
+        Ok(())
+    }
+
+    fn aggregate_deltas(&self, deltas: Vec<Value>) -> Result<Option<Value>, CrustError> {
+        let deltas = deltas
+            .iter()
+            .map(from_value)
+            .collect::<Result<Vec<GCounterDelta<K>>, CrustError>>()?;
+        DeltaBased::aggregate_deltas(&mut self.clone(), deltas)
+            .map(|delta| to_value(&delta))
+            .transpose()
+    }
+
+    fn clone_object(&self) -> Box<dyn CrdtObject<K>> {
+        Box::new(self.clone())
+    }
+
+    fn as_constrained(&self) -> Option<&dyn Constrained<K>> {
+        Some(self)
+    }
+
+    fn as_constrained_mut(&mut self) -> Option<&mut dyn Constrained<K>> {
+        Some(self)
+    }
+
+    fn as_retirable(&self) -> Option<&dyn Retirable> {
+        Some(self)
+    }
+
+    fn as_retirable_mut(&mut self) -> Option<&mut dyn Retirable> {
+        Some(self)
+    }
+}
+
+impl<K> Retirable for GCounter<K>
+where
+    K: CrdtKey,
+{
+    fn contributions(&self) -> VersionVector<String> {
+        let mut contributions = VersionVector::new();
+        for (key, value) in self.counter.iter().chain(self.retirement.retired.iter()) {
+            if let Ok(Value::String(replica)) = to_value(key) {
+                contributions.observe(&Dot::new(replica, *value));
+            }
+        }
+        contributions
+    }
+
+    fn retire_departed(
+        &mut self,
+        departed: &[String],
+        stable: &VersionVector<String>,
+    ) -> Vec<String> {
+        let contributions = self.contributions();
+        let settled: Vec<String> = departed
+            .iter()
+            .filter(|replica| stable.get(replica) >= contributions.get(replica))
+            .cloned()
+            .collect();
+        let keys: Vec<K> = self
+            .counter
+            .keys()
+            .filter(
+                |key| matches!(to_value(*key), Ok(Value::String(name)) if settled.contains(&name)),
+            )
+            .cloned()
+            .collect();
+        for key in &keys {
+            self.retire(key);
+        }
+        settled
+    }
+
+    fn retirement(&self) -> Option<Value> {
+        to_value(&self.retirement).ok()
+    }
+
+    fn fold_retired(&mut self, peer_retirements: &[Value]) -> Result<bool, CrustError> {
+        let reports = peer_retirements
+            .iter()
+            .map(from_value)
+            .collect::<Result<Vec<GCounterRetirement<K>>, CrustError>>()?;
+        for report in &reports {
+            self.merge_retirement(report);
+        }
+        if reports.iter().all(|report| *report == self.retirement) {
+            return Ok(self.fold_retired());
+        }
+        Ok(false)
+    }
+}
+
+impl<K> Constrained<K> for GCounter<K>
+where
+    K: CrdtKey,
+{
+    fn constraint_view(&self) -> ConstraintView<K> {
+        ConstraintView {
+            value: Some(self.value() as i64),
+            ..ConstraintView::default()
+        }
+    }
+
+    fn repair_constraints(&mut self, rules: &[ConstraintRule<K>]) -> Result<(), CrustError> {
+        ConstraintEnforcing::repair_constraints(self, rules);
+        ConstraintEnforcing::check_constraints(self, rules)
     }
 }
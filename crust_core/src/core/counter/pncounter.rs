@@ -0,0 +1,341 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+use serde_json::{json, Value};
+
+use crate::{
+    command::{CounterInnerCommand, CrdtInnerCommand},
+    constraint::{check_rules, value_bounds, ConstraintRule, ConstraintView},
+    error::CrustError,
+    operation::{CounterOperation, CrdtOperation},
+    registry::{from_value, to_value, Constrained, CrdtKey, CrdtObject, Reversible},
+    sync::{ConstraintEnforcing, Crdt, DeltaBased, OperationBased, StateBased},
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "K: Serialize",
+    deserialize = "K: Deserialize<'de> + Eq + Hash"
+))]
+pub struct PNCounter<K>
+where
+    K: Eq + Hash,
+{
+    pub increments: HashMap<K, u64>,
+    pub decrements: HashMap<K, u64>,
+    #[serde(skip)]
+    pub replica: Option<K>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PNCounterDelta<K>
+where
+    K: Eq + Hash,
+{
+    pub increment_map: HashMap<K, u64>,
+    pub decrement_map: HashMap<K, u64>,
+}
+
+fn merge_max<K>(target: &mut HashMap<K, u64>, source: &HashMap<K, u64>)
+where
+    K: Eq + Hash + Clone,
+{
+    for (key, value) in source {
+        let current_value = target.entry(key.clone()).or_insert(0);
+        *current_value = (*current_value).max(*value);
+    }
+}
+
+impl<K> PartialEq for PNCounter<K>
+where
+    K: Eq + Hash,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.increments == other.increments && self.decrements == other.decrements
+    }
+}
+
+impl<K> PNCounter<K>
+where
+    K: Eq + Hash,
+{
+    pub fn increment(&mut self, key: K) {
+        *self.increments.entry(key).or_insert(0) += 1;
+    }
+
+    pub fn decrement(&mut self, key: K) {
+        *self.decrements.entry(key).or_insert(0) += 1;
+    }
+
+    pub fn value(&self) -> i64 {
+        self.increments.values().sum::<u64>() as i64 - self.decrements.values().sum::<u64>() as i64
+    }
+}
+
+impl<K> Crdt for PNCounter<K>
+where
+    K: Eq + Hash + Clone,
+{
+    type State = PNCounter<K>;
+
+    fn new() -> Self::State {
+        PNCounter {
+            increments: HashMap::new(),
+            decrements: HashMap::new(),
+            replica: None,
+        }
+    }
+
+    fn get_state(&self) -> Self::State {
+        self.clone()
+    }
+
+    fn name() -> String {
+        "pncounter".to_string()
+    }
+}
+
+impl<K> StateBased for PNCounter<K>
+where
+    K: Eq + Hash + Clone,
+{
+    fn merge(&mut self, other: &Self::State) -> Self::State {
+        merge_max(&mut self.increments, &other.increments);
+        merge_max(&mut self.decrements, &other.decrements);
+        self.clone()
+    }
+}
+
+impl<K> OperationBased for PNCounter<K>
+where
+    K: Eq + Hash + Clone,
+{
+    type Op = CounterOperation<K>;
+    fn apply(&mut self, op: &Self::Op) -> Self::State {
+        match op {
+            CounterOperation::Increment { value } => self.increment(value.clone()),
+            CounterOperation::Decrement { value } => self.decrement(value.clone()),
+        }
+        self.clone()
+    }
+
+    fn aggregate_operations(&mut self, _operations: Vec<Self::Op>) -> Option<Self::Op> {
+        None
+    }
+}
+
+impl<K> DeltaBased for PNCounter<K>
+where
+    K: Eq + Hash + Clone,
+{
+    type Delta = PNCounterDelta<K>;
+    fn generate_delta(&self) -> Self::Delta {
+        PNCounterDelta {
+            increment_map: self.increments.clone(),
+            decrement_map: self.decrements.clone(),
+        }
+    }
+
+    fn merge_delta(&mut self, other: &Self::Delta) -> Self::State {
+        merge_max(&mut self.increments, &other.increment_map);
+        merge_max(&mut self.decrements, &other.decrement_map);
+        self.clone()
+    }
+
+    fn aggregate_deltas(&mut self, deltas: Vec<Self::Delta>) -> Option<Self::Delta> {
+        if deltas.is_empty() {
+            return None;
+        }
+        let mut increment_map: HashMap<K, u64> = HashMap::new();
+        let mut decrement_map: HashMap<K, u64> = HashMap::new();
+        for delta in deltas {
+            merge_max(&mut increment_map, &delta.increment_map);
+            merge_max(&mut decrement_map, &delta.decrement_map);
+        }
+        Some(PNCounterDelta {
+            increment_map,
+            decrement_map,
+        })
+    }
+}
+
+impl<K> ConstraintEnforcing<K> for PNCounter<K>
+where
+    K: CrdtKey,
+{
+    fn check_constraints(&self, rules: &[ConstraintRule<K>]) -> Result<(), CrustError> {
+        check_rules(rules, &self.constraint_view())
+    }
+
+    fn repair_constraints(&mut self, rules: &[ConstraintRule<K>]) -> Self::State {
+        let Some(replica) = self.replica.clone() else {
+            return self.clone();
+        };
+        let value = self.value();
+        match value_bounds(rules) {
+            (_, Some(max)) if value > max => {
+                *self.decrements.entry(replica).or_insert(0) += (value - max) as u64;
+            }
+            (Some(min), _) if value < min => {
+                *self.increments.entry(replica).or_insert(0) += (min - value) as u64;
+            }
+            _ => {}
+        }
+        self.clone()
+    }
+}
+
+impl<K> CrdtObject<K> for PNCounter<K>
+where
+    K: CrdtKey,
+{
+    fn type_name(&self) -> String {
+        PNCounter::<K>::name()
+    }
+
+    fn commands(&self, value: K) -> Vec<CrdtInnerCommand<K>> {
+        vec![
+            CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+                value: value.clone(),
+            }),
+            CrdtInnerCommand::Counter(CounterInnerCommand::Decrement { value }),
+        ]
+    }
+
+    fn apply_command(
+        &mut self,
+        command: &CrdtInnerCommand<K>,
+    ) -> Result<CrdtOperation<K>, CrustError> {
+        let operation = match command {
+            CrdtInnerCommand::Counter(CounterInnerCommand::Increment { value }) => {
+                CounterOperation::Increment {
+                    value: value.clone(),
+                }
+            }
+            CrdtInnerCommand::Counter(CounterInnerCommand::Decrement { value }) => {
+                CounterOperation::Decrement {
+                    value: value.clone(),
+                }
+            }
+            _ => {
+                return Err(CrustError::InvalidCommand {
+                    crdt_type: self.type_name(),
+                    command: format!("{:?}", command),
+                })
+            }
+        };
+        OperationBased::apply(self, &operation);
+        Ok(CrdtOperation::Counter(operation))
+    }
+
+    fn apply(&mut self, operation: &CrdtOperation<K>) -> Result<(), CrustError> {
+        match operation {
+            CrdtOperation::Counter(op) => {
+                OperationBased::apply(self, op);
+                Ok(())
+            }
+            _ => Err(CrustError::InvalidOperation(format!(
+                "{:?} cannot be applied to a pncounter",
+                operation
+            ))),
+        }
+    }
+
+    fn get_state(&self) -> Value {
+        json!({
+            "value": self.value().to_string(),
+            "state": {
+                "increments": self.increments,
+                "decrements": self.decrements,
+            }
+        })
+    }
+
+    fn to_state(&self) -> Result<Value, CrustError> {
+        to_value(self)
+    }
+
+    fn merge(&mut self, state: &Value) -> Result<(), CrustError> {
+        StateBased::merge(self, &from_value(state)?);
+        Ok(())
+    }
+
+    fn generate_delta(&self) -> Result<Value, CrustError> {
+        to_value(&DeltaBased::generate_delta(self))
+    }
+
+    fn merge_delta(&mut self, delta: &Value) -> Result<(), CrustError> {
+        DeltaBased::merge_delta(self, &from_value(delta)?);
+        Ok(())
+    }
+
+    fn aggregate_deltas(&self, deltas: Vec<Value>) -> Result<Option<Value>, CrustError> {
+        let deltas = deltas
+            .iter()
+            .map(from_value)
+            .collect::<Result<Vec<PNCounterDelta<K>>, CrustError>>()?;
+        DeltaBased::aggregate_deltas(&mut self.clone(), deltas)
+            .map(|delta| to_value(&delta))
+            .transpose()
+    }
+
+    fn clone_object(&self) -> Box<dyn CrdtObject<K>> {
+        Box::new(self.clone())
+    }
+
+    fn assign_replica(&mut self, replica: &str) {
+        self.replica = from_value(&Value::String(replica.to_string())).ok();
+    }
+
+    fn as_reversible(&self) -> Option<&dyn Reversible<K>> {
+        Some(self)
+    }
+
+    fn as_constrained(&self) -> Option<&dyn Constrained<K>> {
+        Some(self)
+    }
+
+    fn as_constrained_mut(&mut self) -> Option<&mut dyn Constrained<K>> {
+        Some(self)
+    }
+}
+
+impl<K> Constrained<K> for PNCounter<K>
+where
+    K: CrdtKey,
+{
+    fn constraint_view(&self) -> ConstraintView<K> {
+        ConstraintView {
+            value: Some(self.value()),
+            ..ConstraintView::default()
+        }
+    }
+
+    fn repair_constraints(&mut self, rules: &[ConstraintRule<K>]) -> Result<(), CrustError> {
+        ConstraintEnforcing::repair_constraints(self, rules);
+        ConstraintEnforcing::check_constraints(self, rules)
+    }
+}
+
+impl<K> Reversible<K> for PNCounter<K>
+where
+    K: CrdtKey,
+{
+    fn inverse_operation(&self, operation: &CrdtOperation<K>) -> Option<CrdtOperation<K>> {
+        match operation {
+            CrdtOperation::Counter(CounterOperation::Increment { value }) => {
+                Some(CrdtOperation::Counter(CounterOperation::Decrement {
+                    value: value.clone(),
+                }))
+            }
+            CrdtOperation::Counter(CounterOperation::Decrement { value }) => {
+                Some(CrdtOperation::Counter(CounterOperation::Increment {
+                    value: value.clone(),
+                }))
+            }
+            _ => None,
+        }
+    }
+}
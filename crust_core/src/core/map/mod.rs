@@ -0,0 +1,2 @@
+pub mod ormap;
+pub mod mvmap;
@@ -0,0 +1,313 @@
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    anti_entropy::merkle::{entry_key, hash_unordered, hash_value},
+    causality::{stability::StablePurge, version_vector::VersionVector},
+    command::{CrdtInnerCommand, MapInnerCommand},
+    constraint::{check_rules, excess_elements, ConstraintRule, ConstraintView},
+    core::{
+        map::ormap::ORMap,
+        register::mvregister::{write_delta, MVRegisterStore},
+    },
+    dot_store::{dot_map::DotMap, Causal, CausalCrdt},
+    error::CrustError,
+    operation::{CrdtOperation, MapOperation},
+    registry::{
+        from_value, to_value, Constrained, CrdtKey, CrdtObject, Reconcilable, Stabilizable,
+    },
+    sync::{Crdt, DeltaBased, StateBased},
+};
+
+pub type MVMapStore<K> = DotMap<K, MVRegisterStore<String, K>>;
+
+pub type MVMapDelta<K> = Causal<String, MVMapStore<K>>;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "K: Serialize",
+    deserialize = "K: Deserialize<'de> + Eq + Hash"
+))]
+pub struct MVMap<K>
+where
+    K: Eq + Hash,
+{
+    pub map: ORMap<K, MVRegisterStore<String, K>, String>,
+    #[serde(skip)]
+    pub replica: String,
+}
+
+impl<K> PartialEq for MVMap<K>
+where
+    K: Eq + Hash + Clone + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K> MVMap<K>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn with_replica(replica: &str) -> Self {
+        MVMap {
+            map: ORMap::default(),
+            replica: replica.to_string(),
+        }
+    }
+
+    pub fn put(&mut self, key: K, value: K) -> MVMapDelta<K> {
+        let replica = self.replica.clone();
+        self.map
+            .apply(key, |register| write_delta(register, replica, value))
+    }
+
+    pub fn remove(&mut self, key: &K) -> MVMapDelta<K> {
+        self.map.remove(key)
+    }
+
+    pub fn get(&self, key: &K) -> Vec<&K> {
+        self.map
+            .get(key)
+            .map(|register| register.values.values().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl<K> Crdt for MVMap<K>
+where
+    K: Eq + Hash + Clone,
+{
+    type State = MVMap<K>;
+
+    fn new() -> Self::State {
+        MVMap::with_replica("none")
+    }
+
+    fn get_state(&self) -> Self::State {
+        self.clone()
+    }
+
+    fn name() -> String {
+        "mvmap".to_string()
+    }
+}
+
+impl<K> CrdtObject<K> for MVMap<K>
+where
+    K: CrdtKey,
+{
+    fn type_name(&self) -> String {
+        MVMap::<K>::name()
+    }
+
+    fn commands(&self, value: K) -> Vec<CrdtInnerCommand<K>> {
+        vec![
+            CrdtInnerCommand::Map(MapInnerCommand::Put {
+                key: value.clone(),
+                value: value.clone(),
+            }),
+            CrdtInnerCommand::Map(MapInnerCommand::Remove { key: value }),
+        ]
+    }
+
+    fn apply_command(
+        &mut self,
+        command: &CrdtInnerCommand<K>,
+    ) -> Result<CrdtOperation<K>, CrustError> {
+        match command {
+            CrdtInnerCommand::Map(MapInnerCommand::Put { key, value }) => {
+                self.put(key.clone(), value.clone());
+                Ok(CrdtOperation::Map(MapOperation::Put {
+                    key: key.clone(),
+                    value: value.clone(),
+                }))
+            }
+            CrdtInnerCommand::Map(MapInnerCommand::Remove { key }) => {
+                self.remove(key);
+                Ok(CrdtOperation::Map(MapOperation::Remove {
+                    key: key.clone(),
+                }))
+            }
+            _ => Err(CrustError::InvalidCommand {
+                crdt_type: self.type_name(),
+                command: format!("{:?}", command),
+            }),
+        }
+    }
+
+    fn apply(&mut self, operation: &CrdtOperation<K>) -> Result<(), CrustError> {
+        Err(CrustError::InvalidOperation(format!(
+            "{:?} cannot be applied to an mvmap, which replicates through states and deltas",
+            operation
+        )))
+    }
+
+    fn get_state(&self) -> Value {
+        let mut entries: Vec<(String, &K, Vec<&K>)> = self
+            .map
+            .keys()
+            .map(|key| {
+                let mut values = self.get(key);
+                values.sort_by_key(|value| entry_key(*value));
+                (entry_key(key), key, values)
+            })
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        json!({
+            "value": entries
+                .into_iter()
+                .map(|(_, key, values)| (key, values))
+                .collect::<Vec<(&K, Vec<&K>)>>(),
+            "len": self.len(),
+        })
+    }
+
+    fn to_state(&self) -> Result<Value, CrustError> {
+        to_value(&self.map)
+    }
+
+    fn merge(&mut self, state: &Value) -> Result<(), CrustError> {
+        StateBased::merge(&mut self.map, &from_value(state)?);
+        Ok(())
+    }
+
+    fn generate_delta(&self) -> Result<Value, CrustError> {
+        to_value(&DeltaBased::generate_delta(&self.map))
+    }
+
+    fn take_delta(&mut self) -> Result<Value, CrustError> {
+        let delta = to_value(&DeltaBased::generate_delta(&self.map))?;
+        self.map.take_delta();
+        Ok(delta)
+    }
+
+    fn merge_delta(&mut self, delta: &Value) -> Result<(), CrustError> {
+        DeltaBased::merge_delta(&mut self.map, &from_value(delta)?);
+        Ok(())
+    }
+
+    fn aggregate_deltas(&self, deltas: Vec<Value>) -> Result<Option<Value>, CrustError> {
+        let deltas = deltas
+            .iter()
+            .map(from_value)
+            .collect::<Result<Vec<MVMapDelta<K>>, CrustError>>()?;
+        DeltaBased::aggregate_deltas(&mut self.map.clone(), deltas)
+            .map(|delta| to_value(&delta))
+            .transpose()
+    }
+
+    fn clone_object(&self) -> Box<dyn CrdtObject<K>> {
+        Box::new(self.clone())
+    }
+
+    fn assign_replica(&mut self, replica: &str) {
+        self.replica = replica.to_string();
+    }
+
+    fn as_constrained(&self) -> Option<&dyn Constrained<K>> {
+        Some(self)
+    }
+
+    fn as_constrained_mut(&mut self) -> Option<&mut dyn Constrained<K>> {
+        Some(self)
+    }
+
+    fn as_stabilizable(&self) -> Option<&dyn Stabilizable> {
+        Some(self)
+    }
+
+    fn as_stabilizable_mut(&mut self) -> Option<&mut dyn Stabilizable> {
+        Some(self)
+    }
+
+    fn as_reconcilable(&self) -> Option<&dyn Reconcilable> {
+        Some(self)
+    }
+
+    fn as_reconcilable_mut(&mut self) -> Option<&mut dyn Reconcilable> {
+        Some(self)
+    }
+}
+
+impl<K> Reconcilable for MVMap<K>
+where
+    K: CrdtKey,
+{
+    fn entry_digests(&self) -> Vec<(String, u64)> {
+        self.map
+            .state
+            .store
+            .entries
+            .iter()
+            .map(|(key, register)| {
+                (
+                    entry_key(key),
+                    hash_unordered(register.values.iter().map(|pair| hash_value(&pair))),
+                )
+            })
+            .collect()
+    }
+
+    fn entries_state(&self, in_scope: &dyn Fn(&str) -> bool) -> Result<Value, CrustError> {
+        to_value(&self.map.state.restrict(|key| in_scope(&entry_key(key))))
+    }
+
+    fn merge_entries(
+        &mut self,
+        in_scope: &dyn Fn(&str) -> bool,
+        entries: &Value,
+    ) -> Result<(), CrustError> {
+        let entries: MVMapDelta<K> = from_value(entries)?;
+        self.map
+            .state
+            .join_restricted(&entries, |key| in_scope(&entry_key(key)));
+        Ok(())
+    }
+}
+
+impl<K> Stabilizable for MVMap<K>
+where
+    K: CrdtKey,
+{
+    fn purge_stable(&mut self, stable: &VersionVector<String>) -> usize {
+        StablePurge::purge_stable(&mut self.map, stable)
+    }
+
+    fn version_vector(&self) -> VersionVector<String> {
+        self.map.state.context.version_vector.clone()
+    }
+}
+
+impl<K> Constrained<K> for MVMap<K>
+where
+    K: CrdtKey,
+{
+    fn constraint_view(&self) -> ConstraintView<K> {
+        let mut elements: Vec<&K> = self.map.keys().collect();
+        elements.sort_by_key(|element| entry_key(*element));
+        ConstraintView {
+            elements: Some(elements.into_iter().cloned().collect()),
+            ..ConstraintView::default()
+        }
+    }
+
+    fn repair_constraints(&mut self, rules: &[ConstraintRule<K>]) -> Result<(), CrustError> {
+        let elements = self.constraint_view().elements.unwrap_or_default();
+        for element in excess_elements(rules, &elements) {
+            self.remove(&element);
+        }
+        check_rules(rules, &self.constraint_view())
+    }
+}
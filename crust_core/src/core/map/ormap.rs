@@ -0,0 +1,154 @@
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    causality::causal_context::CausalContext,
+    dot_store::{dot_map::DotMap, Causal, CausalCrdt, DotStore},
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "M: Serialize, S: Serialize, K: Serialize",
+    deserialize = "M: Deserialize<'de> + Eq + Hash, S: Deserialize<'de>, K: Deserialize<'de> + Eq + Hash"
+))]
+pub struct ORMap<M, S, K>
+where
+    M: Eq + Hash,
+    K: Eq + Hash,
+{
+    pub state: Causal<K, DotMap<M, S>>,
+    #[serde(skip)]
+    pub delta: Option<Causal<K, DotMap<M, S>>>,
+}
+
+impl<M, S, K> PartialEq for ORMap<M, S, K>
+where
+    M: Eq + Hash + Clone,
+    S: DotStore<K> + PartialEq,
+    K: Eq + Hash + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+    }
+}
+
+impl<M, S, K> Default for ORMap<M, S, K>
+where
+    M: Eq + Hash + Clone,
+    S: DotStore<K>,
+    K: Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        ORMap {
+            state: Causal::new(),
+            delta: None,
+        }
+    }
+}
+
+pub fn apply_delta<M, S, K, F>(
+    state: &Causal<K, DotMap<M, S>>,
+    key: M,
+    mutator: F,
+) -> Causal<K, DotMap<M, S>>
+where
+    M: Eq + Hash + Clone,
+    S: DotStore<K>,
+    K: Eq + Hash + Clone,
+    F: FnOnce(&Causal<K, S>) -> Causal<K, S>,
+{
+    let embedded = Causal {
+        store: state.store.get(&key).cloned().unwrap_or_default(),
+        context: state.context.clone(),
+    };
+    let delta = mutator(&embedded);
+    Causal {
+        store: DotMap::singleton(key, delta.store),
+        context: delta.context,
+    }
+}
+
+pub fn remove_delta<M, S, K>(state: &Causal<K, DotMap<M, S>>, key: &M) -> Causal<K, DotMap<M, S>>
+where
+    M: Eq + Hash + Clone,
+    S: DotStore<K>,
+    K: Eq + Hash + Clone,
+{
+    let mut context = CausalContext::new();
+    if let Some(store) = state.store.get(key) {
+        for dot in store.dots() {
+            context.insert(dot);
+        }
+    }
+    Causal {
+        store: DotMap::default(),
+        context,
+    }
+}
+
+impl<M, S, K> ORMap<M, S, K>
+where
+    M: Eq + Hash + Clone,
+    S: DotStore<K>,
+    K: Eq + Hash + Clone,
+{
+    pub fn apply<F>(&mut self, key: M, mutator: F) -> Causal<K, DotMap<M, S>>
+    where
+        F: FnOnce(&Causal<K, S>) -> Causal<K, S>,
+    {
+        let delta = apply_delta(&self.state, key, mutator);
+        self.mutate(delta)
+    }
+
+    pub fn remove(&mut self, key: &M) -> Causal<K, DotMap<M, S>> {
+        let delta = remove_delta(&self.state, key);
+        self.mutate(delta)
+    }
+
+    pub fn get(&self, key: &M) -> Option<&S> {
+        self.state.store.get(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &M> {
+        self.state.store.entries.keys()
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.store.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.state.store.entries.is_empty()
+    }
+}
+
+impl<M, S, K> CausalCrdt for ORMap<M, S, K>
+where
+    M: Eq + Hash + Clone,
+    S: DotStore<K>,
+    K: Eq + Hash + Clone,
+{
+    type Replica = K;
+    type Store = DotMap<M, S>;
+
+    fn type_name() -> String {
+        "ormap".to_string()
+    }
+
+    fn causal(&self) -> &Causal<K, Self::Store> {
+        &self.state
+    }
+
+    fn causal_mut(&mut self) -> &mut Causal<K, Self::Store> {
+        &mut self.state
+    }
+
+    fn delta_buffer(&self) -> &Option<Causal<K, Self::Store>> {
+        &self.delta
+    }
+
+    fn delta_buffer_mut(&mut self) -> &mut Option<Causal<K, Self::Store>> {
+        &mut self.delta
+    }
+}
@@ -1 +1,4 @@
 pub mod counter;
+pub mod map;
+pub mod register;
+pub mod set;
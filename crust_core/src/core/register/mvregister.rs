@@ -0,0 +1,131 @@
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    causality::causal_context::CausalContext,
+    dot_store::{dot_fun::DotFun, Causal, CausalCrdt, DotStore},
+};
+
+pub type MVRegisterStore<K, V> = DotFun<K, V>;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "K: Serialize, V: Serialize",
+    deserialize = "K: Deserialize<'de> + Eq + Hash, V: Deserialize<'de>"
+))]
+pub struct MVRegister<K, V>
+where
+    K: Eq + Hash,
+{
+    pub state: Causal<K, MVRegisterStore<K, V>>,
+    #[serde(skip)]
+    pub delta: Option<Causal<K, MVRegisterStore<K, V>>>,
+}
+
+impl<K, V> PartialEq for MVRegister<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+    }
+}
+
+impl<K, V> Default for MVRegister<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    fn default() -> Self {
+        MVRegister {
+            state: Causal::new(),
+            delta: None,
+        }
+    }
+}
+
+pub fn write_delta<K, V>(
+    state: &Causal<K, MVRegisterStore<K, V>>,
+    replica: K,
+    value: V,
+) -> Causal<K, MVRegisterStore<K, V>>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    let dot = state.next_dot(replica);
+    let mut context = clear_delta(state).context;
+    context.insert(dot.clone());
+    Causal {
+        store: DotFun::singleton(dot, value),
+        context,
+    }
+}
+
+pub fn clear_delta<K, V>(
+    state: &Causal<K, MVRegisterStore<K, V>>,
+) -> Causal<K, MVRegisterStore<K, V>>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    let mut context = CausalContext::new();
+    for dot in state.store.dots() {
+        context.insert(dot);
+    }
+    Causal {
+        store: DotFun::default(),
+        context,
+    }
+}
+
+impl<K, V> MVRegister<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    pub fn write(&mut self, replica: K, value: V) -> Causal<K, MVRegisterStore<K, V>> {
+        let delta = write_delta(&self.state, replica, value);
+        self.mutate(delta)
+    }
+
+    pub fn clear(&mut self) -> Causal<K, MVRegisterStore<K, V>> {
+        let delta = clear_delta(&self.state);
+        self.mutate(delta)
+    }
+
+    pub fn read(&self) -> Vec<&V> {
+        self.state.store.values.values().collect()
+    }
+}
+
+impl<K, V> CausalCrdt for MVRegister<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    type Replica = K;
+    type Store = MVRegisterStore<K, V>;
+
+    fn type_name() -> String {
+        "mvregister".to_string()
+    }
+
+    fn causal(&self) -> &Causal<K, Self::Store> {
+        &self.state
+    }
+
+    fn causal_mut(&mut self) -> &mut Causal<K, Self::Store> {
+        &mut self.state
+    }
+
+    fn delta_buffer(&self) -> &Option<Causal<K, Self::Store>> {
+        &self.delta
+    }
+
+    fn delta_buffer_mut(&mut self) -> &mut Option<Causal<K, Self::Store>> {
+        &mut self.delta
+    }
+}
@@ -0,0 +1,147 @@
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    causality::causal_context::CausalContext,
+    dot_store::{dot_map::DotMap, dot_set::DotSet, Causal, CausalCrdt, DotStore},
+};
+
+pub type AWSetStore<E, K> = DotMap<E, DotSet<K>>;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "E: Serialize, K: Serialize",
+    deserialize = "E: Deserialize<'de> + Eq + Hash, K: Deserialize<'de> + Eq + Hash"
+))]
+pub struct AWSet<E, K>
+where
+    E: Eq + Hash,
+    K: Eq + Hash,
+{
+    pub state: Causal<K, AWSetStore<E, K>>,
+    #[serde(skip)]
+    pub delta: Option<Causal<K, AWSetStore<E, K>>>,
+}
+
+impl<E, K> PartialEq for AWSet<E, K>
+where
+    E: Eq + Hash + Clone,
+    K: Eq + Hash + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+    }
+}
+
+impl<E, K> Default for AWSet<E, K>
+where
+    E: Eq + Hash + Clone,
+    K: Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        AWSet {
+            state: Causal::new(),
+            delta: None,
+        }
+    }
+}
+
+pub fn add_delta<E, K>(
+    state: &Causal<K, AWSetStore<E, K>>,
+    replica: K,
+    element: E,
+) -> Causal<K, AWSetStore<E, K>>
+where
+    E: Eq + Hash + Clone,
+    K: Eq + Hash + Clone,
+{
+    let dot = state.next_dot(replica);
+    let mut context = remove_delta(state, &element).context;
+    context.insert(dot.clone());
+    Causal {
+        store: DotMap::singleton(element, DotSet::singleton(dot)),
+        context,
+    }
+}
+
+pub fn remove_delta<E, K>(
+    state: &Causal<K, AWSetStore<E, K>>,
+    element: &E,
+) -> Causal<K, AWSetStore<E, K>>
+where
+    E: Eq + Hash + Clone,
+    K: Eq + Hash + Clone,
+{
+    let mut context = CausalContext::new();
+    if let Some(dots) = state.store.get(element) {
+        for dot in dots.dots() {
+            context.insert(dot);
+        }
+    }
+    Causal {
+        store: DotMap::default(),
+        context,
+    }
+}
+
+impl<E, K> AWSet<E, K>
+where
+    E: Eq + Hash + Clone,
+    K: Eq + Hash + Clone,
+{
+    pub fn add(&mut self, replica: K, element: E) -> Causal<K, AWSetStore<E, K>> {
+        let delta = add_delta(&self.state, replica, element);
+        self.mutate(delta)
+    }
+
+    pub fn remove(&mut self, element: &E) -> Causal<K, AWSetStore<E, K>> {
+        let delta = remove_delta(&self.state, element);
+        self.mutate(delta)
+    }
+
+    pub fn contains(&self, element: &E) -> bool {
+        self.state.store.entries.contains_key(element)
+    }
+
+    pub fn elements(&self) -> impl Iterator<Item = &E> {
+        self.state.store.entries.keys()
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.store.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.state.store.entries.is_empty()
+    }
+}
+
+impl<E, K> CausalCrdt for AWSet<E, K>
+where
+    E: Eq + Hash + Clone,
+    K: Eq + Hash + Clone,
+{
+    type Replica = K;
+    type Store = AWSetStore<E, K>;
+
+    fn type_name() -> String {
+        "awset".to_string()
+    }
+
+    fn causal(&self) -> &Causal<K, Self::Store> {
+        &self.state
+    }
+
+    fn causal_mut(&mut self) -> &mut Causal<K, Self::Store> {
+        &mut self.state
+    }
+
+    fn delta_buffer(&self) -> &Option<Causal<K, Self::Store>> {
+        &self.delta
+    }
+
+    fn delta_buffer_mut(&mut self) -> &mut Option<Causal<K, Self::Store>> {
+        &mut self.delta
+    }
+}
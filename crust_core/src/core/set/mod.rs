@@ -0,0 +1,2 @@
+pub mod awset;
+pub mod orset;
@@ -0,0 +1,325 @@
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    anti_entropy::merkle::{entry_key, hash_unordered, hash_value},
+    causality::{stability::StablePurge, version_vector::VersionVector},
+    command::{CrdtInnerCommand, SetInnerCommand},
+    constraint::{check_rules, excess_elements, ConstraintRule, ConstraintView},
+    core::set::awset::{AWSet, AWSetStore},
+    dot_store::{Causal, CausalCrdt, DotStore},
+    error::CrustError,
+    operation::{CrdtOperation, SetOperation},
+    registry::{
+        from_value, to_value, Constrained, CrdtKey, CrdtObject, Reconcilable, Reversible,
+        Stabilizable,
+    },
+    sync::{Crdt, DeltaBased, StateBased},
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "K: Serialize",
+    deserialize = "K: Deserialize<'de> + Eq + Hash"
+))]
+pub struct ORSet<K>
+where
+    K: Eq + Hash,
+{
+    pub set: AWSet<K, String>,
+    #[serde(skip)]
+    pub replica: String,
+}
+
+pub type ORSetDelta<K> = Causal<String, AWSetStore<K, String>>;
+
+impl<K> PartialEq for ORSet<K>
+where
+    K: Eq + Hash + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.set == other.set
+    }
+}
+
+impl<K> ORSet<K>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn with_replica(replica: &str) -> Self {
+        ORSet {
+            set: AWSet::default(),
+            replica: replica.to_string(),
+        }
+    }
+
+    pub fn add(&mut self, element: K) -> ORSetDelta<K> {
+        self.set.add(self.replica.clone(), element)
+    }
+
+    pub fn remove(&mut self, element: &K) -> ORSetDelta<K> {
+        self.set.remove(element)
+    }
+
+    pub fn contains(&self, element: &K) -> bool {
+        self.set.contains(element)
+    }
+
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+}
+
+impl<K> Crdt for ORSet<K>
+where
+    K: Eq + Hash + Clone,
+{
+    type State = ORSet<K>;
+
+    fn new() -> Self::State {
+        ORSet::with_replica("none")
+    }
+
+    fn get_state(&self) -> Self::State {
+        self.clone()
+    }
+
+    fn name() -> String {
+        "orset".to_string()
+    }
+}
+
+impl<K> CrdtObject<K> for ORSet<K>
+where
+    K: CrdtKey,
+{
+    fn type_name(&self) -> String {
+        ORSet::<K>::name()
+    }
+
+    fn commands(&self, value: K) -> Vec<CrdtInnerCommand<K>> {
+        vec![
+            CrdtInnerCommand::Set(SetInnerCommand::Add {
+                value: value.clone(),
+            }),
+            CrdtInnerCommand::Set(SetInnerCommand::Remove { value }),
+        ]
+    }
+
+    fn apply_command(
+        &mut self,
+        command: &CrdtInnerCommand<K>,
+    ) -> Result<CrdtOperation<K>, CrustError> {
+        match command {
+            CrdtInnerCommand::Set(SetInnerCommand::Add { value }) => {
+                self.add(value.clone());
+                Ok(CrdtOperation::Set(SetOperation::Add {
+                    value: value.clone(),
+                }))
+            }
+            CrdtInnerCommand::Set(SetInnerCommand::Remove { value }) => {
+                self.remove(value);
+                Ok(CrdtOperation::Set(SetOperation::Remove {
+                    value: value.clone(),
+                }))
+            }
+            _ => Err(CrustError::InvalidCommand {
+                crdt_type: self.type_name(),
+                command: format!("{:?}", command),
+            }),
+        }
+    }
+
+    fn apply(&mut self, operation: &CrdtOperation<K>) -> Result<(), CrustError> {
+        Err(CrustError::InvalidOperation(format!(
+            "{:?} cannot be applied to an orset, which replicates through states and deltas",
+            operation
+        )))
+    }
+
+    fn get_state(&self) -> Value {
+        let mut elements: Vec<(String, &K)> = self
+            .set
+            .elements()
+            .map(|element| (entry_key(element), element))
+            .collect();
+        elements.sort_by(|a, b| a.0.cmp(&b.0));
+        json!({
+            "value": elements.into_iter().map(|(_, element)| element).collect::<Vec<&K>>(),
+            "len": self.len(),
+        })
+    }
+
+    fn to_state(&self) -> Result<Value, CrustError> {
+        to_value(&self.set)
+    }
+
+    fn merge(&mut self, state: &Value) -> Result<(), CrustError> {
+        StateBased::merge(&mut self.set, &from_value(state)?);
+        Ok(())
+    }
+
+    fn generate_delta(&self) -> Result<Value, CrustError> {
+        to_value(&DeltaBased::generate_delta(&self.set))
+    }
+
+    fn take_delta(&mut self) -> Result<Value, CrustError> {
+        let delta = to_value(&DeltaBased::generate_delta(&self.set))?;
+        self.set.take_delta();
+        Ok(delta)
+    }
+
+    fn merge_delta(&mut self, delta: &Value) -> Result<(), CrustError> {
+        DeltaBased::merge_delta(&mut self.set, &from_value(delta)?);
+        Ok(())
+    }
+
+    fn aggregate_deltas(&self, deltas: Vec<Value>) -> Result<Option<Value>, CrustError> {
+        let deltas = deltas
+            .iter()
+            .map(from_value)
+            .collect::<Result<Vec<ORSetDelta<K>>, CrustError>>()?;
+        DeltaBased::aggregate_deltas(&mut self.set.clone(), deltas)
+            .map(|delta| to_value(&delta))
+            .transpose()
+    }
+
+    fn clone_object(&self) -> Box<dyn CrdtObject<K>> {
+        Box::new(self.clone())
+    }
+
+    fn assign_replica(&mut self, replica: &str) {
+        self.replica = replica.to_string();
+    }
+
+    fn as_reversible(&self) -> Option<&dyn Reversible<K>> {
+        Some(self)
+    }
+
+    fn as_constrained(&self) -> Option<&dyn Constrained<K>> {
+        Some(self)
+    }
+
+    fn as_constrained_mut(&mut self) -> Option<&mut dyn Constrained<K>> {
+        Some(self)
+    }
+
+    fn as_stabilizable(&self) -> Option<&dyn Stabilizable> {
+        Some(self)
+    }
+
+    fn as_stabilizable_mut(&mut self) -> Option<&mut dyn Stabilizable> {
+        Some(self)
+    }
+
+    fn as_reconcilable(&self) -> Option<&dyn Reconcilable> {
+        Some(self)
+    }
+
+    fn as_reconcilable_mut(&mut self) -> Option<&mut dyn Reconcilable> {
+        Some(self)
+    }
+}
+
+impl<K> Reversible<K> for ORSet<K>
+where
+    K: CrdtKey,
+{
+    fn inverse_operation(&self, operation: &CrdtOperation<K>) -> Option<CrdtOperation<K>> {
+        match operation {
+            CrdtOperation::Set(SetOperation::Add { value }) => {
+                Some(CrdtOperation::Set(SetOperation::Remove {
+                    value: value.clone(),
+                }))
+            }
+            CrdtOperation::Set(SetOperation::Remove { value }) => {
+                Some(CrdtOperation::Set(SetOperation::Add {
+                    value: value.clone(),
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<K> Reconcilable for ORSet<K>
+where
+    K: CrdtKey,
+{
+    fn entry_digests(&self) -> Vec<(String, u64)> {
+        self.set
+            .state
+            .store
+            .entries
+            .iter()
+            .map(|(element, dots)| {
+                (
+                    entry_key(element),
+                    hash_unordered(dots.dots().iter().map(hash_value)),
+                )
+            })
+            .collect()
+    }
+
+    fn entries_state(&self, in_scope: &dyn Fn(&str) -> bool) -> Result<Value, CrustError> {
+        to_value(
+            &self
+                .set
+                .state
+                .restrict(|element| in_scope(&entry_key(element))),
+        )
+    }
+
+    fn merge_entries(
+        &mut self,
+        in_scope: &dyn Fn(&str) -> bool,
+        entries: &Value,
+    ) -> Result<(), CrustError> {
+        let entries: ORSetDelta<K> = from_value(entries)?;
+        self.set
+            .state
+            .join_restricted(&entries, |element| in_scope(&entry_key(element)));
+        Ok(())
+    }
+}
+
+impl<K> Stabilizable for ORSet<K>
+where
+    K: CrdtKey,
+{
+    fn purge_stable(&mut self, stable: &VersionVector<String>) -> usize {
+        StablePurge::purge_stable(&mut self.set, stable)
+    }
+
+    fn version_vector(&self) -> VersionVector<String> {
+        self.set.state.context.version_vector.clone()
+    }
+}
+
+impl<K> Constrained<K> for ORSet<K>
+where
+    K: CrdtKey,
+{
+    fn constraint_view(&self) -> ConstraintView<K> {
+        let mut elements: Vec<&K> = self.set.elements().collect();
+        elements.sort_by_key(|element| entry_key(*element));
+        ConstraintView {
+            elements: Some(elements.into_iter().cloned().collect()),
+            ..ConstraintView::default()
+        }
+    }
+
+    fn repair_constraints(&mut self, rules: &[ConstraintRule<K>]) -> Result<(), CrustError> {
+        let elements = self.constraint_view().elements.unwrap_or_default();
+        for element in excess_elements(rules, &elements) {
+            self.remove(&element);
+        }
+        check_rules(rules, &self.constraint_view())
+    }
+}
@@ -1,12 +1,8 @@
 use serde::{Deserialize, Serialize};
-use std::hash::Hash;
+use serde_json::Value;
 
-use crate::core::counter::gcounter::GCounterDelta;
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub enum CrdtDelta<K>
-where
-    K: Eq + Hash,
-{
-    GCounter(GCounterDelta<K>),
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CrdtDelta {
+    pub crdt_type: String,
+    pub payload: Value,
 }
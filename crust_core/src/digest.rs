@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{anti_entropy::merkle::hash_bytes, causality::version_vector::VersionVector};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct StateDigest {
+    pub replica: String,
+    pub crdt_type: String,
+    pub digest: String,
+    pub version_vector: VersionVector<String>,
+}
+
+impl StateDigest {
+    pub fn new(
+        replica: String,
+        crdt_type: String,
+        state: &Value,
+        version_vector: VersionVector<String>,
+    ) -> Self {
+        StateDigest {
+            replica,
+            crdt_type,
+            digest: canonical_digest(state),
+            version_vector,
+        }
+    }
+}
+
+pub fn canonical_digest(value: &Value) -> String {
+    let mut bytes = Vec::new();
+    write_canonical(value, &mut bytes);
+    format!("{:016x}", hash_bytes(&bytes))
+}
+
+fn write_canonical(value: &Value, bytes: &mut Vec<u8>) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            bytes.push(b'{');
+            for (key, value) in entries {
+                write_canonical(&Value::String(key.clone()), bytes);
+                bytes.push(b':');
+                write_canonical(value, bytes);
+                bytes.push(b',');
+            }
+            bytes.push(b'}');
+        }
+        Value::Array(values) => {
+            bytes.push(b'[');
+            for value in values {
+                write_canonical(value, bytes);
+                bytes.push(b',');
+            }
+            bytes.push(b']');
+        }
+        value => bytes.extend(value.to_string().into_bytes()),
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    causality::{causal_context::CausalContext, dot::Dot, version_vector::VersionVector},
+    dot_store::{dot_pairs, newest_dot, DotStore, StableDots},
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(
+    serialize = "K: Serialize, V: Serialize",
+    deserialize = "K: Deserialize<'de> + Eq + Hash, V: Deserialize<'de>"
+))]
+pub struct DotFun<K, V>
+where
+    K: Eq + Hash,
+{
+    #[serde(with = "dot_pairs")]
+    pub values: HashMap<Dot<K>, V>,
+}
+
+impl<K, V> Default for DotFun<K, V>
+where
+    K: Eq + Hash,
+{
+    fn default() -> Self {
+        DotFun {
+            values: HashMap::new(),
+        }
+    }
+}
+
+impl<K, V> DotFun<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn singleton(dot: Dot<K>, value: V) -> Self {
+        DotFun {
+            values: HashMap::from([(dot, value)]),
+        }
+    }
+}
+
+impl<K, V> DotStore<K> for DotFun<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    fn dots(&self) -> HashSet<Dot<K>> {
+        self.values.keys().cloned().collect()
+    }
+
+    fn is_bottom(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    fn join(
+        &self,
+        context: &CausalContext<K>,
+        other: &Self,
+        other_context: &CausalContext<K>,
+    ) -> Self {
+        let values =
+            self.values
+                .iter()
+                .filter(|(dot, _)| other.values.contains_key(dot) || !other_context.contains(dot))
+                .chain(
+                    other.values.iter().filter(|(dot, _)| {
+                        !self.values.contains_key(dot) && !context.contains(dot)
+                    }),
+                )
+                .map(|(dot, value)| (dot.clone(), value.clone()))
+                .collect();
+        DotFun { values }
+    }
+}
+
+impl<K, V> StableDots<K> for DotFun<K, V>
+where
+    K: Eq + Hash + Clone + Ord,
+    V: PartialEq,
+{
+    fn collapse_stable(&mut self, stable: &VersionVector<K>) -> usize {
+        let redundant: Vec<Dot<K>> = self
+            .values
+            .iter()
+            .filter(|(dot, _)| stable.contains(dot))
+            .filter(|(dot, value)| {
+                let equal = self
+                    .values
+                    .iter()
+                    .filter(|(other, other_value)| other_value == value && stable.contains(other))
+                    .map(|(other, _)| other);
+                newest_dot(equal).as_ref() != Some(*dot)
+            })
+            .map(|(dot, _)| dot.clone())
+            .collect();
+        for dot in &redundant {
+            self.values.remove(dot);
+        }
+        redundant.len()
+    }
+}
@@ -0,0 +1,136 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    causality::{causal_context::CausalContext, dot::Dot, version_vector::VersionVector},
+    dot_store::{Causal, DotStore, StableDots},
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DotMap<M, S>
+where
+    M: Eq + Hash,
+{
+    pub entries: HashMap<M, S>,
+}
+
+impl<M, S> Default for DotMap<M, S>
+where
+    M: Eq + Hash,
+{
+    fn default() -> Self {
+        DotMap {
+            entries: HashMap::new(),
+        }
+    }
+}
+
+impl<M, S> DotMap<M, S>
+where
+    M: Eq + Hash + Clone,
+{
+    pub fn singleton(key: M, store: S) -> Self {
+        DotMap {
+            entries: HashMap::from([(key, store)]),
+        }
+    }
+
+    pub fn get(&self, key: &M) -> Option<&S> {
+        self.entries.get(key)
+    }
+}
+
+impl<K, M, S> Causal<K, DotMap<M, S>>
+where
+    K: Eq + Hash + Clone,
+    M: Eq + Hash + Clone,
+    S: DotStore<K>,
+{
+    pub fn restrict<F>(&self, in_scope: F) -> Self
+    where
+        F: Fn(&M) -> bool,
+    {
+        Causal {
+            store: DotMap {
+                entries: self
+                    .store
+                    .entries
+                    .iter()
+                    .filter(|(key, _)| in_scope(key))
+                    .map(|(key, store)| (key.clone(), store.clone()))
+                    .collect(),
+            },
+            context: self.context.clone(),
+        }
+    }
+
+    pub fn join_restricted<F>(&mut self, other: &Self, in_scope: F)
+    where
+        F: Fn(&M) -> bool,
+    {
+        let local = self.restrict(&in_scope).store;
+        let remote = other.restrict(&in_scope).store;
+        let joined = local.join(&self.context, &remote, &other.context);
+        self.store.entries.retain(|key, _| !in_scope(key));
+        self.store.entries.extend(joined.entries);
+        for dot in remote.dots() {
+            self.context.insert(dot);
+        }
+    }
+}
+
+impl<K, M, S> DotStore<K> for DotMap<M, S>
+where
+    K: Eq + Hash + Clone,
+    M: Eq + Hash + Clone,
+    S: DotStore<K>,
+{
+    fn dots(&self) -> HashSet<Dot<K>> {
+        self.entries
+            .values()
+            .flat_map(|store| store.dots())
+            .collect()
+    }
+
+    fn is_bottom(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn join(
+        &self,
+        context: &CausalContext<K>,
+        other: &Self,
+        other_context: &CausalContext<K>,
+    ) -> Self {
+        let bottom = S::default();
+        let keys: HashSet<&M> = self.entries.keys().chain(other.entries.keys()).collect();
+        let entries = keys
+            .into_iter()
+            .filter_map(|key| {
+                let left = self.entries.get(key).unwrap_or(&bottom);
+                let right = other.entries.get(key).unwrap_or(&bottom);
+                let store = left.join(context, right, other_context);
+                (!store.is_bottom()).then(|| (key.clone(), store))
+            })
+            .collect();
+        DotMap { entries }
+    }
+}
+
+impl<K, M, S> StableDots<K> for DotMap<M, S>
+where
+    K: Eq + Hash,
+    M: Eq + Hash,
+    S: StableDots<K>,
+{
+    fn collapse_stable(&mut self, stable: &VersionVector<K>) -> usize {
+        self.entries
+            .values_mut()
+            .map(|store| store.collapse_stable(stable))
+            .sum()
+    }
+}
@@ -0,0 +1,86 @@
+use std::{collections::HashSet, hash::Hash};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    causality::{causal_context::CausalContext, dot::Dot, version_vector::VersionVector},
+    dot_store::{newest_dot, DotStore, StableDots},
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DotSet<K>
+where
+    K: Eq + Hash,
+{
+    pub dots: HashSet<Dot<K>>,
+}
+
+impl<K> Default for DotSet<K>
+where
+    K: Eq + Hash,
+{
+    fn default() -> Self {
+        DotSet {
+            dots: HashSet::new(),
+        }
+    }
+}
+
+impl<K> DotSet<K>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn singleton(dot: Dot<K>) -> Self {
+        DotSet {
+            dots: HashSet::from([dot]),
+        }
+    }
+}
+
+impl<K> DotStore<K> for DotSet<K>
+where
+    K: Eq + Hash + Clone,
+{
+    fn dots(&self) -> HashSet<Dot<K>> {
+        self.dots.clone()
+    }
+
+    fn is_bottom(&self) -> bool {
+        self.dots.is_empty()
+    }
+
+    fn join(
+        &self,
+        context: &CausalContext<K>,
+        other: &Self,
+        other_context: &CausalContext<K>,
+    ) -> Self {
+        let dots = self
+            .dots
+            .iter()
+            .filter(|dot| other.dots.contains(dot) || !other_context.contains(dot))
+            .chain(
+                other
+                    .dots
+                    .iter()
+                    .filter(|dot| !self.dots.contains(dot) && !context.contains(dot)),
+            )
+            .cloned()
+            .collect();
+        DotSet { dots }
+    }
+}
+
+impl<K> StableDots<K> for DotSet<K>
+where
+    K: Eq + Hash + Clone + Ord,
+{
+    fn collapse_stable(&mut self, stable: &VersionVector<K>) -> usize {
+        if self.dots.len() < 2 || !self.dots.iter().all(|dot| stable.contains(dot)) {
+            return 0;
+        }
+        let purged = self.dots.len() - 1;
+        self.dots = newest_dot(self.dots.iter()).into_iter().collect();
+        purged
+    }
+}
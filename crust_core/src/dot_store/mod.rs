@@ -0,0 +1,207 @@
+use std::{collections::HashSet, hash::Hash};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    causality::{
+        causal_context::CausalContext, dot::Dot, stability::StablePurge,
+        version_vector::VersionVector,
+    },
+    sync::{Crdt, DeltaBased, StateBased},
+};
+
+pub mod dot_fun;
+pub mod dot_map;
+pub mod dot_set;
+
+pub trait DotStore<K>: Clone + Default
+where
+    K: Eq + Hash + Clone,
+{
+    fn dots(&self) -> HashSet<Dot<K>>;
+    fn is_bottom(&self) -> bool;
+    fn join(
+        &self,
+        context: &CausalContext<K>,
+        other: &Self,
+        other_context: &CausalContext<K>,
+    ) -> Self;
+}
+
+pub trait StableDots<K>
+where
+    K: Eq + Hash,
+{
+    fn collapse_stable(&mut self, stable: &VersionVector<K>) -> usize;
+}
+
+pub(crate) fn newest_dot<'a, K>(dots: impl Iterator<Item = &'a Dot<K>>) -> Option<Dot<K>>
+where
+    K: Ord + Clone + 'a,
+{
+    dots.max_by(|a, b| (a.counter, &a.replica).cmp(&(b.counter, &b.replica)))
+        .cloned()
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Causal<K, S>
+where
+    K: Eq + Hash,
+{
+    pub store: S,
+    pub context: CausalContext<K>,
+}
+
+impl<K, S> Default for Causal<K, S>
+where
+    K: Eq + Hash + Clone,
+    S: DotStore<K>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, S> Causal<K, S>
+where
+    K: Eq + Hash + Clone,
+    S: DotStore<K>,
+{
+    pub fn new() -> Self {
+        Causal {
+            store: S::default(),
+            context: CausalContext::new(),
+        }
+    }
+
+    pub fn next_dot(&self, replica: K) -> Dot<K> {
+        Dot::new(replica.clone(), self.context.max(&replica) + 1)
+    }
+
+    pub fn join(&mut self, other: &Self) {
+        self.store = self.store.join(&self.context, &other.store, &other.context);
+        self.context.merge(&other.context);
+    }
+
+    pub fn is_bottom(&self) -> bool {
+        self.store.is_bottom() && self.context.is_empty()
+    }
+}
+
+pub trait CausalCrdt: Clone + Default {
+    type Replica: Eq + Hash + Clone;
+    type Store: DotStore<Self::Replica>;
+
+    fn type_name() -> String;
+    fn causal(&self) -> &Causal<Self::Replica, Self::Store>;
+    fn causal_mut(&mut self) -> &mut Causal<Self::Replica, Self::Store>;
+    fn delta_buffer_mut(&mut self) -> &mut Option<Causal<Self::Replica, Self::Store>>;
+    fn delta_buffer(&self) -> &Option<Causal<Self::Replica, Self::Store>>;
+
+    fn mutate(
+        &mut self,
+        delta: Causal<Self::Replica, Self::Store>,
+    ) -> Causal<Self::Replica, Self::Store> {
+        self.causal_mut().join(&delta);
+        match self.delta_buffer_mut() {
+            Some(buffer) => buffer.join(&delta),
+            buffer => *buffer = Some(delta.clone()),
+        }
+        delta
+    }
+
+    fn take_delta(&mut self) -> Option<Causal<Self::Replica, Self::Store>> {
+        self.delta_buffer_mut().take()
+    }
+}
+
+impl<T> StablePurge<T::Replica> for T
+where
+    T: CausalCrdt,
+    T::Store: StableDots<T::Replica>,
+{
+    fn purge_stable(&mut self, stable: &VersionVector<T::Replica>) -> usize {
+        self.causal_mut().store.collapse_stable(stable)
+    }
+}
+
+impl<T> Crdt for T
+where
+    T: CausalCrdt,
+{
+    type State = T;
+
+    fn new() -> Self::State {
+        T::default()
+    }
+
+    fn get_state(&self) -> Self::State {
+        self.clone()
+    }
+
+    fn name() -> String {
+        T::type_name()
+    }
+}
+
+impl<T> StateBased for T
+where
+    T: CausalCrdt,
+{
+    fn merge(&mut self, other: &Self::State) -> Self::State {
+        self.causal_mut().join(other.causal());
+        self.clone()
+    }
+}
+
+impl<T> DeltaBased for T
+where
+    T: CausalCrdt,
+{
+    type Delta = Causal<T::Replica, T::Store>;
+
+    fn generate_delta(&self) -> Self::Delta {
+        self.delta_buffer()
+            .clone()
+            .unwrap_or_else(|| self.causal().clone())
+    }
+
+    fn merge_delta(&mut self, other: &Self::Delta) -> Self::State {
+        self.causal_mut().join(other);
+        self.clone()
+    }
+
+    fn aggregate_deltas(&mut self, deltas: Vec<Self::Delta>) -> Option<Self::Delta> {
+        deltas.into_iter().reduce(|mut aggregate, delta| {
+            aggregate.join(&delta);
+            aggregate
+        })
+    }
+}
+
+pub(crate) mod dot_pairs {
+    use std::{collections::HashMap, hash::Hash};
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::causality::dot::Dot;
+
+    pub fn serialize<K, V, S>(map: &HashMap<Dot<K>, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<Dot<K>, V>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let pairs = Vec::<(Dot<K>, V)>::deserialize(deserializer)?;
+        Ok(pairs.into_iter().collect())
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CrustError {
+    UnknownCrdtType(String),
+    DuplicateCrdtType(String),
+    UnknownSyncType(String),
+    UnknownSyncMode(String),
+    CrdtNotInitialized,
+    ObjectNotFound(String),
+    TypeMismatch { expected: String, found: String },
+    InvalidCommand { crdt_type: String, command: String },
+    ConstraintViolation(String),
+    InvalidOperation(String),
+    NotReversible(String),
+    Serialization(String),
+    Network(String),
+    Storage(String),
+    UnsupportedEncoding(String),
+    IncompatibleSchema { found: u32, supported: u32 },
+}
+
+impl CrustError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CrustError::UnknownCrdtType(_) => "unknown_crdt_type",
+            CrustError::DuplicateCrdtType(_) => "duplicate_crdt_type",
+            CrustError::UnknownSyncType(_) => "unknown_sync_type",
+            CrustError::UnknownSyncMode(_) => "unknown_sync_mode",
+            CrustError::CrdtNotInitialized => "crdt_not_initialized",
+            CrustError::ObjectNotFound(_) => "object_not_found",
+            CrustError::TypeMismatch { .. } => "type_mismatch",
+            CrustError::InvalidCommand { .. } => "invalid_command",
+            CrustError::ConstraintViolation(_) => "constraint_violation",
+            CrustError::InvalidOperation(_) => "invalid_operation",
+            CrustError::NotReversible(_) => "not_reversible",
+            CrustError::Serialization(_) => "serialization",
+            CrustError::Network(_) => "network",
+            CrustError::Storage(_) => "storage",
+            CrustError::UnsupportedEncoding(_) => "unsupported_encoding",
+            CrustError::IncompatibleSchema { .. } => "incompatible_schema",
+        }
+    }
+}
+
+impl fmt::Display for CrustError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrustError::UnknownCrdtType(name) => write!(f, "unknown CRDT type `{name}`"),
+            CrustError::DuplicateCrdtType(name) => {
+                write!(f, "CRDT type `{name}` is already registered")
+            }
+            CrustError::UnknownSyncType(name) => write!(f, "unknown sync type `{name}`"),
+            CrustError::UnknownSyncMode(name) => write!(f, "unknown sync mode `{name}`"),
+            CrustError::CrdtNotInitialized => write!(f, "no CRDT has been initialized"),
+            CrustError::ObjectNotFound(name) => write!(f, "no CRDT object named `{name}`"),
+            CrustError::TypeMismatch { expected, found } => {
+                write!(f, "expected CRDT type `{expected}`, found `{found}`")
+            }
+            CrustError::InvalidCommand { crdt_type, command } => {
+                write!(
+                    f,
+                    "command {command} is not valid for CRDT type `{crdt_type}`"
+                )
+            }
+            CrustError::ConstraintViolation(reason) => {
+                write!(f, "constraint violated: {reason}")
+            }
+            CrustError::InvalidOperation(reason) => write!(f, "invalid operation: {reason}"),
+            CrustError::NotReversible(name) => {
+                write!(f, "CRDT type `{name}` does not support undo")
+            }
+            CrustError::Serialization(reason) => write!(f, "serialization failed: {reason}"),
+            CrustError::Network(reason) => write!(f, "network error: {reason}"),
+            CrustError::Storage(reason) => write!(f, "storage error: {reason}"),
+            CrustError::UnsupportedEncoding(content_type) => {
+                write!(f, "unsupported encoding `{content_type}`")
+            }
+            CrustError::IncompatibleSchema { found, supported } => write!(
+                f,
+                "schema version {found} is incompatible with supported version {supported}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CrustError {}
+
+impl From<serde_json::Error> for CrustError {
+    fn from(error: serde_json::Error) -> Self {
+        CrustError::Serialization(error.to_string())
+    }
+}
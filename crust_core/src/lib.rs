@@ -1,7 +1,15 @@
+pub mod anti_entropy;
+pub mod causality;
 pub mod command;
+pub mod constraint;
 pub mod core;
 pub mod delta;
+pub mod digest;
+pub mod dot_store;
+pub mod error;
 pub mod operation;
+pub mod registry;
+pub mod schema;
 pub mod security;
 pub mod sync;
 pub mod r#type;
@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use crate::causality::dot::Dot;
+
+pub type OperationId<K> = Dot<K>;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum CounterOperation<K> {
     Increment { value: K },
@@ -13,6 +17,11 @@ pub enum GraphOperation<K> {
     RemoveEdge { from: K, to: K },
 }
 #[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum MapOperation<K> {
+    Put { key: K, value: K },
+    Remove { key: K },
+}
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum SetOperation<K> {
     Add { value: K },
     Remove { value: K },
@@ -27,6 +36,7 @@ pub enum TextOperation<K> {
 pub enum CrdtOperation<K> {
     Counter(CounterOperation<K>),
     Graph(GraphOperation<K>),
+    Map(MapOperation<K>),
     Set(SetOperation<K>),
     Text(TextOperation<K>),
 }
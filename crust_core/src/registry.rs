@@ -0,0 +1,259 @@
+use std::{
+    any::{Any, TypeId},
+    collections::{hash_map::Entry, HashMap},
+    fmt::{self, Debug},
+    hash::Hash,
+    sync::{OnceLock, RwLock},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::{
+    causality::version_vector::VersionVector,
+    command::CrdtInnerCommand,
+    constraint::{ConstraintRule, ConstraintView},
+    core::{
+        counter::{gcounter::GCounter, pncounter::PNCounter},
+        map::mvmap::MVMap,
+        set::orset::ORSet,
+    },
+    error::CrustError,
+    operation::CrdtOperation,
+    sync::Crdt,
+};
+
+pub trait CrdtKey:
+    Eq + Hash + Clone + Debug + Serialize + DeserializeOwned + Send + Sync + 'static
+{
+}
+
+impl<K> CrdtKey for K where
+    K: Eq + Hash + Clone + Debug + Serialize + DeserializeOwned + Send + Sync + 'static
+{
+}
+
+pub trait CrdtObject<K>: Send + Sync {
+    fn type_name(&self) -> String;
+    fn commands(&self, value: K) -> Vec<CrdtInnerCommand<K>>;
+    fn apply_command(
+        &mut self,
+        command: &CrdtInnerCommand<K>,
+    ) -> Result<CrdtOperation<K>, CrustError>;
+    fn apply(&mut self, operation: &CrdtOperation<K>) -> Result<(), CrustError>;
+    fn get_state(&self) -> Value;
+    fn to_state(&self) -> Result<Value, CrustError>;
+    fn merge(&mut self, state: &Value) -> Result<(), CrustError>;
+    fn generate_delta(&self) -> Result<Value, CrustError>;
+    fn merge_delta(&mut self, delta: &Value) -> Result<(), CrustError>;
+    fn aggregate_deltas(&self, deltas: Vec<Value>) -> Result<Option<Value>, CrustError>;
+    fn clone_object(&self) -> Box<dyn CrdtObject<K>>;
+
+    fn take_delta(&mut self) -> Result<Value, CrustError> {
+        self.generate_delta()
+    }
+
+    fn aggregate_operations(&self, _operations: Vec<CrdtOperation<K>>) -> Option<CrdtOperation<K>> {
+        None
+    }
+
+    fn as_constrained(&self) -> Option<&dyn Constrained<K>> {
+        None
+    }
+
+    fn as_constrained_mut(&mut self) -> Option<&mut dyn Constrained<K>> {
+        None
+    }
+
+    fn as_reversible(&self) -> Option<&dyn Reversible<K>> {
+        None
+    }
+
+    fn as_stabilizable(&self) -> Option<&dyn Stabilizable> {
+        None
+    }
+
+    fn as_stabilizable_mut(&mut self) -> Option<&mut dyn Stabilizable> {
+        None
+    }
+
+    fn as_retirable(&self) -> Option<&dyn Retirable> {
+        None
+    }
+
+    fn as_retirable_mut(&mut self) -> Option<&mut dyn Retirable> {
+        None
+    }
+
+    fn assign_replica(&mut self, _replica: &str) {}
+
+    fn as_reconcilable(&self) -> Option<&dyn Reconcilable> {
+        None
+    }
+
+    fn as_reconcilable_mut(&mut self) -> Option<&mut dyn Reconcilable> {
+        None
+    }
+}
+
+pub trait Constrained<K> {
+    fn constraint_view(&self) -> ConstraintView<K>;
+    fn repair_constraints(&mut self, rules: &[ConstraintRule<K>]) -> Result<(), CrustError>;
+}
+
+pub trait Stabilizable {
+    fn purge_stable(&mut self, stable: &VersionVector<String>) -> usize;
+    fn version_vector(&self) -> VersionVector<String>;
+}
+
+pub trait Retirable {
+    fn contributions(&self) -> VersionVector<String>;
+    fn retire_departed(
+        &mut self,
+        departed: &[String],
+        stable: &VersionVector<String>,
+    ) -> Vec<String>;
+    fn retirement(&self) -> Option<Value>;
+    fn fold_retired(&mut self, peer_retirements: &[Value]) -> Result<bool, CrustError>;
+}
+
+pub trait Reconcilable {
+    fn entry_digests(&self) -> Vec<(String, u64)>;
+    fn entries_state(&self, in_scope: &dyn Fn(&str) -> bool) -> Result<Value, CrustError>;
+    fn merge_entries(
+        &mut self,
+        in_scope: &dyn Fn(&str) -> bool,
+        entries: &Value,
+    ) -> Result<(), CrustError>;
+}
+
+pub trait Reversible<K> {
+    fn inverse_operation(&self, operation: &CrdtOperation<K>) -> Option<CrdtOperation<K>>;
+}
+
+impl<K> Clone for Box<dyn CrdtObject<K>> {
+    fn clone(&self) -> Self {
+        self.clone_object()
+    }
+}
+
+impl<K> Debug for dyn CrdtObject<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CrdtObject")
+            .field("type_name", &self.type_name())
+            .field("state", &self.get_state())
+            .finish()
+    }
+}
+
+pub type CrdtFactory<K> = fn() -> Box<dyn CrdtObject<K>>;
+
+type Factories = HashMap<(TypeId, String), Box<dyn Any + Send + Sync>>;
+
+fn factories() -> &'static RwLock<Factories> {
+    static FACTORIES: OnceLock<RwLock<Factories>> = OnceLock::new();
+    FACTORIES.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn builtin_factories<K>() -> Vec<(String, CrdtFactory<K>)>
+where
+    K: CrdtKey,
+{
+    vec![
+        (GCounter::<K>::name(), || Box::new(GCounter::<K>::new())),
+        (PNCounter::<K>::name(), || Box::new(PNCounter::<K>::new())),
+        (ORSet::<K>::name(), || Box::new(ORSet::<K>::new())),
+        (MVMap::<K>::name(), || Box::new(MVMap::<K>::new())),
+    ]
+}
+
+pub fn register_crdt_type<K>(name: &str, factory: CrdtFactory<K>) -> Result<(), CrustError>
+where
+    K: CrdtKey,
+{
+    if builtin_factories::<K>()
+        .iter()
+        .any(|(builtin, _)| builtin == name)
+    {
+        return Err(CrustError::DuplicateCrdtType(name.to_string()));
+    }
+    match factories()
+        .write()
+        .unwrap()
+        .entry((TypeId::of::<K>(), name.to_string()))
+    {
+        Entry::Occupied(_) => Err(CrustError::DuplicateCrdtType(name.to_string())),
+        Entry::Vacant(entry) => {
+            entry.insert(Box::new(factory));
+            Ok(())
+        }
+    }
+}
+
+pub fn is_registered<K>(name: &str) -> bool
+where
+    K: CrdtKey,
+{
+    lookup_factory::<K>(name).is_some()
+}
+
+pub fn registered_crdt_types<K>() -> Vec<String>
+where
+    K: CrdtKey,
+{
+    let mut names: Vec<String> = builtin_factories::<K>()
+        .into_iter()
+        .map(|(name, _)| name)
+        .chain(
+            factories()
+                .read()
+                .unwrap()
+                .keys()
+                .filter(|(type_id, _)| *type_id == TypeId::of::<K>())
+                .map(|(_, name)| name.clone()),
+        )
+        .collect();
+    names.sort();
+    names
+}
+
+pub fn create_crdt_object<K>(name: &str) -> Result<Box<dyn CrdtObject<K>>, CrustError>
+where
+    K: CrdtKey,
+{
+    lookup_factory::<K>(name)
+        .map(|factory| factory())
+        .ok_or_else(|| CrustError::UnknownCrdtType(name.to_string()))
+}
+
+fn lookup_factory<K>(name: &str) -> Option<CrdtFactory<K>>
+where
+    K: CrdtKey,
+{
+    builtin_factories::<K>()
+        .into_iter()
+        .find(|(builtin, _)| builtin == name)
+        .map(|(_, factory)| factory)
+        .or_else(|| {
+            factories()
+                .read()
+                .unwrap()
+                .get(&(TypeId::of::<K>(), name.to_string()))
+                .and_then(|factory| factory.downcast_ref::<CrdtFactory<K>>())
+                .copied()
+        })
+}
+
+pub fn to_value<T>(value: &T) -> Result<Value, CrustError>
+where
+    T: Serialize,
+{
+    Ok(serde_json::to_value(value)?)
+}
+
+pub fn from_value<T>(value: &Value) -> Result<T, CrustError>
+where
+    T: DeserializeOwned,
+{
+    Ok(T::deserialize(value)?)
+}
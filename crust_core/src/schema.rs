@@ -0,0 +1,77 @@
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
+use serde_json::Value;
+
+use crate::{core::counter::gcounter::migrate_gcounter_v1, error::CrustError};
+
+pub const SCHEMA_VERSION: u32 = 2;
+pub const LEGACY_SCHEMA_VERSION: u32 = 1;
+
+pub type Migration = fn(Value) -> Result<Value, CrustError>;
+
+type Migrations = HashMap<(String, u32), Migration>;
+
+fn migrations() -> &'static RwLock<Migrations> {
+    static MIGRATIONS: OnceLock<RwLock<Migrations>> = OnceLock::new();
+    MIGRATIONS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn builtin_migrations() -> Vec<(String, u32, Migration)> {
+    vec![("gcounter".to_string(), 1, migrate_gcounter_v1)]
+}
+
+pub fn register_migration(
+    crdt_type: &str,
+    from_version: u32,
+    migration: Migration,
+) -> Result<(), CrustError> {
+    if !(LEGACY_SCHEMA_VERSION..SCHEMA_VERSION).contains(&from_version) {
+        return Err(CrustError::IncompatibleSchema {
+            found: from_version,
+            supported: SCHEMA_VERSION,
+        });
+    }
+    migrations()
+        .write()
+        .unwrap()
+        .insert((crdt_type.to_string(), from_version), migration);
+    Ok(())
+}
+
+pub fn check_schema_version(version: u32) -> Result<(), CrustError> {
+    if (LEGACY_SCHEMA_VERSION..=SCHEMA_VERSION).contains(&version) {
+        Ok(())
+    } else {
+        Err(CrustError::IncompatibleSchema {
+            found: version,
+            supported: SCHEMA_VERSION,
+        })
+    }
+}
+
+pub fn migrate_state(crdt_type: &str, version: u32, state: Value) -> Result<Value, CrustError> {
+    check_schema_version(version)?;
+    (version..SCHEMA_VERSION).try_fold(state, |state, from_version| {
+        match lookup_migration(crdt_type, from_version) {
+            Some(migration) => migration(state),
+            None => Ok(state),
+        }
+    })
+}
+
+fn lookup_migration(crdt_type: &str, from_version: u32) -> Option<Migration> {
+    migrations()
+        .read()
+        .unwrap()
+        .get(&(crdt_type.to_string(), from_version))
+        .copied()
+        .or_else(|| {
+            builtin_migrations()
+                .into_iter()
+                .find(|(name, version, _)| name == crdt_type && *version == from_version)
+                .map(|(_, _, migration)| migration)
+        })
+}
@@ -1,12 +1,26 @@
 use std::hash::Hash;
 
-use crate::{delta::CrdtDelta, operation::CrdtOperation, r#type::CrdtType, sync::Crdt};
+#[cfg(any(
+    feature = "byzantine",
+    feature = "confidentiality",
+    feature = "integrity",
+    feature = "access_control"
+))]
+use crate::r#type::CrdtType;
+#[cfg(feature = "byzantine")]
+use crate::{delta::CrdtDelta, operation::CrdtOperation};
 
 pub struct SecurityContext {
     pub request_id: Option<String>,
     pub timestamp: u64,
 }
 
+impl Default for SecurityContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SecurityContext {
     pub fn new() -> Self {
         Self {
@@ -25,7 +39,7 @@ where
     #[cfg(feature = "byzantine")]
     fn validate_operation(&self, operation: &CrdtOperation<K>) -> bool;
     #[cfg(feature = "byzantine")]
-    fn validate_delta(&self, delta: &CrdtDelta<K>) -> bool;
+    fn validate_delta(&self, delta: &CrdtDelta) -> bool;
 
     #[cfg(feature = "confidentiality")]
     fn encrypt_data(&self, data: &CrdtType<K>) -> CrdtType<K>;
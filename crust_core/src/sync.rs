@@ -1,6 +1,8 @@
+use std::fmt;
+#[cfg(feature = "batch")]
 use std::time::Duration;
 
-use crate::command::CrdtInnerCommand;
+use crate::{constraint::ConstraintRule, error::CrustError};
 
 pub trait Crdt {
     type State;
@@ -24,8 +26,8 @@ pub trait DeltaBased: Crdt {
 }
 
 pub trait ConstraintEnforcing<K>: Crdt {
-    fn check_constraints(&self, command: &CrdtInnerCommand<K>) -> bool;
-    fn repair_constraints(&mut self) -> Self::State;
+    fn check_constraints(&self, rules: &[ConstraintRule<K>]) -> Result<(), CrustError>;
+    fn repair_constraints(&mut self, rules: &[ConstraintRule<K>]) -> Self::State;
 }
 
 #[derive(Clone, Copy)]
@@ -36,20 +38,22 @@ pub enum SyncType {
 }
 
 impl SyncType {
-    pub fn new(name: String) -> Self {
+    pub fn new(name: String) -> Result<Self, CrustError> {
         match name.as_str() {
-            "delta" => SyncType::Delta,
-            "operation" => SyncType::Operation,
-            "state" => SyncType::State,
-            _ => panic!("Unknown sync type"),
+            "delta" => Ok(SyncType::Delta),
+            "operation" => Ok(SyncType::Operation),
+            "state" => Ok(SyncType::State),
+            _ => Err(CrustError::UnknownSyncType(name)),
         }
     }
+}
 
-    pub fn to_string(&self) -> String {
+impl fmt::Display for SyncType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            SyncType::Delta => "delta".to_string(),
-            SyncType::Operation => "operation".to_string(),
-            SyncType::State => "state".to_string(),
+            SyncType::Delta => write!(f, "delta"),
+            SyncType::Operation => write!(f, "operation"),
+            SyncType::State => write!(f, "state"),
         }
     }
 }
@@ -64,29 +68,30 @@ pub enum SyncMode {
 }
 
 impl SyncMode {
-    pub fn new(name: String) -> Self {
+    pub fn new(name: String) -> Result<Self, CrustError> {
         match name.as_str() {
-            "immediate" => SyncMode::Immediate,
+            "immediate" => Ok(SyncMode::Immediate),
             #[cfg(feature = "batch")]
-            "batch_time_based" => SyncMode::BatchTimeBased,
+            "batch_time_based" => Ok(SyncMode::BatchTimeBased),
             #[cfg(feature = "batch")]
-            "batch_count_based" => SyncMode::BatchCountBased,
-            _ => panic!("Unknown sync mode"),
+            "batch_count_based" => Ok(SyncMode::BatchCountBased),
+            _ => Err(CrustError::UnknownSyncMode(name)),
         }
     }
+}
 
-    pub fn to_string(&self) -> String {
+impl fmt::Display for SyncMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            SyncMode::Immediate => "immediate".to_string(),
+            SyncMode::Immediate => write!(f, "immediate"),
             #[cfg(feature = "batch")]
-            SyncMode::BatchTimeBased => "batch_time_based".to_string(),
+            SyncMode::BatchTimeBased => write!(f, "batch_time_based"),
             #[cfg(feature = "batch")]
-            SyncMode::BatchCountBased => "batch_count_based".to_string(),
+            SyncMode::BatchCountBased => write!(f, "batch_count_based"),
         }
     }
 }
 
-
 pub struct SyncConfig {
     pub sync_type: SyncType,
     pub sync_mode: SyncMode,
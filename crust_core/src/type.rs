@@ -1,83 +1,149 @@
-use std::{
-    collections::HashMap,
-    hash::Hash,
-    time::{Duration, SystemTime, UNIX_EPOCH},
-};
+#[cfg(feature = "batch")]
+use std::time::Duration;
+#[cfg(any(feature = "batch", feature = "reversible"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fmt, hash::Hash};
 
-use serde::{de::value, Deserialize, Serialize};
-use serde_json::{json, Value};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
 
+#[cfg(feature = "constraints")]
+use crate::constraint::{check_rules, ConstraintRule, ConstraintView, RepairPolicy};
+#[cfg(any(
+    feature = "byzantine",
+    feature = "confidentiality",
+    feature = "integrity",
+    feature = "access_control"
+))]
+use crate::security::SecurityHook;
+#[cfg(feature = "batch")]
+use crate::sync::{SyncConfig, SyncMode};
 use crate::{
-    command::{CounterInnerCommand, CrdtInnerCommand},
-    core::counter::gcounter::GCounter,
+    causality::version_vector::VersionVector,
+    command::CrdtInnerCommand,
     delta::CrdtDelta,
-    operation::{CounterOperation, CrdtOperation},
-    security::{self, SecurityHook},
-    sync::{Crdt, DeltaBased, OperationBased, StateBased, SyncConfig, SyncMode},
+    error::CrustError,
+    operation::CrdtOperation,
+    registry::{create_crdt_object, CrdtKey, CrdtObject},
+    schema::{migrate_state, LEGACY_SCHEMA_VERSION, SCHEMA_VERSION},
 };
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub enum CrdtTypeVariant<K>
-where
-    K: Eq + Hash,
-{
-    GCounter(GCounter<K>),
-}
-
-#[cfg(feature = "constraints")]
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub enum ConstraintRule<K> {
-    MaxValue(u64),
-    MinValue(u64),
-    RangeValue(u64, u64),
-}
-
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone)]
 pub struct CrdtType<K>
 where
     K: Eq + Hash,
 {
-    pub variant: CrdtTypeVariant<K>,
+    pub object: Box<dyn CrdtObject<K>>,
     #[cfg(feature = "batch")]
     pub operations_buffer: Vec<CrdtOperation<K>>,
     #[cfg(feature = "batch")]
-    pub deltas_buffer: Vec<CrdtDelta<K>>,
+    pub deltas_buffer: Vec<CrdtDelta>,
     #[cfg(any(
         feature = "byzantine",
         feature = "confidentiality",
         feature = "integrity",
         feature = "access_control"
     ))]
-    #[serde(skip)]
     security: Option<Box<dyn SecurityHook<K> + Send + Sync>>,
     #[cfg(feature = "constraints")]
-    pub constraints: Option<Vec<ConstraintRule<K>>>,
+    pub constraints: Vec<ConstraintRule<K>>,
+    #[cfg(feature = "constraints")]
+    pub repair_policy: RepairPolicy,
+    #[cfg(feature = "constraints")]
+    pub repairing: bool,
     #[cfg(feature = "reversible")]
     pub operation_history: Vec<(CrdtOperation<K>, i64)>,
+    #[cfg(feature = "reversible")]
+    pub undone_operations: Vec<(CrdtOperation<K>, i64)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedCrdtType {
+    #[serde(default = "legacy_schema_version")]
+    version: u32,
+    crdt_type: String,
+    state: Value,
+}
+
+fn legacy_schema_version() -> u32 {
+    LEGACY_SCHEMA_VERSION
+}
+
+impl<K> fmt::Debug for CrdtType<K>
+where
+    K: Eq + Hash,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CrdtType")
+            .field("object", &self.object)
+            .finish()
+    }
+}
+
+impl<K> Serialize for CrdtType<K>
+where
+    K: Eq + Hash,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        SerializedCrdtType {
+            version: SCHEMA_VERSION,
+            crdt_type: self.object.type_name(),
+            state: self.object.to_state().map_err(ser::Error::custom)?,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, K> Deserialize<'de> for CrdtType<K>
+where
+    K: CrdtKey,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let serialized = SerializedCrdtType::deserialize(deserializer)?;
+        let state = migrate_state(&serialized.crdt_type, serialized.version, serialized.state)
+            .map_err(de::Error::custom)?;
+        let mut crdt = CrdtType::new(serialized.crdt_type).map_err(de::Error::custom)?;
+        crdt.object.merge(&state).map_err(de::Error::custom)?;
+        Ok(crdt)
+    }
 }
 
 impl<K> CrdtType<K>
 where
-    CrdtType<K>: Clone,
-    K: Eq + Hash + Clone + Serialize + for<'a> Deserialize<'a>,
+    K: CrdtKey,
 {
-    pub fn new(name: String) -> Option<Self> {
-        match name.as_str() {
-            "gcounter" => Some(CrdtType {
-                variant: CrdtTypeVariant::GCounter(GCounter::new()),
-                #[cfg(feature = "batch")]
-                operations_buffer: Vec::new(),
-                #[cfg(feature = "batch")]
-                deltas_buffer: Vec::new(),
-            }),
-            _ => None,
+    pub fn new(name: String) -> Result<Self, CrustError> {
+        Ok(CrdtType::from_object(create_crdt_object(&name)?))
+    }
+
+    pub fn from_object(object: Box<dyn CrdtObject<K>>) -> Self {
+        CrdtType {
+            object,
+            #[cfg(feature = "batch")]
+            operations_buffer: Vec::new(),
+            #[cfg(feature = "batch")]
+            deltas_buffer: Vec::new(),
+            #[cfg(feature = "constraints")]
+            constraints: Vec::new(),
+            #[cfg(feature = "constraints")]
+            repair_policy: RepairPolicy::default(),
+            #[cfg(feature = "constraints")]
+            repairing: true,
+            #[cfg(feature = "reversible")]
+            operation_history: Vec::new(),
+            #[cfg(feature = "reversible")]
+            undone_operations: Vec::new(),
         }
     }
 
     pub fn name(&self) -> String {
-        match &self.variant {
-            CrdtTypeVariant::GCounter(_) => "gcounter".to_string(),
-        }
+        self.object.type_name()
     }
 
     #[cfg(any(
@@ -105,97 +171,246 @@ where
     }
 
     #[cfg(feature = "constraints")]
-    pub fn check_constraints(&self, command: &CrdtInnerCommand<K>) -> bool {
-        match &self.variant {
-            CrdtTypeVariant::GCounter(gcounter) => match command {
-                CrdtInnerCommand::Counter(CounterInnerCommand::Increment { value }) => {
-                    gcounter.check_constraints(value)
-                }
-            },
-        }
+    pub fn with_constraints(
+        mut self,
+        rules: Vec<ConstraintRule<K>>,
+        repair_policy: RepairPolicy,
+    ) -> Result<Self, CrustError> {
+        self.set_constraint_rules(rules)?;
+        self.repair_policy = repair_policy;
+        Ok(self)
     }
 
     #[cfg(feature = "constraints")]
-    pub fn set_constraint_rules(&mut self) -> Result<(), String> {
-        let rules = match &self.variant {
-            CrdtTypeVariant::GCounter(_) => {
-                vec![ConstraintRule::MaxValue(1000), ConstraintRule::MinValue(0)]
-            }
-        };
-
-        for rule in &rules {
-            if !self.is_rule_valid_for_type(rule) {
-                return Err(format!(
-                    "Rule {:?} is not valid for CRDT type {}",
-                    rule,
-                    self.name()
-                ));
-            }
-        }
+    pub fn check_constraints(&self) -> Result<(), CrustError> {
+        check_rules(&self.constraints, &constraint_view(self.object.as_ref()))
+    }
 
-        self.constraints = Some(rules);
+    #[cfg(feature = "constraints")]
+    pub fn set_constraint_rules(
+        &mut self,
+        rules: Vec<ConstraintRule<K>>,
+    ) -> Result<(), CrustError> {
+        let view = constraint_view(self.object.as_ref());
+        if let Some(rule) = rules.iter().find(|rule| !rule.applies_to(&view)) {
+            return Err(CrustError::ConstraintViolation(format!(
+                "rule {:?} is not valid for CRDT type {}",
+                rule,
+                self.name()
+            )));
+        }
+        self.constraints = rules;
         Ok(())
     }
 
     #[cfg(feature = "constraints")]
-    fn is_rule_valid_for_type(&self, rule: &ConstraintRule<K>) -> bool {
-        match &self.variant {
-            CrdtTypeVariant::GCounter(_) => match rule {
-                ConstraintRule::MaxValue(_) => true,
-                ConstraintRule::MinValue(_) => true,
-                ConstraintRule::RangeValue(_, _) => true,
-            },
-        }
+    pub fn get_constraint_rules(&self) -> &[ConstraintRule<K>] {
+        &self.constraints
+    }
+
+    #[cfg(feature = "constraints")]
+    pub fn set_repair_policy(&mut self, repair_policy: RepairPolicy) {
+        self.repair_policy = repair_policy;
     }
 
     #[cfg(feature = "constraints")]
-    pub fn get_constraint_rules(&self) -> Option<&Vec<ConstraintRule<K>>> {
-        self.constraints.as_ref()
+    pub fn set_repairing(&mut self, repairing: bool) {
+        self.repairing = repairing;
+    }
+
+    fn merge_replicated<F>(&mut self, merge: F) -> Result<(), CrustError>
+    where
+        F: FnOnce(&mut dyn CrdtObject<K>) -> Result<(), CrustError>,
+    {
+        #[cfg(feature = "constraints")]
+        if self.repair_policy == RepairPolicy::Reject && !self.constraints.is_empty() {
+            let mut candidate = self.object.clone();
+            merge(candidate.as_mut())?;
+            check_rules(&self.constraints, &constraint_view(candidate.as_ref()))?;
+            self.object = candidate;
+            return Ok(());
+        }
+        merge(self.object.as_mut())?;
+        #[cfg(feature = "constraints")]
+        if self.repair_policy == RepairPolicy::Repair
+            && self.repairing
+            && self.check_constraints().is_err()
+        {
+            if let Some(constrained) = self.object.as_constrained_mut() {
+                let _ = constrained.repair_constraints(&self.constraints);
+            }
+        }
+        Ok(())
     }
 
     #[cfg(feature = "reversible")]
-    pub fn compute_inverse_operation(&self, operation: &CrdtOperation<K>) -> CrdtOperation<K> {
-        match &self.variant {
-            CrdtTypeVariant::GCounter(gcounter) => match operation {
-                CrdtOperation::Counter(CounterOperation::Increment { value }) => {
-                    CrdtOperation::Counter(CounterOperation::Decrement {
-                        value: value.clone(),
-                    })
-                }
-            },
+    pub fn compute_inverse_operation(
+        &self,
+        operation: &CrdtOperation<K>,
+    ) -> Option<CrdtOperation<K>> {
+        self.object
+            .as_reversible()
+            .and_then(|reversible| reversible.inverse_operation(operation))
+    }
+
+    #[cfg(feature = "reversible")]
+    fn ensure_reversible(&self) -> Result<(), CrustError> {
+        match self.object.as_reversible() {
+            Some(_) => Ok(()),
+            None => Err(CrustError::NotReversible(self.name())),
         }
     }
 
     #[cfg(feature = "reversible")]
-    pub fn revert_operation(&mut self, operation_id: usize) -> Result<(), String> {
-        if operation_id >= self.operation_history.len() {
-            return Err("Invalid operation ID".to_string());
+    fn apply_inverse(
+        &mut self,
+        operation: &CrdtOperation<K>,
+    ) -> Result<CrdtOperation<K>, CrustError> {
+        let inverse = self.compute_inverse_operation(operation).ok_or_else(|| {
+            CrustError::InvalidOperation(format!(
+                "{:?} cannot be reversed on a {}",
+                operation,
+                self.name()
+            ))
+        })?;
+        self.execute(&CrdtInnerCommand::from(&inverse))
+    }
+
+    #[cfg(feature = "reversible")]
+    pub fn revert_operation(
+        &mut self,
+        operation_id: usize,
+    ) -> Result<CrdtOperation<K>, CrustError> {
+        self.ensure_reversible()?;
+        let (operation, _) = self
+            .operation_history
+            .get(operation_id)
+            .cloned()
+            .ok_or_else(|| {
+                CrustError::InvalidOperation(format!("no operation with id {operation_id}"))
+            })?;
+        let inverse = self.apply_inverse(&operation)?;
+        self.operation_history.remove(operation_id);
+        Ok(inverse)
+    }
+
+    #[cfg(feature = "reversible")]
+    pub fn undo(&mut self) -> Result<CrdtOperation<K>, CrustError> {
+        self.ensure_reversible()?;
+        let (operation, timestamp) = self
+            .operation_history
+            .last()
+            .cloned()
+            .ok_or_else(|| CrustError::InvalidOperation("nothing to undo".to_string()))?;
+        let inverse = self.apply_inverse(&operation)?;
+        self.operation_history.pop();
+        self.undone_operations.push((operation, timestamp));
+        Ok(inverse)
+    }
+
+    #[cfg(feature = "reversible")]
+    pub fn redo(&mut self) -> Result<CrdtOperation<K>, CrustError> {
+        self.ensure_reversible()?;
+        let (operation, _) = self
+            .undone_operations
+            .last()
+            .cloned()
+            .ok_or_else(|| CrustError::InvalidOperation("nothing to redo".to_string()))?;
+        let operation = self.execute(&CrdtInnerCommand::from(&operation))?;
+        self.undone_operations.pop();
+        self.operation_history
+            .push((operation.clone(), self.get_unix_timestamp_seconds()));
+        Ok(operation)
+    }
+
+    pub fn get_state(&self) -> Value {
+        self.object.get_state()
+    }
+
+    pub fn purge_stable(&mut self, stable: &VersionVector<String>) -> usize {
+        self.object
+            .as_stabilizable_mut()
+            .map_or(0, |stabilizable| stabilizable.purge_stable(stable))
+    }
+
+    pub fn version_vector(&self) -> Option<VersionVector<String>> {
+        self.object
+            .as_stabilizable()
+            .map(|stabilizable| stabilizable.version_vector())
+    }
+
+    pub fn contributions(&self) -> Option<VersionVector<String>> {
+        self.object
+            .as_retirable()
+            .map(|retirable| retirable.contributions())
+    }
+
+    pub fn retire_departed(
+        &mut self,
+        departed: &[String],
+        stable: &VersionVector<String>,
+    ) -> Vec<String> {
+        match self.object.as_retirable_mut() {
+            Some(retirable) => retirable.retire_departed(departed, stable),
+            None => departed.to_vec(),
         }
+    }
+
+    pub fn retirement(&self) -> Option<Value> {
+        self.object
+            .as_retirable()
+            .and_then(|retirable| retirable.retirement())
+    }
 
-        let (operation, _) = &self.operation_history[operation_id];
-        if let Some(inverse) = self.compute_inverse_operation(operation) {
-            self.apply(&inverse);
-            Ok(())
-        } else {
-            Err("Operation cannot be reversed".to_string())
+    pub fn fold_retired(&mut self, peer_retirements: &[Value]) -> Result<bool, CrustError> {
+        match self.object.as_retirable_mut() {
+            Some(retirable) => retirable.fold_retired(peer_retirements),
+            None => Ok(false),
         }
     }
 
-    pub fn get_state(&self) -> Value {
-        match self.variant {
-            CrdtTypeVariant::GCounter(ref gcounter) => {
-                let counter_state = gcounter.get_state();
-                json!({
-                    "value": counter_state.counter.values().sum::<u64>().to_string(),
-                    "state": counter_state.counter.iter()
-                        .map(|(k, v)| (k.clone(), *v))
-                        .collect::<HashMap<K, u64>>()
-                })
-            }
+    pub fn assign_replica(&mut self, replica: &str) {
+        self.object.assign_replica(replica)
+    }
+
+    pub fn entry_digests(&self) -> Option<Vec<(String, u64)>> {
+        self.object
+            .as_reconcilable()
+            .map(|reconcilable| reconcilable.entry_digests())
+    }
+
+    pub fn entries_state(&self, in_scope: &dyn Fn(&str) -> bool) -> Result<Value, CrustError> {
+        match self.object.as_reconcilable() {
+            Some(reconcilable) => reconcilable.entries_state(in_scope),
+            None => Err(not_reconcilable(self.object.as_ref())),
+        }
+    }
+
+    pub fn merge_entries(
+        &mut self,
+        in_scope: &dyn Fn(&str) -> bool,
+        entries: &Value,
+    ) -> Result<(), CrustError> {
+        self.merge_replicated(|object| {
+            let error = not_reconcilable(object);
+            object
+                .as_reconcilable_mut()
+                .ok_or(error)?
+                .merge_entries(in_scope, entries)
+        })
+    }
+
+    fn check_type(&self, found: String) -> Result<(), CrustError> {
+        if self.name() != found {
+            return Err(CrustError::TypeMismatch {
+                expected: self.name(),
+                found,
+            });
         }
+        Ok(())
     }
 
-    pub fn merge(&mut self, other: &Self) {
+    pub fn merge(&mut self, other: &Self) -> Result<(), CrustError> {
         #[cfg(any(
             feature = "byzantine",
             feature = "confidentiality",
@@ -207,18 +422,17 @@ where
         #[cfg(feature = "access_control")]
         security.check_access(&self);
 
-        match (&mut self.variant, &other.variant) {
-            (CrdtTypeVariant::GCounter(gcounter1), CrdtTypeVariant::GCounter(gcounter2)) => {
-                #[cfg(feature = "byzantine")]
-                security.validate_state(&self);
-                let _ = gcounter1.merge(&gcounter2);
-                #[cfg(feature = "access_control")]
-                security.audit_log(&self);
-            }
-        }
+        self.check_type(other.name())?;
+        #[cfg(feature = "byzantine")]
+        security.validate_state(&self);
+        let state = other.object.to_state()?;
+        self.merge_replicated(|object| object.merge(&state))?;
+        #[cfg(feature = "access_control")]
+        security.audit_log(&self);
+        Ok(())
     }
 
-    pub fn apply(&mut self, operation: &CrdtOperation<K>) {
+    pub fn apply(&mut self, operation: &CrdtOperation<K>) -> Result<(), CrustError> {
         #[cfg(any(
             feature = "byzantine",
             feature = "confidentiality",
@@ -230,25 +444,17 @@ where
         #[cfg(feature = "access_control")]
         security.check_access(&self);
 
-        match &mut self.variant {
-            CrdtTypeVariant::GCounter(gcounter) => {
-                #[cfg(feature = "byzantine")]
-                security.validate_operation(&self);
+        #[cfg(feature = "byzantine")]
+        security.validate_operation(&self);
 
-                if let CrdtOperation::Counter(op) = operation {
-                    let _ = gcounter.apply(op);
-                    #[cfg(feature = "reversible")]
-                    self.operation_history
-                        .push((operation.clone(), self.get_unix_timestamp_seconds()));
-                }
+        self.merge_replicated(|object| object.apply(operation))?;
 
-                #[cfg(feature = "access_control")]
-                security.audit_log(&self);
-            }
-        }
+        #[cfg(feature = "access_control")]
+        security.audit_log(&self);
+        Ok(())
     }
 
-    pub fn merge_delta(&mut self, delta: &CrdtDelta<K>) {
+    pub fn merge_delta(&mut self, delta: &CrdtDelta) -> Result<(), CrustError> {
         #[cfg(any(
             feature = "byzantine",
             feature = "confidentiality",
@@ -260,85 +466,55 @@ where
         #[cfg(feature = "access_control")]
         security.check_access(&self);
 
-        match &mut self.variant {
-            CrdtTypeVariant::GCounter(gcounter) => {
-                #[cfg(feature = "byzantine")]
-                security.validate_delta(&self);
+        self.check_type(delta.crdt_type.clone())?;
+        #[cfg(feature = "byzantine")]
+        security.validate_delta(&self);
+        self.merge_replicated(|object| object.merge_delta(&delta.payload))?;
 
-                let CrdtDelta::GCounter(delta) = delta;
-                let _ = gcounter.merge_delta(delta);
-
-                #[cfg(feature = "access_control")]
-                security.audit_log(&self);
-            }
-        }
+        #[cfg(feature = "access_control")]
+        security.audit_log(&self);
+        Ok(())
     }
 
     pub fn validate_command(&self, value: K) -> Vec<CrdtInnerCommand<K>> {
-        match &self.variant {
-            CrdtTypeVariant::GCounter(_) => {
-                vec![CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
-                    value: value,
-                })]
-            }
-        }
+        self.object.commands(value)
     }
 
-    pub fn is_command_valid(&self, command: &CrdtInnerCommand<K>) -> bool {
-        match (&self.variant, command) {
-            (CrdtTypeVariant::GCounter(_), CrdtInnerCommand::Counter(_)) => true,
-            _ => false,
+    pub fn apply_command(
+        &mut self,
+        command: &CrdtInnerCommand<K>,
+    ) -> Result<CrdtOperation<K>, CrustError> {
+        let operation = self.execute(command)?;
+        #[cfg(feature = "reversible")]
+        {
+            self.operation_history
+                .push((operation.clone(), self.get_unix_timestamp_seconds()));
+            self.undone_operations.clear();
         }
+        Ok(operation)
     }
 
-    pub fn apply_command(&mut self, command: &CrdtInnerCommand<K>) -> Option<CrdtOperation<K>> {
-        if !self.is_command_valid(command) {
-            return None;
-        }
-
+    fn execute(&mut self, command: &CrdtInnerCommand<K>) -> Result<CrdtOperation<K>, CrustError> {
         #[cfg(feature = "constraints")]
-        if !self.check_constraints(command) {
-            return None;
-        }
-
-        match (&mut self.variant, command) {
-            (
-                CrdtTypeVariant::GCounter(gcounter),
-                CrdtInnerCommand::Counter(CounterInnerCommand::Increment { value }),
-            ) => {
-                gcounter.increment(value.clone());
-                Some(CrdtOperation::Counter(CounterOperation::Increment {
-                    value: value.clone(),
-                }))
-            }
-            _ => None,
+        if !self.constraints.is_empty() {
+            let mut candidate = self.object.clone();
+            let operation = candidate.apply_command(command)?;
+            check_rules(&self.constraints, &constraint_view(candidate.as_ref()))?;
+            self.object = candidate;
+            return Ok(operation);
         }
+        self.object.apply_command(command)
     }
 
     #[cfg(feature = "batch")]
     fn generate_operation_helper(&mut self) -> Option<CrdtOperation<K>> {
-        match &mut self.variant {
-            CrdtTypeVariant::GCounter(gcounter) => {
-                let operations = self
-                    .operations_buffer
-                    .iter()
-                    .filter_map(|crdt_op| {
-                        if let CrdtOperation::Counter(counter_op) = crdt_op {
-                            Some(counter_op.clone())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-                let aggregate_operations = gcounter.aggregate_operations(operations);
-                if let Some(aggregate_operation) = aggregate_operations {
-                    self.operations_buffer.clear();
-                    Some(CrdtOperation::Counter(aggregate_operation))
-                } else {
-                    None
-                }
-            }
+        let aggregate_operation = self
+            .object
+            .aggregate_operations(self.operations_buffer.clone());
+        if aggregate_operation.is_some() {
+            self.operations_buffer.clear();
         }
+        aggregate_operation
     }
 
     #[cfg(feature = "batch")]
@@ -356,7 +532,7 @@ where
         None
     }
 
-    #[cfg(feature = "batch")]
+    #[cfg(any(feature = "batch", feature = "reversible"))]
     fn get_unix_timestamp_seconds(&self) -> i64 {
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -369,7 +545,7 @@ where
         let timestamp_system_time = UNIX_EPOCH + Duration::from_secs(timestamp_seconds as u64);
         SystemTime::now()
             .duration_since(timestamp_system_time)
-            .unwrap_or(Duration::ZERO) 
+            .unwrap_or(Duration::ZERO)
     }
 
     #[cfg(feature = "batch")]
@@ -388,7 +564,7 @@ where
                         return None;
                     }
                 } else {
-                    config.last_batch_check_timestamp = Some(self.get_unix_timestamp_seconds()); 
+                    config.last_batch_check_timestamp = Some(self.get_unix_timestamp_seconds());
                     return None;
                 }
             }
@@ -396,37 +572,54 @@ where
         None
     }
 
-    pub fn generate_delta(&self) -> CrdtDelta<K> {
-        match &self.variant {
-            CrdtTypeVariant::GCounter(gcounter) => CrdtDelta::GCounter(gcounter.generate_delta()),
-        }
+    pub fn generate_delta(&self) -> Result<CrdtDelta, CrustError> {
+        Ok(CrdtDelta {
+            crdt_type: self.name(),
+            payload: self.object.generate_delta()?,
+        })
+    }
+
+    pub fn take_delta(&mut self) -> Result<CrdtDelta, CrustError> {
+        Ok(CrdtDelta {
+            crdt_type: self.name(),
+            payload: self.object.take_delta()?,
+        })
+    }
+
+    pub fn aggregate_deltas(
+        &self,
+        deltas: Vec<CrdtDelta>,
+    ) -> Result<Option<CrdtDelta>, CrustError> {
+        let payloads = deltas
+            .into_iter()
+            .map(|delta| {
+                self.check_type(delta.crdt_type)?;
+                Ok(delta.payload)
+            })
+            .collect::<Result<Vec<Value>, CrustError>>()?;
+        Ok(self
+            .object
+            .aggregate_deltas(payloads)?
+            .map(|payload| CrdtDelta {
+                crdt_type: self.name(),
+                payload,
+            }))
     }
 
     #[cfg(feature = "batch")]
-    fn generate_delta_helper(&mut self) -> Option<CrdtDelta<K>> {
-        match &mut self.variant {
-            CrdtTypeVariant::GCounter(gcounter) => {
-                let deltas = self
-                    .deltas_buffer
-                    .iter()
-                    .filter_map(|crdt_delta| {
-                        let CrdtDelta::GCounter(gcounter_delta) = crdt_delta;
-                        Some(gcounter_delta.clone())
-                    })
-                    .collect();
-                let aggregate_deltas = gcounter.aggregate_deltas(deltas);
-                if let Some(aggregate_delta) = aggregate_deltas {
-                    self.deltas_buffer.clear();
-                    Some(CrdtDelta::GCounter(aggregate_delta))
-                } else {
-                    None
-                }
-            }
+    fn generate_delta_helper(&mut self) -> Option<CrdtDelta> {
+        let aggregate_delta = self
+            .aggregate_deltas(self.deltas_buffer.clone())
+            .ok()
+            .flatten();
+        if aggregate_delta.is_some() {
+            self.deltas_buffer.clear();
         }
+        aggregate_delta
     }
 
     #[cfg(feature = "batch")]
-    pub fn generate_delta_count_based(&mut self, config: &SyncConfig) -> Option<CrdtDelta<K>> {
+    pub fn generate_delta_count_based(&mut self, config: &SyncConfig) -> Option<CrdtDelta> {
         if let (SyncMode::BatchCountBased, Some(batch_times)) =
             (&config.sync_mode, config.batch_times)
         {
@@ -438,7 +631,7 @@ where
     }
 
     #[cfg(feature = "batch")]
-    pub fn generate_delta_time_based(&mut self, config: &mut SyncConfig) -> Option<CrdtDelta<K>> {
+    pub fn generate_delta_time_based(&mut self, config: &mut SyncConfig) -> Option<CrdtDelta> {
         if let SyncMode::BatchTimeBased = config.sync_mode {
             if let Some(batching_interval) = config.batching_interval {
                 if let Some(last_check) = config.last_batch_check_timestamp {
@@ -450,7 +643,7 @@ where
                         return None;
                     }
                 } else {
-                    config.last_batch_check_timestamp = Some(self.get_unix_timestamp_seconds()); 
+                    config.last_batch_check_timestamp = Some(self.get_unix_timestamp_seconds());
                     return None;
                 }
             }
@@ -458,3 +651,21 @@ where
         None
     }
 }
+
+fn not_reconcilable<K>(object: &dyn CrdtObject<K>) -> CrustError {
+    CrustError::InvalidOperation(format!(
+        "{} does not expose its entries",
+        object.type_name()
+    ))
+}
+
+#[cfg(feature = "constraints")]
+fn constraint_view<K>(object: &dyn CrdtObject<K>) -> ConstraintView<K>
+where
+    K: CrdtKey,
+{
+    object
+        .as_constrained()
+        .map(|constrained| constrained.constraint_view())
+        .unwrap_or_default()
+}
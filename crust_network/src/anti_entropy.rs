@@ -0,0 +1,234 @@
+use std::{collections::HashMap, time::Duration};
+
+use crust_core::{
+    anti_entropy::{iblt::DEFAULT_IBLT_CELLS, merkle::DEFAULT_MERKLE_DEPTH},
+    error::CrustError,
+    registry::CrdtKey,
+};
+use futures::future::join_all;
+
+use crate::{
+    membership::SwimMessage, message::NetworkMessage, object_store::ReplicatedObject,
+    receiver::AppState, sender::NetworkSender,
+};
+
+type PeerMessage<K> = (String, String, NetworkMessage<K>);
+
+pub async fn run_delta_anti_entropy<K>(state: AppState<K>, interval: Duration)
+where
+    K: CrdtKey,
+{
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        if state.objects.is_empty() {
+            continue;
+        }
+        let Ok(retained_pod_names) = state.retained_pod_names().await else {
+            continue;
+        };
+        let current_pod_name = state.objects.replica().clone();
+        let retained: Vec<String> = retained_pod_names
+            .iter()
+            .filter(|pod_name| **pod_name != current_pod_name)
+            .cloned()
+            .collect();
+        let replica_pod_names = state.membership.live(retained_pod_names);
+        let peers: Vec<String> = replica_pod_names
+            .iter()
+            .filter(|pod_name| **pod_name != current_pod_name)
+            .cloned()
+            .collect();
+        let stability_peers = state.dissemination.round_peers(&peers);
+        let mut batches: HashMap<&String, Vec<PeerMessage<K>>> =
+            peers.iter().map(|peer| (peer, Vec::new())).collect();
+        for (name, crdt_type) in state.objects.objects() {
+            for (peer, batch) in batches.iter_mut() {
+                if let Ok(Some(message)) = state
+                    .objects
+                    .read_object(&name, |object| Ok(object.prepare_delta_interval(peer)))
+                {
+                    batch.push((name.clone(), crdt_type.clone(), message));
+                }
+            }
+            let stability = state.objects.with_object(&name, |object| {
+                object.garbage_collect_delta_log(&retained);
+                object.add_stability_replicas(&retained);
+                let _ = object.retire_departed(&retained);
+                object.purge_stable();
+                Ok(object.prepare_stability())
+            });
+            if let Ok(message) = stability {
+                for peer in &stability_peers {
+                    if let Some(batch) = batches.get_mut(peer) {
+                        batch.push((name.clone(), crdt_type.clone(), message.clone()));
+                    }
+                }
+            }
+        }
+        let sender = state.sender(replica_pod_names);
+        send_batches(&sender, batches).await;
+    }
+}
+
+async fn send_batches<K>(sender: &NetworkSender, batches: HashMap<&String, Vec<PeerMessage<K>>>)
+where
+    K: CrdtKey,
+{
+    join_all(batches.into_iter().map(|(peer, batch)| async move {
+        for (name, crdt_type, message) in &batch {
+            let _ = sender.send_to_peer(peer, name, crdt_type, message).await;
+        }
+    }))
+    .await;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reconciliation {
+    Merkle { depth: u32 },
+    Iblt { cells: usize },
+}
+
+impl Default for Reconciliation {
+    fn default() -> Self {
+        Reconciliation::Merkle {
+            depth: DEFAULT_MERKLE_DEPTH,
+        }
+    }
+}
+
+impl Reconciliation {
+    pub fn new(name: &str) -> Result<Self, CrustError> {
+        match name {
+            "merkle" => Ok(Reconciliation::Merkle {
+                depth: DEFAULT_MERKLE_DEPTH,
+            }),
+            "iblt" => Ok(Reconciliation::Iblt {
+                cells: DEFAULT_IBLT_CELLS,
+            }),
+            _ => Err(CrustError::InvalidOperation(format!(
+                "unknown reconciliation mode `{name}`"
+            ))),
+        }
+    }
+
+    pub fn prepare<K>(
+        &self,
+        object: &ReplicatedObject<K>,
+    ) -> Result<Option<NetworkMessage<K>>, CrustError>
+    where
+        K: CrdtKey,
+    {
+        match self {
+            Reconciliation::Merkle { depth } => object.prepare_merkle_digest(*depth),
+            Reconciliation::Iblt { cells } => object.prepare_reconcile_sketch(*cells),
+        }
+    }
+}
+
+pub async fn run_reconciliation<K>(
+    state: AppState<K>,
+    interval: Duration,
+    reconciliation: Reconciliation,
+) where
+    K: CrdtKey,
+{
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        if state.objects.is_empty() {
+            continue;
+        }
+        let Ok(replica_pod_names) = state.replica_pod_names().await else {
+            continue;
+        };
+        let current_pod_name = state.objects.replica().clone();
+        let peers: Vec<String> = replica_pod_names
+            .iter()
+            .filter(|pod_name| **pod_name != current_pod_name)
+            .cloned()
+            .collect();
+        let sender = state.sender(replica_pod_names);
+        for (name, crdt_type) in state.objects.objects() {
+            let peers = state.dissemination.round_peers(&peers);
+            let Ok(Some(message)) = state
+                .objects
+                .read_object(&name, |object| reconciliation.prepare(object))
+            else {
+                continue;
+            };
+            for peer in &peers {
+                let _ = sender.send_to_peer(peer, &name, &crdt_type, &message).await;
+            }
+        }
+    }
+}
+
+pub async fn run_membership<K>(state: AppState<K>)
+where
+    K: CrdtKey,
+{
+    let membership = state.membership.clone();
+    let mut ticker = tokio::time::interval(membership.config().protocol_period);
+    loop {
+        ticker.tick().await;
+        if let Ok(replica_pod_names) = state.peer_discovery.replica_pod_names().await {
+            membership.join(&replica_pod_names);
+        }
+        membership.expire_suspicions();
+        membership.prune_dead();
+        let Some(target) = membership.next_probe_target() else {
+            continue;
+        };
+        let sender = state.sender(vec![]);
+        if !probe_member(&state, &sender, &target).await {
+            membership.suspect(&target);
+        }
+    }
+}
+
+async fn probe_member<K>(state: &AppState<K>, sender: &NetworkSender, target: &str) -> bool
+where
+    K: CrdtKey,
+{
+    let membership = &state.membership;
+    let config = membership.config();
+    let ping = SwimMessage::Ping {
+        sender_pod_name: membership.replica().clone(),
+        updates: membership.piggyback(target),
+    };
+    if let Some(SwimMessage::Ack { updates, .. }) = sender
+        .send_swim(
+            sender.membership_url(target, "ping"),
+            &ping,
+            config.ping_timeout,
+        )
+        .await
+    {
+        membership.apply_all(&updates);
+        return true;
+    }
+    for prober in membership.indirect_probers(target) {
+        let ping_req = SwimMessage::PingReq {
+            sender_pod_name: membership.replica().clone(),
+            target: target.to_string(),
+            updates: membership.piggyback(&prober),
+        };
+        match sender
+            .send_swim(
+                sender.membership_url(&prober, "ping-req"),
+                &ping_req,
+                config.ping_timeout * 2,
+            )
+            .await
+        {
+            Some(SwimMessage::Ack { updates, .. }) => {
+                membership.apply_all(&updates);
+                return true;
+            }
+            Some(SwimMessage::Nack { updates, .. }) => membership.apply_all(&updates),
+            _ => {}
+        }
+    }
+    false
+}
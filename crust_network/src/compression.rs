@@ -0,0 +1,171 @@
+use std::{
+    io::Read,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use axum::http::{header::CONTENT_ENCODING, HeaderMap};
+use crust_core::error::CrustError;
+
+pub const MAX_DECOMPRESSED_BYTES: usize = 64 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Compression {
+    #[default]
+    None,
+    Zstd,
+    Lz4,
+}
+
+impl Compression {
+    pub fn new(name: &str) -> Result<Self, CrustError> {
+        match name {
+            "none" | "identity" => Ok(Compression::None),
+            "zstd" => Ok(Compression::Zstd),
+            "lz4" => Ok(Compression::Lz4),
+            _ => Err(CrustError::UnsupportedEncoding(name.to_string())),
+        }
+    }
+
+    pub fn content_encoding(&self) -> Option<&'static str> {
+        match self {
+            Compression::None => None,
+            Compression::Zstd => Some("zstd"),
+            Compression::Lz4 => Some("lz4"),
+        }
+    }
+
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, CrustError> {
+        match headers.get(CONTENT_ENCODING) {
+            Some(content_encoding) => Compression::new(
+                content_encoding
+                    .to_str()
+                    .map_err(|error| CrustError::UnsupportedEncoding(error.to_string()))?
+                    .trim(),
+            ),
+            None => Ok(Compression::None),
+        }
+    }
+
+    pub fn compress(&self, bytes: &[u8]) -> Result<Vec<u8>, CrustError> {
+        match self {
+            Compression::None => Ok(bytes.to_vec()),
+            Compression::Zstd => zstd::encode_all(bytes, 0)
+                .map_err(|error| CrustError::Serialization(error.to_string())),
+            Compression::Lz4 => Ok(lz4_flex::compress_prepend_size(bytes)),
+        }
+    }
+
+    pub fn decompress(&self, bytes: &[u8]) -> Result<Vec<u8>, CrustError> {
+        match self {
+            Compression::None => Ok(bytes.to_vec()),
+            Compression::Zstd => {
+                let mut decompressed = Vec::new();
+                zstd::stream::read::Decoder::new(bytes)
+                    .and_then(|decoder| {
+                        decoder
+                            .take(MAX_DECOMPRESSED_BYTES as u64 + 1)
+                            .read_to_end(&mut decompressed)
+                    })
+                    .map_err(|error| CrustError::Serialization(error.to_string()))?;
+                if decompressed.len() > MAX_DECOMPRESSED_BYTES {
+                    return Err(too_large(decompressed.len()));
+                }
+                Ok(decompressed)
+            }
+            Compression::Lz4 => {
+                let (size, compressed) = lz4_flex::block::uncompressed_size(bytes)
+                    .map_err(|error| CrustError::Serialization(error.to_string()))?;
+                if size > MAX_DECOMPRESSED_BYTES {
+                    return Err(too_large(size));
+                }
+                lz4_flex::decompress(compressed, size)
+                    .map_err(|error| CrustError::Serialization(error.to_string()))
+            }
+        }
+    }
+}
+
+fn too_large(size: usize) -> CrustError {
+    CrustError::Serialization(format!(
+        "decompressed payload of {size} bytes exceeds the {MAX_DECOMPRESSED_BYTES} byte limit"
+    ))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompressionConfig {
+    pub compression: Compression,
+    pub threshold: usize,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        CompressionConfig {
+            compression: Compression::None,
+            threshold: 1024,
+        }
+    }
+}
+
+impl CompressionConfig {
+    pub fn new(compression: Compression, threshold: usize) -> Self {
+        CompressionConfig {
+            compression,
+            threshold,
+        }
+    }
+
+    pub fn apply(&self, bytes: Vec<u8>) -> Result<(Compression, Vec<u8>), CrustError> {
+        if self.compression == Compression::None || bytes.len() < self.threshold {
+            return Ok((Compression::None, bytes));
+        }
+        let compressed = self.compression.compress(&bytes)?;
+        if compressed.len() >= bytes.len() {
+            return Ok((Compression::None, bytes));
+        }
+        Ok((self.compression, compressed))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CompressionMetrics {
+    messages: AtomicU64,
+    compressed_messages: AtomicU64,
+    original_bytes: AtomicU64,
+    sent_bytes: AtomicU64,
+}
+
+impl CompressionMetrics {
+    pub fn record(&self, compression: Compression, original_bytes: usize, sent_bytes: usize) {
+        self.messages.fetch_add(1, Ordering::Relaxed);
+        if compression != Compression::None {
+            self.compressed_messages.fetch_add(1, Ordering::Relaxed);
+        }
+        self.original_bytes
+            .fetch_add(original_bytes as u64, Ordering::Relaxed);
+        self.sent_bytes
+            .fetch_add(sent_bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn messages(&self) -> u64 {
+        self.messages.load(Ordering::Relaxed)
+    }
+
+    pub fn compressed_messages(&self) -> u64 {
+        self.compressed_messages.load(Ordering::Relaxed)
+    }
+
+    pub fn original_bytes(&self) -> u64 {
+        self.original_bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn sent_bytes(&self) -> u64 {
+        self.sent_bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn ratio(&self) -> f64 {
+        match self.original_bytes() {
+            0 => 1.0,
+            original_bytes => self.sent_bytes() as f64 / original_bytes as f64,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use crust_config::k8s_discovery::get_replica_pod_names;
+use crust_core::error::CrustError;
+
+#[derive(Clone, Debug)]
+pub enum PeerDiscovery {
+    Kubernetes,
+    Static(Vec<String>),
+}
+
+impl PeerDiscovery {
+    pub async fn replica_pod_names(&self) -> Result<Vec<String>, CrustError> {
+        match self {
+            PeerDiscovery::Kubernetes => get_replica_pod_names()
+                .await
+                .map_err(|error| CrustError::Network(error.to_string())),
+            PeerDiscovery::Static(pod_names) => Ok(pod_names.clone()),
+        }
+    }
+}
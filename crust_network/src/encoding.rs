@@ -0,0 +1,97 @@
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::{header::CONTENT_TYPE, HeaderMap},
+};
+use crust_core::error::CrustError;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{compression::Compression, error::ApiError};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Encoding {
+    #[default]
+    Json,
+    Cbor,
+}
+
+impl Encoding {
+    pub fn new(name: &str) -> Result<Self, CrustError> {
+        match name {
+            "json" => Ok(Encoding::Json),
+            "cbor" => Ok(Encoding::Cbor),
+            _ => Err(CrustError::UnsupportedEncoding(name.to_string())),
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Encoding::Json => "application/json",
+            Encoding::Cbor => "application/cbor",
+        }
+    }
+
+    pub fn from_content_type(content_type: &str) -> Result<Self, CrustError> {
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+        [Encoding::Json, Encoding::Cbor]
+            .into_iter()
+            .find(|encoding| media_type.eq_ignore_ascii_case(encoding.content_type()))
+            .ok_or_else(|| CrustError::UnsupportedEncoding(content_type.to_string()))
+    }
+
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, CrustError> {
+        match headers.get(CONTENT_TYPE) {
+            Some(content_type) => Encoding::from_content_type(
+                content_type
+                    .to_str()
+                    .map_err(|error| CrustError::UnsupportedEncoding(error.to_string()))?,
+            ),
+            None => Ok(Encoding::Json),
+        }
+    }
+
+    pub fn encode<T>(&self, value: &T) -> Result<Vec<u8>, CrustError>
+    where
+        T: Serialize + ?Sized,
+    {
+        match self {
+            Encoding::Json => Ok(serde_json::to_vec(value)?),
+            Encoding::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes)
+                    .map_err(|error| CrustError::Serialization(error.to_string()))?;
+                Ok(bytes)
+            }
+        }
+    }
+
+    pub fn decode<T>(&self, bytes: &[u8]) -> Result<T, CrustError>
+    where
+        T: DeserializeOwned,
+    {
+        match self {
+            Encoding::Json => Ok(serde_json::from_slice(bytes)?),
+            Encoding::Cbor => ciborium::from_reader(bytes)
+                .map_err(|error| CrustError::Serialization(error.to_string())),
+        }
+    }
+}
+
+pub struct Encoded<T>(pub T);
+
+impl<S, T> FromRequest<S> for Encoded<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let encoding = Encoding::from_headers(request.headers())?;
+        let compression = Compression::from_headers(request.headers())?;
+        let bytes = Bytes::from_request(request, state)
+            .await
+            .map_err(|error| CrustError::Serialization(error.to_string()))?;
+        Ok(Encoded(encoding.decode(&compression.decompress(&bytes)?)?))
+    }
+}
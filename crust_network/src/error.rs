@@ -0,0 +1,48 @@
+use axum::{
+    response::{IntoResponse, Response},
+    Json,
+};
+use crust_core::error::CrustError;
+use reqwest::StatusCode;
+use serde_json::json;
+
+#[derive(Debug)]
+pub struct ApiError(pub CrustError);
+
+impl ApiError {
+    pub fn status_code(&self) -> StatusCode {
+        match self.0 {
+            CrustError::UnknownCrdtType(_)
+            | CrustError::UnknownSyncType(_)
+            | CrustError::UnknownSyncMode(_)
+            | CrustError::InvalidCommand { .. }
+            | CrustError::Serialization(_) => StatusCode::BAD_REQUEST,
+            CrustError::CrdtNotInitialized | CrustError::ObjectNotFound(_) => StatusCode::NOT_FOUND,
+            CrustError::DuplicateCrdtType(_)
+            | CrustError::TypeMismatch { .. }
+            | CrustError::IncompatibleSchema { .. } => StatusCode::CONFLICT,
+            CrustError::ConstraintViolation(_)
+            | CrustError::InvalidOperation(_)
+            | CrustError::NotReversible(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            CrustError::Network(_) => StatusCode::BAD_GATEWAY,
+            CrustError::Storage(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            CrustError::UnsupportedEncoding(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        }
+    }
+}
+
+impl From<CrustError> for ApiError {
+    fn from(error: CrustError) -> Self {
+        ApiError(error)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (
+            self.status_code(),
+            Json(json!({"error": self.0.to_string(), "kind": self.0.kind()})),
+        )
+            .into_response()
+    }
+}
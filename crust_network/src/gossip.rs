@@ -0,0 +1,134 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crust_core::{error::CrustError, operation::OperationId};
+use rand::seq::IndexedRandom;
+
+pub const DEFAULT_GOSSIP_FANOUT: usize = 3;
+pub const DEFAULT_GOSSIP_HOPS: u32 = 4;
+pub const SEEN_RUMORS_CAPACITY: usize = 4096;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GossipConfig {
+    pub fanout: usize,
+    pub hops: u32,
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        GossipConfig {
+            fanout: DEFAULT_GOSSIP_FANOUT,
+            hops: DEFAULT_GOSSIP_HOPS,
+        }
+    }
+}
+
+impl GossipConfig {
+    pub fn new(fanout: usize, hops: u32) -> Self {
+        GossipConfig { fanout, hops }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Dissemination {
+    #[default]
+    Broadcast,
+    Gossip(GossipConfig),
+}
+
+impl Dissemination {
+    pub fn new(name: &str) -> Result<Self, CrustError> {
+        match name {
+            "broadcast" => Ok(Dissemination::Broadcast),
+            "gossip" => Ok(Dissemination::Gossip(GossipConfig::default())),
+            _ => Err(CrustError::InvalidOperation(format!(
+                "unknown dissemination strategy `{name}`"
+            ))),
+        }
+    }
+
+    pub fn round_peers(&self, peers: &[String]) -> Vec<String> {
+        match self {
+            Dissemination::Broadcast => peers.to_vec(),
+            Dissemination::Gossip(config) => sample_peers(peers, &[], config.fanout),
+        }
+    }
+}
+
+pub fn sample_peers(peers: &[String], excluded: &[&str], fanout: usize) -> Vec<String> {
+    let candidates: Vec<&String> = peers
+        .iter()
+        .filter(|peer| !excluded.contains(&peer.as_str()))
+        .collect();
+    candidates
+        .choose_multiple(&mut rand::rng(), fanout)
+        .map(|peer| (*peer).clone())
+        .collect()
+}
+
+#[derive(Debug, Default)]
+struct SeenRumors {
+    ids: HashSet<OperationId<String>>,
+    order: VecDeque<OperationId<String>>,
+}
+
+#[derive(Debug)]
+pub struct RumorLog {
+    sequence: AtomicU64,
+    seen: Mutex<SeenRumors>,
+}
+
+impl Default for RumorLog {
+    fn default() -> Self {
+        let incarnation = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_micros() as u64);
+        RumorLog {
+            sequence: AtomicU64::new(incarnation),
+            seen: Mutex::default(),
+        }
+    }
+}
+
+impl RumorLog {
+    pub fn next_rumor(&self, replica: &str) -> OperationId<String> {
+        let rumor_id = OperationId::new(
+            replica.to_string(),
+            self.sequence.fetch_add(1, Ordering::Relaxed) + 1,
+        );
+        self.observe(&rumor_id);
+        rumor_id
+    }
+
+    pub fn contains(&self, rumor_id: &OperationId<String>) -> bool {
+        self.seen.lock().unwrap().ids.contains(rumor_id)
+    }
+
+    pub fn observe(&self, rumor_id: &OperationId<String>) -> bool {
+        let mut seen = self.seen.lock().unwrap();
+        if !seen.ids.insert(rumor_id.clone()) {
+            return false;
+        }
+        seen.order.push_back(rumor_id.clone());
+        if seen.order.len() > SEEN_RUMORS_CAPACITY {
+            if let Some(oldest) = seen.order.pop_front() {
+                seen.ids.remove(&oldest);
+            }
+        }
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.lock().unwrap().order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,180 @@
+use std::{str::FromStr, time::Duration};
+
+use anti_entropy::Reconciliation;
+use axum::{
+    routing::{get, post, put},
+    Router,
+};
+use compression::{Compression, CompressionConfig};
+use crust_core::{error::CrustError, registry::CrdtKey};
+use encoding::Encoding;
+use gossip::{Dissemination, GossipConfig};
+use membership::SwimConfig;
+#[cfg(feature = "constraints")]
+use objects::set_object_constraints;
+use objects::{
+    apply_object_command, create_object, delete_object, get_object, get_object_digest,
+    list_objects, receive_object_message,
+};
+#[cfg(feature = "reversible")]
+use objects::{redo_object_command, undo_object_command};
+use receiver::{
+    get_compression_metrics, get_membership, get_state, ping_member, ping_member_indirectly,
+    receive_message_from_internal, receive_message_from_other_instances, AppState,
+};
+use tokio::net::TcpListener;
+
+pub mod anti_entropy;
+pub mod compression;
+pub mod discovery;
+pub mod encoding;
+pub mod error;
+pub mod gossip;
+pub mod membership;
+pub mod message;
+pub mod object_store;
+pub mod objects;
+pub mod persistence;
+pub mod receiver;
+pub mod sender;
+
+pub static PORT: u16 = 8000;
+
+pub fn router<K>(state: AppState<K>) -> Router
+where
+    K: CrdtKey,
+{
+    let router = Router::new()
+        .route(
+            "/receive/{type}",
+            post(receive_message_from_other_instances),
+        )
+        .route(
+            "/receive/internal/{crdt_type}/{sync_type}/{sync_mode}",
+            post(receive_message_from_internal),
+        )
+        .route("/state/{type}", get(get_state))
+        .route("/digest/{name}", get(get_object_digest))
+        .route("/metrics/compression", get(get_compression_metrics))
+        .route("/membership", get(get_membership))
+        .route("/membership/ping", post(ping_member))
+        .route("/membership/ping-req", post(ping_member_indirectly))
+        .route("/objects", get(list_objects))
+        .route(
+            "/objects/{name}",
+            put(create_object).get(get_object).delete(delete_object),
+        )
+        .route(
+            "/objects/{name}/command/{sync_type}/{sync_mode}",
+            post(apply_object_command),
+        )
+        .route(
+            "/objects/{name}/receive/{crdt_type}",
+            post(receive_object_message),
+        );
+    #[cfg(feature = "constraints")]
+    let router = router.route("/objects/{name}/constraints", put(set_object_constraints));
+    #[cfg(feature = "reversible")]
+    let router = router
+        .route(
+            "/objects/{name}/undo/{sync_type}/{sync_mode}",
+            post(undo_object_command),
+        )
+        .route(
+            "/objects/{name}/redo/{sync_type}/{sync_mode}",
+            post(redo_object_command),
+        );
+    router.with_state(state)
+}
+
+pub async fn serve<K>(listener: TcpListener, state: AppState<K>) -> std::io::Result<()>
+where
+    K: CrdtKey,
+{
+    axum::serve(listener, router(state)).await
+}
+
+pub fn get_current_pod_name() -> String {
+    std::env::var("POD_NAME").unwrap_or_else(|_| "none".to_string())
+}
+
+pub fn get_current_service_name() -> String {
+    std::env::var("SERVICE_NAME").unwrap_or_else(|_| "none".to_string())
+}
+
+fn parse_env<T: FromStr>(name: &str) -> Result<Option<T>, CrustError> {
+    std::env::var(name)
+        .ok()
+        .map(|value| {
+            value.parse().map_err(|_| {
+                CrustError::InvalidOperation(format!("invalid value `{value}` for {name}"))
+            })
+        })
+        .transpose()
+}
+
+pub fn get_current_compression() -> Result<CompressionConfig, CrustError> {
+    let default = CompressionConfig::default();
+    let compression = match std::env::var("CRUST_COMPRESSION") {
+        Ok(name) => Compression::new(&name)?,
+        Err(_) => default.compression,
+    };
+    Ok(CompressionConfig::new(
+        compression,
+        parse_env("CRUST_COMPRESSION_THRESHOLD")?.unwrap_or(default.threshold),
+    ))
+}
+
+pub fn get_current_encoding() -> Result<Encoding, CrustError> {
+    match std::env::var("CRUST_ENCODING") {
+        Ok(name) => Encoding::new(&name),
+        Err(_) => Ok(Encoding::default()),
+    }
+}
+
+pub fn get_current_reconciliation() -> Result<Reconciliation, CrustError> {
+    let reconciliation = match std::env::var("CRUST_RECONCILIATION") {
+        Ok(name) => Reconciliation::new(&name)?,
+        Err(_) => Reconciliation::default(),
+    };
+    Ok(match reconciliation {
+        Reconciliation::Merkle { depth } => Reconciliation::Merkle {
+            depth: parse_env("CRUST_MERKLE_DEPTH")?.unwrap_or(depth),
+        },
+        Reconciliation::Iblt { cells } => Reconciliation::Iblt {
+            cells: parse_env("CRUST_IBLT_CELLS")?.unwrap_or(cells),
+        },
+    })
+}
+
+pub fn get_current_dissemination() -> Result<Dissemination, CrustError> {
+    let dissemination = match std::env::var("CRUST_DISSEMINATION") {
+        Ok(name) => Dissemination::new(&name)?,
+        Err(_) => Dissemination::default(),
+    };
+    Ok(match dissemination {
+        Dissemination::Broadcast => Dissemination::Broadcast,
+        Dissemination::Gossip(config) => Dissemination::Gossip(GossipConfig::new(
+            parse_env("CRUST_GOSSIP_FANOUT")?.unwrap_or(config.fanout),
+            parse_env("CRUST_GOSSIP_HOPS")?.unwrap_or(config.hops),
+        )),
+    })
+}
+
+pub fn get_current_swim() -> Result<SwimConfig, CrustError> {
+    let default = SwimConfig::default();
+    let duration = |name: &str, default: Duration| -> Result<Duration, CrustError> {
+        Ok(parse_env(name)?
+            .map(Duration::from_millis)
+            .unwrap_or(default))
+    };
+    Ok(SwimConfig {
+        protocol_period: duration("CRUST_SWIM_PERIOD_MS", default.protocol_period)?,
+        ping_timeout: duration("CRUST_SWIM_PING_TIMEOUT_MS", default.ping_timeout)?,
+        indirect_probes: parse_env("CRUST_SWIM_INDIRECT_PROBES")?
+            .unwrap_or(default.indirect_probes),
+        suspicion_timeout: duration("CRUST_SWIM_SUSPICION_MS", default.suspicion_timeout)?,
+        confirmation_timeout: duration("CRUST_SWIM_CONFIRMATION_MS", default.confirmation_timeout)?,
+        tombstone_ttl: duration("CRUST_SWIM_TOMBSTONE_TTL_MS", default.tombstone_ttl)?,
+    })
+}
@@ -1,40 +1,31 @@
-use axum::{
-    routing::{get, post},
-    Router,
-};
-use receiver::{
-    get_state, receive_message_from_internal, receive_message_from_other_instances, AppState,
-};
+use std::time::Duration;
 
-pub mod message;
-pub mod receiver;
-pub mod sender;
+use crust_network::{
+    anti_entropy::{run_delta_anti_entropy, run_membership, run_reconciliation},
+    get_current_reconciliation,
+    persistence::StorageConfig,
+    receiver::AppState,
+    serve, PORT,
+};
 
-static PORT: u16 = 8000;
+static ANTI_ENTROPY_INTERVAL: Duration = Duration::from_secs(5);
+static RECONCILIATION_INTERVAL: Duration = Duration::from_secs(30);
 
 #[tokio::main]
 async fn main() {
-    let state = AppState::new();
-    let app = Router::new()
-        .route(
-            "/receive/{type}",
-            post(receive_message_from_other_instances),
-        )
-        .route(
-            "/receive/internal/{crdt_type}/{sync_type}/{sync_mode}",
-            post(receive_message_from_internal),
-        )
-        .route("/state/{type}", get(get_state))
-        .with_state(state);
+    let state = match StorageConfig::from_env().unwrap() {
+        Some(config) => AppState::<String>::with_storage(config).unwrap(),
+        None => AppState::<String>::new().unwrap(),
+    };
+    let reconciliation = get_current_reconciliation().unwrap();
+    tokio::spawn(run_delta_anti_entropy(state.clone(), ANTI_ENTROPY_INTERVAL));
+    tokio::spawn(run_reconciliation(
+        state.clone(),
+        RECONCILIATION_INTERVAL,
+        reconciliation,
+    ));
+    tokio::spawn(run_membership(state.clone()));
     let url = format!("0.0.0.0:{PORT}");
     let listener = tokio::net::TcpListener::bind(url).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
-}
-
-fn get_current_pod_name() -> String {
-    std::env::var("POD_NAME").unwrap_or_else(|_| "none".to_string())
-}
-
-fn get_current_service_name() -> String {
-    std::env::var("SERVICE_NAME").unwrap_or_else(|_| "none".to_string())
+    serve(listener, state).await.unwrap();
 }
@@ -0,0 +1,393 @@
+use std::{
+    collections::BTreeMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::gossip::sample_peers;
+
+pub const DEFAULT_PROTOCOL_PERIOD: Duration = Duration::from_secs(1);
+pub const DEFAULT_PING_TIMEOUT: Duration = Duration::from_millis(300);
+pub const DEFAULT_INDIRECT_PROBES: usize = 3;
+pub const DEFAULT_SUSPICION_TIMEOUT: Duration = Duration::from_secs(5);
+pub const DEFAULT_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(10);
+pub const DEFAULT_TOMBSTONE_TTL: Duration = Duration::from_secs(60);
+pub const MAX_PIGGYBACKED_UPDATES: usize = 8;
+pub const RETRANSMIT_MULTIPLIER: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwimConfig {
+    pub protocol_period: Duration,
+    pub ping_timeout: Duration,
+    pub indirect_probes: usize,
+    pub suspicion_timeout: Duration,
+    pub confirmation_timeout: Duration,
+    pub tombstone_ttl: Duration,
+}
+
+impl Default for SwimConfig {
+    fn default() -> Self {
+        SwimConfig {
+            protocol_period: DEFAULT_PROTOCOL_PERIOD,
+            ping_timeout: DEFAULT_PING_TIMEOUT,
+            indirect_probes: DEFAULT_INDIRECT_PROBES,
+            suspicion_timeout: DEFAULT_SUSPICION_TIMEOUT,
+            confirmation_timeout: DEFAULT_CONFIRMATION_TIMEOUT,
+            tombstone_ttl: DEFAULT_TOMBSTONE_TTL,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MemberState {
+    Alive,
+    Suspect,
+    Dead,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MembershipUpdate {
+    pub pod_name: String,
+    pub state: MemberState,
+    pub incarnation: u64,
+}
+
+impl MembershipUpdate {
+    fn precedence(&self) -> (u64, MemberState) {
+        (self.incarnation, self.state)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum SwimMessage {
+    Ping {
+        sender_pod_name: String,
+        updates: Vec<MembershipUpdate>,
+    },
+    PingReq {
+        sender_pod_name: String,
+        target: String,
+        updates: Vec<MembershipUpdate>,
+    },
+    Ack {
+        sender_pod_name: String,
+        updates: Vec<MembershipUpdate>,
+    },
+    Nack {
+        sender_pod_name: String,
+        updates: Vec<MembershipUpdate>,
+    },
+}
+
+impl SwimMessage {
+    pub fn sender_pod_name(&self) -> &String {
+        match self {
+            SwimMessage::Ping {
+                sender_pod_name, ..
+            }
+            | SwimMessage::PingReq {
+                sender_pod_name, ..
+            }
+            | SwimMessage::Ack {
+                sender_pod_name, ..
+            }
+            | SwimMessage::Nack {
+                sender_pod_name, ..
+            } => sender_pod_name,
+        }
+    }
+
+    pub fn updates(&self) -> &[MembershipUpdate] {
+        match self {
+            SwimMessage::Ping { updates, .. }
+            | SwimMessage::PingReq { updates, .. }
+            | SwimMessage::Ack { updates, .. }
+            | SwimMessage::Nack { updates, .. } => updates,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Member {
+    update: MembershipUpdate,
+    changed_at: Instant,
+}
+
+impl Member {
+    fn dead_for(&self, timeout: Duration) -> bool {
+        self.update.state == MemberState::Dead && self.changed_at.elapsed() >= timeout
+    }
+}
+
+#[derive(Debug, Default)]
+struct MembershipView {
+    incarnation: u64,
+    members: BTreeMap<String, Member>,
+    pending: Vec<(MembershipUpdate, usize)>,
+    probe_order: Vec<String>,
+}
+
+impl MembershipView {
+    fn retransmissions(&self) -> usize {
+        let size = self.members.len() + 1;
+        RETRANSMIT_MULTIPLIER * (usize::BITS - size.leading_zeros()) as usize
+    }
+
+    fn enqueue(&mut self, update: MembershipUpdate) {
+        let retransmissions = self.retransmissions();
+        self.pending
+            .retain(|(pending, _)| pending.pod_name != update.pod_name);
+        self.pending.push((update, retransmissions));
+    }
+
+    fn set(&mut self, update: MembershipUpdate) {
+        self.members.insert(
+            update.pod_name.clone(),
+            Member {
+                update: update.clone(),
+                changed_at: Instant::now(),
+            },
+        );
+        self.enqueue(update);
+    }
+}
+
+#[derive(Debug)]
+pub struct Membership {
+    replica: String,
+    config: SwimConfig,
+    view: Mutex<MembershipView>,
+}
+
+impl Membership {
+    pub fn new(replica: String, config: SwimConfig) -> Self {
+        Membership {
+            replica,
+            config,
+            view: Mutex::new(MembershipView::default()),
+        }
+    }
+
+    pub fn replica(&self) -> &String {
+        &self.replica
+    }
+
+    pub fn config(&self) -> SwimConfig {
+        self.config
+    }
+
+    pub fn incarnation(&self) -> u64 {
+        self.view.lock().unwrap().incarnation
+    }
+
+    pub fn join(&self, pod_names: &[String]) {
+        let mut view = self.view.lock().unwrap();
+        for pod_name in pod_names {
+            if *pod_name == self.replica || view.members.contains_key(pod_name) {
+                continue;
+            }
+            view.members.insert(
+                pod_name.clone(),
+                Member {
+                    update: MembershipUpdate {
+                        pod_name: pod_name.clone(),
+                        state: MemberState::Alive,
+                        incarnation: 0,
+                    },
+                    changed_at: Instant::now(),
+                },
+            );
+        }
+    }
+
+    pub fn apply(&self, update: &MembershipUpdate) -> bool {
+        let mut view = self.view.lock().unwrap();
+        if update.pod_name == self.replica {
+            if update.state == MemberState::Alive || update.incarnation < view.incarnation {
+                return false;
+            }
+            view.incarnation = update.incarnation + 1;
+            let refutation = MembershipUpdate {
+                pod_name: self.replica.clone(),
+                state: MemberState::Alive,
+                incarnation: view.incarnation,
+            };
+            view.enqueue(refutation);
+            return true;
+        }
+        let overrides = match view.members.get(&update.pod_name) {
+            Some(member) => update.precedence() > member.update.precedence(),
+            None => true,
+        };
+        if overrides {
+            view.set(update.clone());
+        }
+        overrides
+    }
+
+    pub fn apply_all(&self, updates: &[MembershipUpdate]) {
+        for update in updates {
+            self.apply(update);
+        }
+    }
+
+    pub fn suspect(&self, pod_name: &str) -> bool {
+        let mut view = self.view.lock().unwrap();
+        let Some(member) = view.members.get(pod_name) else {
+            return false;
+        };
+        if member.update.state != MemberState::Alive {
+            return false;
+        }
+        let update = MembershipUpdate {
+            state: MemberState::Suspect,
+            ..member.update.clone()
+        };
+        view.set(update);
+        true
+    }
+
+    pub fn expire_suspicions(&self) -> Vec<String> {
+        let mut view = self.view.lock().unwrap();
+        let expired: Vec<MembershipUpdate> = view
+            .members
+            .values()
+            .filter(|member| {
+                member.update.state == MemberState::Suspect
+                    && member.changed_at.elapsed() >= self.config.suspicion_timeout
+            })
+            .map(|member| MembershipUpdate {
+                state: MemberState::Dead,
+                ..member.update.clone()
+            })
+            .collect();
+        expired
+            .into_iter()
+            .map(|update| {
+                let pod_name = update.pod_name.clone();
+                view.set(update);
+                pod_name
+            })
+            .collect()
+    }
+
+    pub fn prune_dead(&self) -> Vec<String> {
+        let mut view = self.view.lock().unwrap();
+        let pruned: Vec<String> = view
+            .members
+            .values()
+            .filter(|member| member.dead_for(self.config.tombstone_ttl))
+            .map(|member| member.update.pod_name.clone())
+            .collect();
+        for pod_name in &pruned {
+            view.members.remove(pod_name);
+        }
+        view.pending
+            .retain(|(update, _)| !pruned.contains(&update.pod_name));
+        pruned
+    }
+
+    pub fn piggyback(&self, recipient: &str) -> Vec<MembershipUpdate> {
+        let mut view = self.view.lock().unwrap();
+        view.pending
+            .sort_by_key(|(_, remaining)| std::cmp::Reverse(*remaining));
+        let mut updates: Vec<MembershipUpdate> = view
+            .pending
+            .iter_mut()
+            .take(MAX_PIGGYBACKED_UPDATES)
+            .map(|(update, remaining)| {
+                *remaining -= 1;
+                update.clone()
+            })
+            .collect();
+        view.pending.retain(|(_, remaining)| *remaining > 0);
+        if let Some(member) = view.members.get(recipient) {
+            if member.update.state != MemberState::Alive && !updates.contains(&member.update) {
+                updates.push(member.update.clone());
+            }
+        }
+        updates
+    }
+
+    pub fn next_probe_target(&self) -> Option<String> {
+        let mut view = self.view.lock().unwrap();
+        while let Some(pod_name) = view.probe_order.pop() {
+            if view
+                .members
+                .get(&pod_name)
+                .is_some_and(|member| member.update.state != MemberState::Dead)
+            {
+                return Some(pod_name);
+            }
+        }
+        let mut probe_order: Vec<String> = view
+            .members
+            .values()
+            .filter(|member| member.update.state != MemberState::Dead)
+            .map(|member| member.update.pod_name.clone())
+            .collect();
+        probe_order.shuffle(&mut rand::rng());
+        let target = probe_order.pop();
+        view.probe_order = probe_order;
+        target
+    }
+
+    pub fn indirect_probers(&self, target: &str) -> Vec<String> {
+        let candidates: Vec<String> = self
+            .members()
+            .into_iter()
+            .filter(|member| member.state == MemberState::Alive)
+            .map(|member| member.pod_name)
+            .collect();
+        sample_peers(&candidates, &[target], self.config.indirect_probes)
+    }
+
+    pub fn state(&self, pod_name: &str) -> Option<MemberState> {
+        self.view
+            .lock()
+            .unwrap()
+            .members
+            .get(pod_name)
+            .map(|member| member.update.state)
+    }
+
+    pub fn is_live(&self, pod_name: &str) -> bool {
+        self.state(pod_name) != Some(MemberState::Dead)
+    }
+
+    pub fn live(&self, pod_names: Vec<String>) -> Vec<String> {
+        pod_names
+            .into_iter()
+            .filter(|pod_name| self.is_live(pod_name))
+            .collect()
+    }
+
+    pub fn is_departed(&self, pod_name: &str) -> bool {
+        self.view
+            .lock()
+            .unwrap()
+            .members
+            .get(pod_name)
+            .is_some_and(|member| member.dead_for(self.config.confirmation_timeout))
+    }
+
+    pub fn retained(&self, pod_names: Vec<String>) -> Vec<String> {
+        pod_names
+            .into_iter()
+            .filter(|pod_name| !self.is_departed(pod_name))
+            .collect()
+    }
+
+    pub fn members(&self) -> Vec<MembershipUpdate> {
+        self.view
+            .lock()
+            .unwrap()
+            .members
+            .values()
+            .map(|member| member.update.clone())
+            .collect()
+    }
+}
@@ -1,8 +1,19 @@
-use crust_core::{delta::CrdtDelta, operation::CrdtOperation, r#type::CrdtType};
+use crust_core::{
+    anti_entropy::{
+        delta_interval::DeltaIntervalMessage, iblt::ReconcileMessage, merkle::MerkleMessage,
+    },
+    causality::version_vector::VersionVector,
+    delta::CrdtDelta,
+    operation::{CrdtOperation, OperationId},
+    r#type::CrdtType,
+    registry::CrdtKey,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::hash::Hash;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(deserialize = "K: CrdtKey"))]
 pub enum NetworkMessage<K>
 where
     K: Eq + Hash,
@@ -10,13 +21,73 @@ where
     Operation {
         payload: CrdtOperation<K>,
         sender_pod_name: String,
+        operation_id: OperationId<String>,
+        clock: VersionVector<String>,
     },
     Delta {
-        payload: CrdtDelta<K>,
+        payload: CrdtDelta,
         sender_pod_name: String,
     },
     State {
         payload: CrdtType<K>,
         sender_pod_name: String,
     },
+    DeltaInterval {
+        payload: DeltaIntervalMessage<CrdtDelta, CrdtType<K>>,
+        sender_pod_name: String,
+    },
+    Stability {
+        version_vector: VersionVector<String>,
+        contributions: Option<VersionVector<String>>,
+        retirement: Option<Value>,
+        sender_pod_name: String,
+    },
+    Merkle {
+        payload: MerkleMessage<Value>,
+        sender_pod_name: String,
+    },
+    Reconcile {
+        payload: ReconcileMessage<Value, CrdtType<K>>,
+        sender_pod_name: String,
+    },
+    Rumor {
+        rumor_id: OperationId<String>,
+        hops: u32,
+        payload: Box<NetworkMessage<K>>,
+        sender_pod_name: String,
+    },
+}
+
+impl<K> NetworkMessage<K>
+where
+    K: Eq + Hash,
+{
+    pub fn sender_pod_name(&self) -> &String {
+        match self {
+            NetworkMessage::Operation {
+                sender_pod_name, ..
+            }
+            | NetworkMessage::Delta {
+                sender_pod_name, ..
+            }
+            | NetworkMessage::State {
+                sender_pod_name, ..
+            }
+            | NetworkMessage::DeltaInterval {
+                sender_pod_name, ..
+            }
+            | NetworkMessage::Stability {
+                sender_pod_name, ..
+            }
+            | NetworkMessage::Merkle {
+                sender_pod_name, ..
+            }
+            | NetworkMessage::Reconcile {
+                sender_pod_name, ..
+            }
+            | NetworkMessage::Rumor {
+                sender_pod_name, ..
+            } => sender_pod_name,
+        }
+    }
 }
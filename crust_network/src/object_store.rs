@@ -0,0 +1,1046 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    hash::Hash,
+    sync::{Arc, Mutex, RwLock},
+};
+
+#[cfg(feature = "constraints")]
+use crust_core::constraint::{ConstraintRule, RepairPolicy};
+use crust_core::{
+    anti_entropy::{
+        delta_interval::{DeltaInterval, DeltaIntervalMessage},
+        iblt::{Iblt, ReconcileMessage},
+        merkle::{entry_hash, merkle_bucket, MerkleMessage, MerkleStep, MerkleTree},
+    },
+    causality::{
+        causal_delivery::{CausalDeliveryBuffer, CausalMessage},
+        deduplication::OperationDeduplicator,
+        stability::{CausalStability, StablePurge},
+        version_vector::VersionVector,
+    },
+    command::CrdtInnerCommand,
+    delta::CrdtDelta,
+    digest::StateDigest,
+    error::CrustError,
+    operation::{CrdtOperation, OperationId},
+    r#type::CrdtType,
+    registry::CrdtKey,
+    sync::{SyncConfig, SyncMode, SyncType},
+};
+use serde_json::Value;
+
+use crate::{
+    message::NetworkMessage,
+    persistence::{Journal, ObjectSnapshot, Persistence, StorageConfig, WalEntry, WalRecord},
+    receiver::sync_config,
+};
+
+pub type IdentifiedOperation<K> = (OperationId<String>, CrdtOperation<K>);
+
+pub struct ReplicatedObject<K>
+where
+    K: Eq + Hash,
+{
+    crdt: CrdtType<K>,
+    causal_delivery: CausalDeliveryBuffer<String, IdentifiedOperation<K>>,
+    applied_operations: OperationDeduplicator<String>,
+    delta_interval: DeltaInterval<String, CrdtDelta>,
+    stability: CausalStability<String>,
+    contributions: CausalStability<String>,
+    departed: HashSet<String>,
+    peer_retirements: HashMap<String, Value>,
+    journal: Option<Journal>,
+    removed: bool,
+}
+
+impl<K> ReplicatedObject<K>
+where
+    K: CrdtKey,
+{
+    pub fn new(crdt_type: String, replica: String) -> Result<Self, CrustError> {
+        let mut crdt = CrdtType::new(crdt_type)?;
+        crdt.assign_replica(&replica);
+        Ok(ReplicatedObject {
+            crdt,
+            causal_delivery: CausalDeliveryBuffer::new(replica.clone()),
+            applied_operations: OperationDeduplicator::new(),
+            delta_interval: DeltaInterval::new(),
+            stability: CausalStability::new(replica.clone()),
+            contributions: CausalStability::new(replica),
+            departed: HashSet::new(),
+            peer_retirements: HashMap::new(),
+            journal: None,
+            removed: false,
+        })
+    }
+
+    pub fn restore(snapshot: ObjectSnapshot<K>, replica: String) -> Result<Self, CrustError> {
+        #[cfg(feature = "constraints")]
+        let mut crdt = snapshot
+            .crdt
+            .with_constraints(snapshot.constraints, snapshot.repair_policy)?;
+        #[cfg(not(feature = "constraints"))]
+        let mut crdt = snapshot.crdt;
+        crdt.assign_replica(&replica);
+        #[cfg(feature = "reversible")]
+        {
+            crdt.operation_history = snapshot.operation_history;
+            crdt.undone_operations = snapshot.undone_operations;
+        }
+        Ok(ReplicatedObject {
+            crdt,
+            causal_delivery: CausalDeliveryBuffer::restore(replica.clone(), snapshot.delivered),
+            applied_operations: snapshot.applied_operations,
+            delta_interval: DeltaInterval::new(),
+            stability: CausalStability::new(replica.clone()),
+            contributions: CausalStability::new(replica),
+            departed: HashSet::new(),
+            peer_retirements: HashMap::new(),
+            journal: None,
+            removed: false,
+        })
+    }
+
+    pub fn snapshot(&self) -> ObjectSnapshot<K> {
+        ObjectSnapshot {
+            crdt: self.crdt.clone(),
+            delivered: self.causal_delivery.delivered().clone(),
+            applied_operations: self.applied_operations.clone(),
+            #[cfg(feature = "constraints")]
+            constraints: self.crdt.get_constraint_rules().to_vec(),
+            #[cfg(feature = "constraints")]
+            repair_policy: self.crdt.repair_policy,
+            #[cfg(feature = "reversible")]
+            operation_history: self.crdt.operation_history.clone(),
+            #[cfg(feature = "reversible")]
+            undone_operations: self.crdt.undone_operations.clone(),
+        }
+    }
+
+    pub fn enable_journal(&mut self, journal: Journal) {
+        self.journal = Some(journal);
+    }
+
+    fn remove(&mut self) {
+        self.journal = None;
+        self.removed = true;
+    }
+
+    fn journal<F>(&self, record: F) -> Result<(), CrustError>
+    where
+        F: FnOnce() -> WalRecord<K>,
+    {
+        match &self.journal {
+            Some(journal) => journal.append(record()),
+            None => Ok(()),
+        }
+    }
+
+    pub fn replay(&mut self, record: &WalRecord<K>) -> Result<(), CrustError> {
+        let config = |sync_type: &String, sync_mode: &String| -> Result<SyncConfig, CrustError> {
+            Ok(sync_config(
+                SyncType::new(sync_type.clone())?,
+                SyncMode::new(sync_mode.clone())?,
+            ))
+        };
+        match record {
+            WalRecord::Command {
+                command,
+                sync_type,
+                sync_mode,
+            } => {
+                self.apply_command(command, &mut config(sync_type, sync_mode)?)?;
+            }
+            #[cfg(feature = "reversible")]
+            WalRecord::Undo {
+                sync_type,
+                sync_mode,
+            } => {
+                self.undo(&mut config(sync_type, sync_mode)?)?;
+            }
+            #[cfg(feature = "reversible")]
+            WalRecord::Redo {
+                sync_type,
+                sync_mode,
+            } => {
+                self.redo(&mut config(sync_type, sync_mode)?)?;
+            }
+            #[cfg(feature = "constraints")]
+            WalRecord::Constraints {
+                rules,
+                repair_policy,
+            } => self.set_constraints(rules.clone(), *repair_policy)?,
+            WalRecord::Message { message } => {
+                self.receive(message)?;
+            }
+            WalRecord::Merge { state } => self.crdt.merge(state)?,
+        }
+        Ok(())
+    }
+
+    pub fn replica(&self) -> &String {
+        self.causal_delivery.replica()
+    }
+
+    pub fn crdt(&self) -> &CrdtType<K> {
+        &self.crdt
+    }
+
+    pub fn crdt_type(&self) -> String {
+        self.crdt.name()
+    }
+
+    pub fn get_state(&self) -> Value {
+        self.crdt.get_state()
+    }
+
+    pub fn digest(&self) -> StateDigest {
+        StateDigest::new(
+            self.replica().clone(),
+            self.crdt_type(),
+            &self.get_state(),
+            self.version_vector(),
+        )
+    }
+
+    #[cfg(feature = "constraints")]
+    pub fn set_constraints(
+        &mut self,
+        rules: Vec<ConstraintRule<K>>,
+        repair_policy: RepairPolicy,
+    ) -> Result<(), CrustError> {
+        self.journal(|| WalRecord::Constraints {
+            rules: rules.clone(),
+            repair_policy,
+        })?;
+        self.crdt.set_constraint_rules(rules)?;
+        self.crdt.set_repair_policy(repair_policy);
+        Ok(())
+    }
+
+    pub fn apply_command(
+        &mut self,
+        command: &CrdtInnerCommand<K>,
+        sync_config: &mut SyncConfig,
+    ) -> Result<Option<NetworkMessage<K>>, CrustError> {
+        self.journal(|| WalRecord::Command {
+            command: command.clone(),
+            sync_type: sync_config.sync_type.to_string(),
+            sync_mode: sync_config.sync_mode.to_string(),
+        })?;
+        let operation = self.crdt.apply_command(command)?;
+        self.prepare_message(operation, sync_config)
+    }
+
+    #[cfg(feature = "reversible")]
+    pub fn undo(
+        &mut self,
+        sync_config: &mut SyncConfig,
+    ) -> Result<Option<NetworkMessage<K>>, CrustError> {
+        self.journal(|| WalRecord::Undo {
+            sync_type: sync_config.sync_type.to_string(),
+            sync_mode: sync_config.sync_mode.to_string(),
+        })?;
+        let operation = self.crdt.undo()?;
+        self.prepare_message(operation, sync_config)
+    }
+
+    #[cfg(feature = "reversible")]
+    pub fn redo(
+        &mut self,
+        sync_config: &mut SyncConfig,
+    ) -> Result<Option<NetworkMessage<K>>, CrustError> {
+        self.journal(|| WalRecord::Redo {
+            sync_type: sync_config.sync_type.to_string(),
+            sync_mode: sync_config.sync_mode.to_string(),
+        })?;
+        let operation = self.crdt.redo()?;
+        self.prepare_message(operation, sync_config)
+    }
+
+    fn prepare_message(
+        &mut self,
+        operation: CrdtOperation<K>,
+        sync_config: &mut SyncConfig,
+    ) -> Result<Option<NetworkMessage<K>>, CrustError> {
+        let message = match sync_config.sync_mode {
+            SyncMode::Immediate => match sync_config.sync_type {
+                SyncType::Delta => {
+                    let delta = self.crdt.take_delta()?;
+                    self.record_delta(delta.clone());
+                    Some(NetworkMessage::Delta {
+                        payload: delta,
+                        sender_pod_name: self.replica().clone(),
+                    })
+                }
+                SyncType::Operation => Some(self.prepare_operation(operation)),
+                SyncType::State => Some(self.prepare_state()),
+            },
+            #[cfg(feature = "batch")]
+            SyncMode::BatchTimeBased => match sync_config.sync_type {
+                SyncType::Delta => self
+                    .crdt
+                    .generate_delta_time_based(sync_config)
+                    .map(|delta| self.prepare_delta(delta)),
+                SyncType::Operation => self
+                    .crdt
+                    .generate_operation_time_based(sync_config)
+                    .map(|operation| self.prepare_operation(operation)),
+                SyncType::State => Some(self.prepare_state()),
+            },
+            #[cfg(feature = "batch")]
+            SyncMode::BatchCountBased => match sync_config.sync_type {
+                SyncType::Delta => self
+                    .crdt
+                    .generate_delta_count_based(sync_config)
+                    .map(|delta| self.prepare_delta(delta)),
+                SyncType::Operation => self
+                    .crdt
+                    .generate_operation_count_based(sync_config)
+                    .map(|operation| self.prepare_operation(operation)),
+                SyncType::State => Some(self.prepare_state()),
+            },
+        };
+        Ok(message)
+    }
+
+    pub fn receive(
+        &mut self,
+        message: &NetworkMessage<K>,
+    ) -> Result<Option<NetworkMessage<K>>, CrustError> {
+        let journaled = match message {
+            NetworkMessage::Stability { .. }
+            | NetworkMessage::Merkle {
+                payload: MerkleMessage::Digest { .. },
+                ..
+            }
+            | NetworkMessage::Reconcile {
+                payload: ReconcileMessage::Sketch { .. },
+                ..
+            }
+            | NetworkMessage::DeltaInterval {
+                payload: DeltaIntervalMessage::Ack { .. },
+                ..
+            } => false,
+            NetworkMessage::Rumor { payload, .. } => payload.sender_pod_name() != self.replica(),
+            _ => message.sender_pod_name() != self.replica(),
+        };
+        if journaled {
+            self.journal(|| WalRecord::Message {
+                message: message.clone(),
+            })?;
+        }
+        self.deliver_message(message)
+    }
+
+    fn deliver_message(
+        &mut self,
+        message: &NetworkMessage<K>,
+    ) -> Result<Option<NetworkMessage<K>>, CrustError> {
+        match message {
+            NetworkMessage::Operation {
+                payload,
+                sender_pod_name,
+                operation_id,
+                clock,
+            } => {
+                if sender_pod_name == self.replica() {
+                    return Ok(None);
+                }
+                let released = self.deliver_operation(
+                    operation_id.clone(),
+                    payload.clone(),
+                    sender_pod_name.clone(),
+                    clock.clone(),
+                );
+                let mut rejections = Vec::new();
+                for (operation_id, operation) in released {
+                    if let Err(error) = self.crdt.apply(&operation) {
+                        rejections.push(format!(
+                            "operation {}:{} was rejected: {error}",
+                            operation_id.replica, operation_id.counter
+                        ));
+                    }
+                    self.applied_operations.record(operation_id);
+                }
+                if rejections.is_empty() {
+                    return Ok(None);
+                }
+                Err(CrustError::InvalidOperation(rejections.join("; ")))
+            }
+            NetworkMessage::Delta {
+                payload,
+                sender_pod_name,
+            } => {
+                if sender_pod_name != self.replica() {
+                    self.crdt.merge_delta(payload)?;
+                }
+                Ok(None)
+            }
+            NetworkMessage::State {
+                payload,
+                sender_pod_name,
+            } => {
+                if sender_pod_name != self.replica() {
+                    self.crdt.merge(payload)?;
+                }
+                Ok(None)
+            }
+            NetworkMessage::DeltaInterval {
+                payload,
+                sender_pod_name,
+            } => {
+                if sender_pod_name == self.replica() {
+                    return Ok(None);
+                }
+                let sequence = match payload {
+                    DeltaIntervalMessage::Delta { delta, sequence } => {
+                        self.crdt.merge_delta(delta)?;
+                        *sequence
+                    }
+                    DeltaIntervalMessage::State { state, sequence } => {
+                        self.crdt.merge(state)?;
+                        *sequence
+                    }
+                    DeltaIntervalMessage::Ack { sequence } => {
+                        self.delta_interval
+                            .receive_ack(sender_pod_name.clone(), *sequence);
+                        return Ok(None);
+                    }
+                };
+                Ok(Some(NetworkMessage::DeltaInterval {
+                    payload: DeltaIntervalMessage::Ack { sequence },
+                    sender_pod_name: self.replica().clone(),
+                }))
+            }
+            NetworkMessage::Stability {
+                version_vector,
+                contributions,
+                retirement,
+                sender_pod_name,
+            } => {
+                if sender_pod_name == self.replica() {
+                    return Ok(None);
+                }
+                self.stability
+                    .observe(sender_pod_name.clone(), version_vector);
+                if let Some(contributions) = contributions {
+                    self.contributions
+                        .observe(sender_pod_name.clone(), contributions);
+                }
+                if let Some(retirement) = retirement {
+                    self.peer_retirements
+                        .insert(sender_pod_name.clone(), retirement.clone());
+                }
+                self.purge_stable();
+                Ok(None)
+            }
+            NetworkMessage::Merkle {
+                payload,
+                sender_pod_name,
+            } => {
+                if sender_pod_name == self.replica() {
+                    return Ok(None);
+                }
+                self.receive_merkle(payload)
+            }
+            NetworkMessage::Reconcile {
+                payload,
+                sender_pod_name,
+            } => {
+                if sender_pod_name == self.replica() {
+                    return Ok(None);
+                }
+                self.receive_reconcile(payload)
+            }
+            NetworkMessage::Rumor { payload, .. } => self.deliver_message(payload),
+        }
+    }
+
+    pub fn merkle_tree(&self, depth: u32) -> Result<Option<MerkleTree>, CrustError> {
+        self.crdt
+            .entry_digests()
+            .map(|digests| MerkleTree::new(depth, digests))
+            .transpose()
+    }
+
+    pub fn prepare_merkle_digest(
+        &self,
+        depth: u32,
+    ) -> Result<Option<NetworkMessage<K>>, CrustError> {
+        Ok(self.merkle_tree(depth)?.map(|tree| NetworkMessage::Merkle {
+            payload: tree.digest(),
+            sender_pod_name: self.replica().clone(),
+        }))
+    }
+
+    fn prepare_merkle_entries(
+        &self,
+        depth: u32,
+        buckets: Vec<usize>,
+        reply: bool,
+    ) -> Result<NetworkMessage<K>, CrustError> {
+        let entries = self
+            .crdt
+            .entries_state(&|key| buckets.contains(&merkle_bucket(depth, key)))?;
+        Ok(NetworkMessage::Merkle {
+            payload: MerkleMessage::Entries {
+                depth,
+                buckets,
+                entries,
+                reply,
+            },
+            sender_pod_name: self.replica().clone(),
+        })
+    }
+
+    fn receive_merkle(
+        &mut self,
+        payload: &MerkleMessage<Value>,
+    ) -> Result<Option<NetworkMessage<K>>, CrustError> {
+        match payload {
+            MerkleMessage::Digest {
+                depth,
+                level,
+                nodes,
+            } => {
+                let Some(tree) = self.merkle_tree(*depth)? else {
+                    return Ok(None);
+                };
+                match tree.compare(*level, nodes) {
+                    MerkleStep::Converged => Ok(None),
+                    MerkleStep::Descend { level, nodes } => Ok(Some(NetworkMessage::Merkle {
+                        payload: MerkleMessage::Digest {
+                            depth: *depth,
+                            level,
+                            nodes,
+                        },
+                        sender_pod_name: self.replica().clone(),
+                    })),
+                    MerkleStep::Divergent { buckets } => {
+                        self.prepare_merkle_entries(*depth, buckets, true).map(Some)
+                    }
+                }
+            }
+            MerkleMessage::Entries {
+                depth,
+                buckets,
+                entries,
+                reply,
+            } => {
+                self.crdt.merge_entries(
+                    &|key| buckets.contains(&merkle_bucket(*depth, key)),
+                    entries,
+                )?;
+                if !reply {
+                    return Ok(None);
+                }
+                self.prepare_merkle_entries(*depth, buckets.clone(), false)
+                    .map(Some)
+            }
+        }
+    }
+
+    fn prepare_state(&self) -> NetworkMessage<K> {
+        NetworkMessage::State {
+            payload: self.crdt.clone(),
+            sender_pod_name: self.replica().clone(),
+        }
+    }
+
+    #[cfg(feature = "batch")]
+    fn prepare_delta(&mut self, delta: CrdtDelta) -> NetworkMessage<K> {
+        self.record_delta(delta.clone());
+        NetworkMessage::Delta {
+            payload: delta,
+            sender_pod_name: self.replica().clone(),
+        }
+    }
+
+    fn prepare_operation(&mut self, operation: CrdtOperation<K>) -> NetworkMessage<K> {
+        let operation_id = OperationId::new(
+            self.replica().clone(),
+            self.causal_delivery.delivered().get(self.replica()) + 1,
+        );
+        let message = self
+            .causal_delivery
+            .prepare((operation_id.clone(), operation));
+        self.applied_operations.record(operation_id.clone());
+        NetworkMessage::Operation {
+            payload: message.payload.1,
+            sender_pod_name: message.sender,
+            operation_id,
+            clock: message.clock,
+        }
+    }
+
+    fn deliver_operation(
+        &mut self,
+        operation_id: OperationId<String>,
+        operation: CrdtOperation<K>,
+        sender_pod_name: String,
+        clock: VersionVector<String>,
+    ) -> Vec<(OperationId<String>, CrdtOperation<K>)> {
+        if self.applied_operations.is_duplicate(&operation_id) {
+            return Vec::new();
+        }
+        let released = self.causal_delivery.receive(CausalMessage {
+            sender: sender_pod_name,
+            clock,
+            payload: (operation_id, operation),
+        });
+        released
+            .into_iter()
+            .filter(|(operation_id, _)| !self.applied_operations.is_duplicate(operation_id))
+            .collect()
+    }
+
+    pub fn record_delta(&mut self, delta: CrdtDelta) -> u64 {
+        self.delta_interval.record_delta(delta)
+    }
+
+    pub fn prepare_delta_interval(&self, peer: &str) -> Option<NetworkMessage<K>> {
+        let payload = self.delta_interval.prepare(
+            &peer.to_string(),
+            |deltas| self.crdt.aggregate_deltas(deltas).ok().flatten(),
+            || self.crdt.clone(),
+        )?;
+        Some(NetworkMessage::DeltaInterval {
+            payload,
+            sender_pod_name: self.replica().clone(),
+        })
+    }
+
+    pub fn garbage_collect_delta_log(&mut self, peers: &[String]) {
+        self.delta_interval.garbage_collect(peers);
+    }
+
+    pub fn add_stability_replicas(&mut self, replicas: &[String]) {
+        for replica in replicas {
+            self.stability.add_replica(replica.clone());
+            self.contributions.add_replica(replica.clone());
+        }
+        #[cfg(feature = "constraints")]
+        self.elect_repairer();
+    }
+
+    #[cfg(feature = "constraints")]
+    fn elect_repairer(&mut self) {
+        let repairing = self
+            .stability
+            .replicas()
+            .all(|replica| replica >= self.replica());
+        self.crdt.set_repairing(repairing);
+    }
+
+    pub fn prepare_stability(&self) -> NetworkMessage<K> {
+        NetworkMessage::Stability {
+            version_vector: self.version_vector(),
+            contributions: self.crdt.contributions(),
+            retirement: self.crdt.retirement(),
+            sender_pod_name: self.replica().clone(),
+        }
+    }
+
+    pub fn retire_departed(&mut self, live_replicas: &[String]) -> Result<bool, CrustError> {
+        let peers: Vec<String> = live_replicas
+            .iter()
+            .filter(|replica| *replica != self.replica())
+            .cloned()
+            .collect();
+        let departed: Vec<String> = self
+            .stability
+            .replicas()
+            .filter(|replica| *replica != self.replica() && !peers.contains(replica))
+            .cloned()
+            .collect();
+        for replica in &departed {
+            self.stability.remove_replica(replica);
+            self.contributions.remove_replica(replica);
+        }
+        #[cfg(feature = "constraints")]
+        self.elect_repairer();
+        self.departed.extend(departed.iter().cloned());
+        self.departed.retain(|replica| !peers.contains(replica));
+        self.peer_retirements
+            .retain(|replica, _| peers.contains(replica));
+
+        if let Some(contributions) = self.crdt.contributions() {
+            self.contributions
+                .observe(self.replica().clone(), &contributions);
+        }
+        let pending: Vec<String> = self.departed.iter().cloned().collect();
+        for replica in self
+            .crdt
+            .retire_departed(&pending, &self.contributions.stable())
+        {
+            self.departed.remove(&replica);
+        }
+
+        if self.crdt.retirement().is_none() {
+            return Ok(false);
+        }
+        let Some(reports) = peers
+            .iter()
+            .map(|peer| self.peer_retirements.get(peer).cloned())
+            .collect::<Option<Vec<Value>>>()
+        else {
+            return Ok(false);
+        };
+        if self.journal.is_none() {
+            return self.crdt.fold_retired(&reports);
+        }
+        let mut folded = self.crdt.clone();
+        if !folded.fold_retired(&reports)? {
+            return Ok(false);
+        }
+        self.journal(|| WalRecord::Merge {
+            state: folded.clone(),
+        })?;
+        self.crdt = folded;
+        Ok(true)
+    }
+
+    fn reconcile_items(&self) -> Option<HashMap<u64, String>> {
+        self.crdt.entry_digests().map(|digests| {
+            digests
+                .into_iter()
+                .map(|(key, digest)| (entry_hash(&key, digest), key))
+                .collect()
+        })
+    }
+
+    pub fn prepare_reconcile_sketch(
+        &self,
+        cells: usize,
+    ) -> Result<Option<NetworkMessage<K>>, CrustError> {
+        let Some(items) = self.reconcile_items() else {
+            return Ok(None);
+        };
+        Ok(Some(NetworkMessage::Reconcile {
+            payload: ReconcileMessage::Sketch {
+                sketch: Iblt::from_items(cells, items.into_keys())?,
+            },
+            sender_pod_name: self.replica().clone(),
+        }))
+    }
+
+    fn prepare_reconcile_entries(
+        &self,
+        mut keys: Vec<String>,
+        wanted: Option<Vec<u64>>,
+    ) -> Result<NetworkMessage<K>, CrustError> {
+        keys.sort();
+        keys.dedup();
+        let scope: HashSet<&str> = keys.iter().map(String::as_str).collect();
+        let entries = self.crdt.entries_state(&|key| scope.contains(key))?;
+        Ok(NetworkMessage::Reconcile {
+            payload: ReconcileMessage::Entries {
+                keys,
+                entries,
+                wanted,
+            },
+            sender_pod_name: self.replica().clone(),
+        })
+    }
+
+    fn receive_reconcile(
+        &mut self,
+        payload: &ReconcileMessage<Value, CrdtType<K>>,
+    ) -> Result<Option<NetworkMessage<K>>, CrustError> {
+        match payload {
+            ReconcileMessage::Sketch { sketch } => {
+                let Some(items) = self.reconcile_items() else {
+                    return Ok(None);
+                };
+                let local = Iblt::from_items(sketch.len(), items.keys().copied())?;
+                let Some(difference) = local.subtract(sketch)?.decode() else {
+                    return Ok(Some(NetworkMessage::Reconcile {
+                        payload: ReconcileMessage::Fallback {
+                            state: self.crdt.clone(),
+                            reply: true,
+                        },
+                        sender_pod_name: self.replica().clone(),
+                    }));
+                };
+                if difference.is_empty() {
+                    return Ok(None);
+                }
+                let keys = difference
+                    .local
+                    .iter()
+                    .filter_map(|item| items.get(item).cloned())
+                    .collect();
+                self.prepare_reconcile_entries(keys, Some(difference.remote))
+                    .map(Some)
+            }
+            ReconcileMessage::Entries {
+                keys,
+                entries,
+                wanted,
+            } => {
+                let items = self.reconcile_items().unwrap_or_default();
+                let scope: HashSet<&str> = keys.iter().map(String::as_str).collect();
+                self.crdt
+                    .merge_entries(&|key| scope.contains(key), entries)?;
+                let Some(wanted) = wanted else {
+                    return Ok(None);
+                };
+                let keys = keys
+                    .iter()
+                    .cloned()
+                    .chain(wanted.iter().filter_map(|item| items.get(item).cloned()))
+                    .collect();
+                self.prepare_reconcile_entries(keys, None).map(Some)
+            }
+            ReconcileMessage::Fallback { state, reply } => {
+                self.crdt.merge(state)?;
+                if !reply {
+                    return Ok(None);
+                }
+                Ok(Some(NetworkMessage::Reconcile {
+                    payload: ReconcileMessage::Fallback {
+                        state: self.crdt.clone(),
+                        reply: false,
+                    },
+                    sender_pod_name: self.replica().clone(),
+                }))
+            }
+        }
+    }
+
+    pub fn version_vector(&self) -> VersionVector<String> {
+        self.crdt
+            .version_vector()
+            .unwrap_or_else(|| self.causal_delivery.delivered().clone())
+    }
+
+    pub fn stable_version_vector(&mut self) -> VersionVector<String> {
+        let version_vector = self.version_vector();
+        self.stability
+            .observe(self.replica().clone(), &version_vector);
+        self.stability.stable()
+    }
+
+    pub fn purge_stable(&mut self) -> usize {
+        let stable = self.stable_version_vector();
+        self.applied_operations.purge_stable(&stable) + self.crdt.purge_stable(&stable)
+    }
+}
+
+type SharedObject<K> = Arc<Mutex<ReplicatedObject<K>>>;
+
+pub struct ObjectStore<K>
+where
+    K: Eq + Hash,
+{
+    replica: String,
+    objects: Arc<RwLock<HashMap<String, SharedObject<K>>>>,
+    persistence: Option<Arc<Persistence>>,
+}
+
+impl<K> Clone for ObjectStore<K>
+where
+    K: Eq + Hash,
+{
+    fn clone(&self) -> Self {
+        ObjectStore {
+            replica: self.replica.clone(),
+            objects: self.objects.clone(),
+            persistence: self.persistence.clone(),
+        }
+    }
+}
+
+impl<K> ObjectStore<K>
+where
+    K: CrdtKey,
+{
+    pub fn new(replica: String) -> Self {
+        ObjectStore {
+            replica,
+            objects: Arc::new(RwLock::new(HashMap::new())),
+            persistence: None,
+        }
+    }
+
+    pub fn open(replica: String, config: StorageConfig) -> Result<Self, CrustError> {
+        let (persistence, recovery) = Persistence::open::<K>(config)?;
+        let mut store = ObjectStore::new(replica);
+        if let Some(snapshot) = recovery.snapshot {
+            let mut objects = store.objects.write().unwrap();
+            for (name, object) in snapshot.objects {
+                let object = ReplicatedObject::restore(object, store.replica.clone())?;
+                objects.insert(name, Arc::new(Mutex::new(object)));
+            }
+        }
+        for entry in recovery.entries {
+            store.replay(entry)?;
+        }
+        let persistence = Arc::new(persistence);
+        for (name, object) in store.objects.read().unwrap().iter() {
+            object
+                .lock()
+                .unwrap()
+                .enable_journal(Journal::new(name.clone(), persistence.clone()));
+        }
+        store.persistence = Some(persistence);
+        store.snapshot()?;
+        Ok(store)
+    }
+
+    fn replay(&self, entry: WalEntry<K>) -> Result<(), CrustError> {
+        match entry {
+            WalEntry::Declare { name, crdt_type } => self.declare(&name, &crdt_type).map(|_| ()),
+            WalEntry::Remove { name } => self.remove(&name),
+            WalEntry::Record { name, record } => match self.with_object(&name, |object| {
+                let _ = object.replay(&record);
+                Ok(())
+            }) {
+                Err(CrustError::ObjectNotFound(_)) => Ok(()),
+                result => result,
+            },
+        }
+    }
+
+    pub fn snapshot(&self) -> Result<(), CrustError> {
+        let Some(persistence) = &self.persistence else {
+            return Ok(());
+        };
+        persistence.snapshot(|| {
+            self.objects
+                .read()
+                .unwrap()
+                .iter()
+                .map(|(name, object)| (name.clone(), object.lock().unwrap().snapshot()))
+                .collect::<BTreeMap<String, ObjectSnapshot<K>>>()
+        })
+    }
+
+    pub fn replica(&self) -> &String {
+        &self.replica
+    }
+
+    pub fn declare(&self, name: &str, crdt_type: &str) -> Result<bool, CrustError> {
+        let _hold = self
+            .persistence
+            .as_ref()
+            .map(|persistence| persistence.hold());
+        let mut objects = self.objects.write().unwrap();
+        if let Some(object) = objects.get(name) {
+            let expected = object.lock().unwrap().crdt_type();
+            if expected != crdt_type {
+                return Err(CrustError::TypeMismatch {
+                    expected,
+                    found: crdt_type.to_string(),
+                });
+            }
+            return Ok(false);
+        }
+        let mut object = ReplicatedObject::new(crdt_type.to_string(), self.replica.clone())?;
+        if let Some(persistence) = &self.persistence {
+            persistence.append(vec![WalEntry::<K>::Declare {
+                name: name.to_string(),
+                crdt_type: crdt_type.to_string(),
+            }])?;
+            object.enable_journal(Journal::new(name.to_string(), persistence.clone()));
+        }
+        objects.insert(name.to_string(), Arc::new(Mutex::new(object)));
+        Ok(true)
+    }
+
+    pub fn remove(&self, name: &str) -> Result<(), CrustError> {
+        let _hold = self
+            .persistence
+            .as_ref()
+            .map(|persistence| persistence.hold());
+        let mut objects = self.objects.write().unwrap();
+        let Some(object) = objects.get(name) else {
+            return Err(CrustError::ObjectNotFound(name.to_string()));
+        };
+        let mut object = object.lock().unwrap();
+        if let Some(persistence) = &self.persistence {
+            persistence.append(vec![WalEntry::<K>::Remove {
+                name: name.to_string(),
+            }])?;
+        }
+        object.remove();
+        drop(object);
+        objects.remove(name);
+        Ok(())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.objects.read().unwrap().contains_key(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn objects(&self) -> Vec<(String, String)> {
+        let objects: Vec<(String, SharedObject<K>)> = self
+            .objects
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, object)| (name.clone(), object.clone()))
+            .collect();
+        let mut objects: Vec<(String, String)> = objects
+            .into_iter()
+            .map(|(name, object)| (name, object.lock().unwrap().crdt_type()))
+            .collect();
+        objects.sort();
+        objects
+    }
+
+    pub fn with_object<R, F>(&self, name: &str, f: F) -> Result<R, CrustError>
+    where
+        F: FnOnce(&mut ReplicatedObject<K>) -> Result<R, CrustError>,
+    {
+        let object = self
+            .get(name)
+            .ok_or_else(|| CrustError::ObjectNotFound(name.to_string()))?;
+        let Some(persistence) = &self.persistence else {
+            let mut object = object.lock().unwrap();
+            if object.removed {
+                return Err(CrustError::ObjectNotFound(name.to_string()));
+            }
+            return f(&mut object);
+        };
+        let result = {
+            let _hold = persistence.hold();
+            let mut object = object.lock().unwrap();
+            if object.removed {
+                return Err(CrustError::ObjectNotFound(name.to_string()));
+            }
+            f(&mut object)
+        };
+        if persistence.snapshot_due() {
+            let _ = self.snapshot();
+        }
+        result
+    }
+
+    pub fn read_object<R, F>(&self, name: &str, f: F) -> Result<R, CrustError>
+    where
+        F: FnOnce(&ReplicatedObject<K>) -> Result<R, CrustError>,
+    {
+        let object = self
+            .get(name)
+            .ok_or_else(|| CrustError::ObjectNotFound(name.to_string()))?;
+        let object = object.lock().unwrap();
+        if object.removed {
+            return Err(CrustError::ObjectNotFound(name.to_string()));
+        }
+        f(&object)
+    }
+
+    fn get(&self, name: &str) -> Option<SharedObject<K>> {
+        self.objects.read().unwrap().get(name).cloned()
+    }
+}
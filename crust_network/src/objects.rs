@@ -0,0 +1,183 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    Json,
+};
+#[cfg(feature = "constraints")]
+use crust_core::constraint::{ConstraintRule, RepairPolicy};
+use crust_core::{
+    command::CrdtInnerCommand,
+    error::CrustError,
+    registry::CrdtKey,
+    sync::{SyncMode, SyncType},
+};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[cfg(feature = "reversible")]
+use crate::receiver::update_object;
+use crate::{
+    encoding::Encoded,
+    error::ApiError,
+    message::NetworkMessage,
+    receiver::{apply_command_to_object, deliver_message_to_object, sync_config, AppState},
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreateObject {
+    pub crdt_type: String,
+}
+
+#[cfg(feature = "constraints")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ObjectConstraints<K> {
+    pub rules: Vec<ConstraintRule<K>>,
+    #[serde(default)]
+    pub repair_policy: RepairPolicy,
+}
+
+pub async fn list_objects<K>(State(state): State<AppState<K>>) -> impl IntoResponse
+where
+    K: CrdtKey,
+{
+    let objects: Vec<_> = state
+        .objects
+        .objects()
+        .into_iter()
+        .map(|(name, crdt_type)| json!({"name": name, "crdt_type": crdt_type}))
+        .collect();
+    (StatusCode::OK, Json(json!({ "objects": objects })))
+}
+
+pub async fn create_object<K>(
+    State(state): State<AppState<K>>,
+    Path(name): Path<String>,
+    Json(request): Json<CreateObject>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    K: CrdtKey,
+{
+    let status = if state.objects.declare(&name, &request.crdt_type)? {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((
+        status,
+        Json(json!({"name": name, "crdt_type": request.crdt_type})),
+    ))
+}
+
+pub async fn get_object<K>(
+    State(state): State<AppState<K>>,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    K: CrdtKey,
+{
+    let object = state.objects.read_object(&name, |object| {
+        Ok(json!({
+            "name": name,
+            "crdt_type": object.crdt_type(),
+            "state": object.get_state(),
+        }))
+    })?;
+    Ok((StatusCode::OK, Json(object)))
+}
+
+pub async fn get_object_digest<K>(
+    State(state): State<AppState<K>>,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    K: CrdtKey,
+{
+    let digest = state
+        .objects
+        .read_object(&name, |object| Ok(object.digest()))?;
+    Ok((StatusCode::OK, Json(digest)))
+}
+
+pub async fn delete_object<K>(
+    State(state): State<AppState<K>>,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    K: CrdtKey,
+{
+    state.objects.remove(&name)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(feature = "constraints")]
+pub async fn set_object_constraints<K>(
+    State(state): State<AppState<K>>,
+    Path(name): Path<String>,
+    Json(constraints): Json<ObjectConstraints<K>>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    K: CrdtKey,
+{
+    state.objects.with_object(&name, |object| {
+        object.set_constraints(constraints.rules.clone(), constraints.repair_policy)
+    })?;
+    Ok((StatusCode::OK, Json(json!(constraints))))
+}
+
+pub async fn apply_object_command<K>(
+    State(state): State<AppState<K>>,
+    Path((name, sync_type, sync_mode)): Path<(String, String, String)>,
+    Json(command): Json<CrdtInnerCommand<K>>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    K: CrdtKey,
+{
+    let sync_config = sync_config(SyncType::new(sync_type)?, SyncMode::new(sync_mode)?);
+    if !state.objects.contains(&name) {
+        return Err(CrustError::ObjectNotFound(name).into());
+    }
+    apply_command_to_object(&state, &name, &command, sync_config).await
+}
+
+pub async fn receive_object_message<K>(
+    State(state): State<AppState<K>>,
+    Path((name, crdt_type)): Path<(String, String)>,
+    Encoded(message): Encoded<NetworkMessage<K>>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    K: CrdtKey,
+{
+    state.objects.declare(&name, &crdt_type)?;
+    deliver_message_to_object(&state, &name, message).await
+}
+
+#[cfg(feature = "reversible")]
+pub async fn undo_object_command<K>(
+    State(state): State<AppState<K>>,
+    Path((name, sync_type, sync_mode)): Path<(String, String, String)>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    K: CrdtKey,
+{
+    let sync_config = sync_config(SyncType::new(sync_type)?, SyncMode::new(sync_mode)?);
+    update_object(&state, &name, sync_config, |object, sync_config| {
+        object.undo(sync_config)
+    })
+    .await
+}
+
+#[cfg(feature = "reversible")]
+pub async fn redo_object_command<K>(
+    State(state): State<AppState<K>>,
+    Path((name, sync_type, sync_mode)): Path<(String, String, String)>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    K: CrdtKey,
+{
+    let sync_config = sync_config(SyncType::new(sync_type)?, SyncMode::new(sync_mode)?);
+    update_object(&state, &name, sync_config, |object, sync_config| {
+        object.redo(sync_config)
+    })
+    .await
+}
@@ -0,0 +1,281 @@
+use std::{
+    collections::BTreeMap,
+    hash::Hash,
+    path::PathBuf,
+    sync::{Arc, Mutex, RwLock, RwLockReadGuard},
+};
+
+#[cfg(feature = "constraints")]
+use crust_core::constraint::{ConstraintRule, RepairPolicy};
+#[cfg(feature = "reversible")]
+use crust_core::operation::CrdtOperation;
+use crust_core::{
+    causality::{deduplication::OperationDeduplicator, version_vector::VersionVector},
+    command::CrdtInnerCommand,
+    error::CrustError,
+    r#type::CrdtType,
+    registry::CrdtKey,
+};
+use crust_storage::{Checkpoint, FsyncPolicy, LogRecord, Storage, StorageBackend};
+use serde::{Deserialize, Serialize};
+
+use crate::message::NetworkMessage;
+
+#[derive(Clone, Debug)]
+pub struct StorageConfig {
+    pub backend: StorageBackend,
+    pub fsync_policy: FsyncPolicy,
+    pub snapshot_threshold: usize,
+}
+
+impl StorageConfig {
+    pub fn new(backend: StorageBackend) -> Self {
+        StorageConfig {
+            backend,
+            fsync_policy: FsyncPolicy::Always,
+            snapshot_threshold: 1000,
+        }
+    }
+
+    pub fn with_fsync_policy(mut self, fsync_policy: FsyncPolicy) -> Self {
+        self.fsync_policy = fsync_policy;
+        self
+    }
+
+    pub fn with_snapshot_threshold(mut self, snapshot_threshold: usize) -> Self {
+        self.snapshot_threshold = snapshot_threshold;
+        self
+    }
+
+    pub fn from_env() -> Result<Option<Self>, CrustError> {
+        let data_dir = std::env::var("CRUST_DATA_DIR").ok().map(PathBuf::from);
+        let backend = match (std::env::var("CRUST_STORAGE"), data_dir) {
+            (Ok(name), data_dir) => StorageBackend::new(&name, data_dir)?,
+            (Err(_), Some(data_dir)) => StorageBackend::File(data_dir),
+            (Err(_), None) => return Ok(None),
+        };
+        let mut config = StorageConfig::new(backend);
+        if let Ok(fsync_policy) = std::env::var("CRUST_FSYNC_POLICY") {
+            config = config.with_fsync_policy(FsyncPolicy::new(&fsync_policy)?);
+        }
+        if let Ok(threshold) = std::env::var("CRUST_SNAPSHOT_THRESHOLD") {
+            let threshold = threshold.parse().map_err(|_| {
+                CrustError::Storage(format!("invalid snapshot threshold `{threshold}`"))
+            })?;
+            config = config.with_snapshot_threshold(threshold);
+        }
+        Ok(Some(config))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(deserialize = "K: CrdtKey"))]
+pub enum WalRecord<K>
+where
+    K: Eq + Hash,
+{
+    Command {
+        command: CrdtInnerCommand<K>,
+        sync_type: String,
+        sync_mode: String,
+    },
+    #[cfg(feature = "reversible")]
+    Undo {
+        sync_type: String,
+        sync_mode: String,
+    },
+    #[cfg(feature = "reversible")]
+    Redo {
+        sync_type: String,
+        sync_mode: String,
+    },
+    #[cfg(feature = "constraints")]
+    Constraints {
+        rules: Vec<ConstraintRule<K>>,
+        repair_policy: RepairPolicy,
+    },
+    Message {
+        message: NetworkMessage<K>,
+    },
+    Merge {
+        state: CrdtType<K>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(deserialize = "K: CrdtKey"))]
+pub enum WalEntry<K>
+where
+    K: Eq + Hash,
+{
+    Declare { name: String, crdt_type: String },
+    Remove { name: String },
+    Record { name: String, record: WalRecord<K> },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(deserialize = "K: CrdtKey"))]
+pub struct ObjectSnapshot<K>
+where
+    K: Eq + Hash,
+{
+    pub crdt: CrdtType<K>,
+    pub delivered: VersionVector<String>,
+    pub applied_operations: OperationDeduplicator<String>,
+    #[cfg(feature = "constraints")]
+    pub constraints: Vec<ConstraintRule<K>>,
+    #[cfg(feature = "constraints")]
+    pub repair_policy: RepairPolicy,
+    #[cfg(feature = "reversible")]
+    #[serde(default)]
+    pub operation_history: Vec<(CrdtOperation<K>, i64)>,
+    #[cfg(feature = "reversible")]
+    #[serde(default)]
+    pub undone_operations: Vec<(CrdtOperation<K>, i64)>,
+}
+
+pub struct Snapshot<K>
+where
+    K: Eq + Hash,
+{
+    pub sequence: u64,
+    pub objects: BTreeMap<String, ObjectSnapshot<K>>,
+}
+
+pub struct Recovery<K>
+where
+    K: Eq + Hash,
+{
+    pub snapshot: Option<Snapshot<K>>,
+    pub entries: Vec<WalEntry<K>>,
+}
+
+struct WriteAheadLog {
+    storage: Box<dyn Storage>,
+    next_sequence: u64,
+    since_snapshot: usize,
+}
+
+pub struct Persistence {
+    config: StorageConfig,
+    gate: RwLock<()>,
+    wal: Mutex<WriteAheadLog>,
+}
+
+impl Persistence {
+    pub fn open<K>(config: StorageConfig) -> Result<(Self, Recovery<K>), CrustError>
+    where
+        K: CrdtKey,
+    {
+        let mut storage = config.backend.open(config.fsync_policy)?;
+        let snapshot = storage
+            .load_checkpoint()?
+            .map(|checkpoint| -> Result<Snapshot<K>, CrustError> {
+                Ok(Snapshot {
+                    sequence: checkpoint.sequence,
+                    objects: checkpoint
+                        .objects
+                        .into_iter()
+                        .map(|(name, object)| Ok((name, serde_json::from_value(object)?)))
+                        .collect::<Result<_, CrustError>>()?,
+                })
+            })
+            .transpose()?;
+        let snapshot_sequence = snapshot.as_ref().map_or(0, |snapshot| snapshot.sequence);
+
+        let mut last_sequence = snapshot_sequence;
+        let mut entries = Vec::new();
+        for record in storage.read_log()? {
+            if record.sequence > snapshot_sequence {
+                last_sequence = record.sequence;
+                entries.push(serde_json::from_value(record.entry)?);
+            }
+        }
+
+        let persistence = Persistence {
+            config,
+            gate: RwLock::new(()),
+            wal: Mutex::new(WriteAheadLog {
+                storage,
+                next_sequence: last_sequence + 1,
+                since_snapshot: entries.len(),
+            }),
+        };
+        Ok((persistence, Recovery { snapshot, entries }))
+    }
+
+    pub fn config(&self) -> &StorageConfig {
+        &self.config
+    }
+
+    pub(crate) fn hold(&self) -> RwLockReadGuard<'_, ()> {
+        self.gate.read().unwrap()
+    }
+
+    pub fn append<K>(&self, entries: Vec<WalEntry<K>>) -> Result<bool, CrustError>
+    where
+        K: CrdtKey,
+    {
+        let mut wal = self.wal.lock().unwrap();
+        if !entries.is_empty() {
+            let mut records = Vec::with_capacity(entries.len());
+            for entry in entries {
+                records.push(LogRecord {
+                    sequence: wal.next_sequence + records.len() as u64,
+                    entry: serde_json::to_value(entry)?,
+                });
+            }
+            wal.storage.append_log(&records)?;
+            wal.next_sequence += records.len() as u64;
+            wal.since_snapshot += records.len();
+        }
+        Ok(wal.since_snapshot >= self.config.snapshot_threshold)
+    }
+
+    pub fn snapshot_due(&self) -> bool {
+        self.wal.lock().unwrap().since_snapshot >= self.config.snapshot_threshold
+    }
+
+    pub fn snapshot<K, F>(&self, collect: F) -> Result<(), CrustError>
+    where
+        K: CrdtKey,
+        F: FnOnce() -> BTreeMap<String, ObjectSnapshot<K>>,
+    {
+        let _gate = self.gate.write().unwrap();
+        let mut wal = self.wal.lock().unwrap();
+        let checkpoint = Checkpoint {
+            sequence: wal.next_sequence - 1,
+            objects: collect()
+                .into_iter()
+                .map(|(name, object)| Ok((name, serde_json::to_value(object)?)))
+                .collect::<Result<_, CrustError>>()?,
+        };
+        wal.storage.save_checkpoint(&checkpoint)?;
+        wal.since_snapshot = 0;
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct Journal {
+    name: String,
+    persistence: Arc<Persistence>,
+}
+
+impl Journal {
+    pub fn new(name: String, persistence: Arc<Persistence>) -> Self {
+        Journal { name, persistence }
+    }
+
+    pub fn append<K>(&self, record: WalRecord<K>) -> Result<(), CrustError>
+    where
+        K: CrdtKey,
+    {
+        self.persistence
+            .append(vec![WalEntry::Record {
+                name: self.name.clone(),
+                record,
+            }])
+            .map(|_| ())
+    }
+}
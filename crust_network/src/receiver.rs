@@ -1,74 +1,183 @@
-use std::{
-    fmt::Debug,
-    sync::{Arc, RwLock},
-    time::Duration,
-};
+#[cfg(feature = "batch")]
+use std::time::Duration;
+use std::{hash::Hash, sync::Arc};
 
 use axum::{
     extract::{Path, State},
     response::IntoResponse,
     Json,
 };
-use axum_macros::debug_handler;
-use crust_config::{
-    instance::{setup_remote_test_environement, DeploymentConfig},
-    k8s_discovery::get_replica_pod_names,
-};
 use crust_core::{
     command::CrdtInnerCommand,
-    r#type::CrdtType,
-    security,
+    error::CrustError,
+    registry::CrdtKey,
     sync::{SyncConfig, SyncMode, SyncType},
 };
 use reqwest::StatusCode;
-use serde::Serialize;
-use serde_json::json;
+use serde_json::{json, Value};
 
 use crate::{
-    get_current_pod_name, get_current_service_name, message::NetworkMessage, sender::NetworkSender,
+    compression::{CompressionConfig, CompressionMetrics},
+    discovery::PeerDiscovery,
+    encoding::{Encoded, Encoding},
+    error::ApiError,
+    get_current_compression, get_current_dissemination, get_current_encoding, get_current_pod_name,
+    get_current_service_name, get_current_swim,
+    gossip::{Dissemination, RumorLog, DEFAULT_GOSSIP_FANOUT},
+    membership::{Membership, SwimConfig, SwimMessage},
+    message::NetworkMessage,
+    object_store::{ObjectStore, ReplicatedObject},
+    persistence::StorageConfig,
+    sender::NetworkSender,
 };
 
-#[derive(Clone)]
-pub struct AppState {
-    crdt_type: Arc<RwLock<Option<CrdtType<String>>>>,
+pub struct AppState<K>
+where
+    K: Eq + Hash,
+{
+    pub objects: ObjectStore<K>,
+    pub peer_discovery: PeerDiscovery,
+    pub encoding: Encoding,
+    pub compression: CompressionConfig,
+    pub compression_metrics: Arc<CompressionMetrics>,
+    pub dissemination: Dissemination,
+    pub rumors: Arc<RumorLog>,
+    pub membership: Arc<Membership>,
 }
 
-impl AppState {
-    pub fn new() -> Self {
-        let crdt_type = Arc::new(RwLock::new(CrdtType::new("none".to_string())));
-        Self { crdt_type }
+impl<K> Clone for AppState<K>
+where
+    K: Eq + Hash,
+{
+    fn clone(&self) -> Self {
+        AppState {
+            objects: self.objects.clone(),
+            peer_discovery: self.peer_discovery.clone(),
+            encoding: self.encoding,
+            compression: self.compression,
+            compression_metrics: self.compression_metrics.clone(),
+            dissemination: self.dissemination,
+            rumors: self.rumors.clone(),
+            membership: self.membership.clone(),
+        }
     }
+}
 
-    pub fn get_crdt_type(&self) -> CrdtType<String> {
-        self.crdt_type.read().unwrap().clone().unwrap()
+impl<K> AppState<K>
+where
+    K: CrdtKey,
+{
+    pub fn new() -> Result<Self, CrustError> {
+        Ok(Self {
+            objects: ObjectStore::new(get_current_pod_name()),
+            peer_discovery: PeerDiscovery::Kubernetes,
+            encoding: get_current_encoding()?,
+            compression: get_current_compression()?,
+            compression_metrics: Arc::new(CompressionMetrics::default()),
+            dissemination: get_current_dissemination()?,
+            rumors: Arc::new(RumorLog::default()),
+            membership: Arc::new(Membership::new(get_current_pod_name(), get_current_swim()?)),
+        })
     }
 
-    pub fn set_crdt_type(&mut self, crdt_type: String) {
-        let crdt_type = CrdtType::new(crdt_type);
-        self.crdt_type = Arc::new(RwLock::new(crdt_type));
+    pub fn with_storage(config: StorageConfig) -> Result<Self, CrustError> {
+        Ok(Self {
+            objects: ObjectStore::open(get_current_pod_name(), config)?,
+            peer_discovery: PeerDiscovery::Kubernetes,
+            encoding: get_current_encoding()?,
+            compression: get_current_compression()?,
+            compression_metrics: Arc::new(CompressionMetrics::default()),
+            dissemination: get_current_dissemination()?,
+            rumors: Arc::new(RumorLog::default()),
+            membership: Arc::new(Membership::new(get_current_pod_name(), get_current_swim()?)),
+        })
     }
 
-    pub fn get_or_create_crdt_type(&mut self, crdt_type: String) -> CrdtType<String> {
-        if self.crdt_type.read().unwrap().clone().unwrap().name() == crdt_type {
-            return self.get_crdt_type();
-        }
-        self.set_crdt_type(crdt_type);
-        self.get_crdt_type()
+    pub fn with_peers(
+        replica_pod_name: String,
+        replica_pod_names: Vec<String>,
+    ) -> Result<Self, CrustError> {
+        Ok(Self {
+            objects: ObjectStore::new(replica_pod_name.clone()),
+            peer_discovery: PeerDiscovery::Static(replica_pod_names),
+            encoding: get_current_encoding()?,
+            compression: get_current_compression()?,
+            compression_metrics: Arc::new(CompressionMetrics::default()),
+            dissemination: get_current_dissemination()?,
+            rumors: Arc::new(RumorLog::default()),
+            membership: Arc::new(Membership::new(replica_pod_name, get_current_swim()?)),
+        })
+    }
+
+    pub fn with_compression(mut self, compression: CompressionConfig) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    pub fn with_dissemination(mut self, dissemination: Dissemination) -> Self {
+        self.dissemination = dissemination;
+        self
+    }
+
+    pub fn with_swim(mut self, config: SwimConfig) -> Self {
+        self.membership = Arc::new(Membership::new(self.objects.replica().clone(), config));
+        self
+    }
+
+    pub async fn replica_pod_names(&self) -> Result<Vec<String>, CrustError> {
+        let replica_pod_names = self.peer_discovery.replica_pod_names().await?;
+        Ok(self.membership.live(replica_pod_names))
+    }
+
+    pub async fn retained_pod_names(&self) -> Result<Vec<String>, CrustError> {
+        let replica_pod_names = self.peer_discovery.replica_pod_names().await?;
+        Ok(self.membership.retained(replica_pod_names))
+    }
+
+    pub fn sender(&self, replica_pod_names: Vec<String>) -> NetworkSender {
+        NetworkSender::new(
+            self.objects.replica().clone(),
+            get_current_service_name(),
+            replica_pod_names,
+        )
+        .with_encoding(self.encoding)
+        .with_compression(self.compression, self.compression_metrics.clone())
+    }
+}
+
+pub fn sync_config(sync_type: SyncType, sync_mode: SyncMode) -> SyncConfig {
+    SyncConfig {
+        sync_type,
+        sync_mode,
+        #[cfg(feature = "batch")]
+        batch_times: Some(3),
+        #[cfg(feature = "batch")]
+        batching_interval: Some(Duration::from_secs(5)),
+        #[cfg(feature = "batch")]
+        last_batch_check_timestamp: None,
     }
 }
 
-#[debug_handler(state = AppState)]
-pub async fn receive_message_from_other_instances(
-    State(mut state): State<AppState>,
+pub async fn receive_message_from_other_instances<K>(
+    State(state): State<AppState<K>>,
     Path(crdt_type): Path<String>,
-    Json(message): Json<NetworkMessage<String>>,
-) -> impl IntoResponse
+    Encoded(message): Encoded<NetworkMessage<K>>,
+) -> Result<impl IntoResponse, ApiError>
 where
-    NetworkMessage<String>: Debug,
-    CrdtType<String>: Clone,
+    K: CrdtKey,
 {
-    let mut crdt = state.get_or_create_crdt_type(crdt_type);
+    state.objects.declare(&crdt_type, &crdt_type)?;
+    deliver_message_to_object(&state, &crdt_type, message).await
+}
 
+pub async fn deliver_message_to_object<K>(
+    state: &AppState<K>,
+    name: &str,
+    message: NetworkMessage<K>,
+) -> Result<(StatusCode, Json<Value>), ApiError>
+where
+    K: CrdtKey,
+{
     #[cfg(any(
         feature = "byzantine",
         feature = "confidentiality",
@@ -83,201 +192,286 @@ where
     #[cfg(feature = "confidentiality")]
     let message = security.encrypt_data(message);
 
-    match &message {
-        NetworkMessage::Operation {
-            payload,
-            sender_pod_name,
-        } => {
-            if *sender_pod_name != get_current_pod_name() {
-                crdt.apply(payload);
-            }
-        }
-        NetworkMessage::Delta {
-            payload,
-            sender_pod_name,
-        } => {
-            if *sender_pod_name != get_current_pod_name() {
-                crdt.merge_delta(payload);
-            }
+    if let NetworkMessage::Rumor { rumor_id, .. } = &message {
+        if state.rumors.contains(rumor_id) {
+            return Ok((
+                StatusCode::OK,
+                Json(json!({"message": "Rumor already delivered"})),
+            ));
         }
-        NetworkMessage::State {
-            payload,
-            sender_pod_name,
-        } => {
-            if *sender_pod_name != get_current_pod_name() {
-                crdt.merge(payload);
-            }
+    }
+
+    let (crdt_type, reply) = state.objects.with_object(name, |object| {
+        Ok((object.crdt_type(), object.receive(&message)?))
+    })?;
+
+    if let NetworkMessage::Rumor {
+        rumor_id,
+        hops,
+        payload,
+        sender_pod_name,
+    } = &message
+    {
+        if state.rumors.observe(rumor_id) && *hops > 0 {
+            let rumor = NetworkMessage::Rumor {
+                rumor_id: rumor_id.clone(),
+                hops: *hops - 1,
+                payload: payload.clone(),
+                sender_pod_name: state.objects.replica().clone(),
+            };
+            tokio::spawn(forward_rumor(
+                state.clone(),
+                name.to_string(),
+                crdt_type.clone(),
+                rumor,
+                sender_pod_name.clone(),
+            ));
         }
     }
 
-    (
+    if let Some(reply) = reply {
+        let sender = state.sender(vec![]);
+        let peer = message.sender_pod_name().clone();
+        let (name, crdt_type) = (name.to_string(), crdt_type.clone());
+        tokio::spawn(async move {
+            let _ = sender.send_to_peer(&peer, &name, &crdt_type, &reply).await;
+        });
+    }
+
+    Ok((
         StatusCode::OK,
         Json(json!({"message":format!("{:?}", message)})),
-    )
+    ))
 }
 
-#[debug_handler(state = AppState)]
-pub async fn receive_message_from_internal(
-    State(mut state): State<AppState>,
+async fn forward_rumor<K>(
+    state: AppState<K>,
+    name: String,
+    crdt_type: String,
+    rumor: NetworkMessage<K>,
+    sender_pod_name: String,
+) where
+    K: CrdtKey,
+{
+    let NetworkMessage::Rumor { rumor_id, .. } = &rumor else {
+        return;
+    };
+    let fanout = match state.dissemination {
+        Dissemination::Gossip(config) => config.fanout,
+        Dissemination::Broadcast => DEFAULT_GOSSIP_FANOUT,
+    };
+    let Ok(replica_pod_names) = state.replica_pod_names().await else {
+        return;
+    };
+    state
+        .sender(replica_pod_names)
+        .gossip_message(
+            &name,
+            &crdt_type,
+            &rumor,
+            fanout,
+            &[&sender_pod_name, &rumor_id.replica],
+        )
+        .await;
+}
+
+pub async fn receive_message_from_internal<K>(
+    State(state): State<AppState<K>>,
     Path((crdt_type, sync_type, sync_mode)): Path<(String, String, String)>,
-    Json(command): Json<CrdtInnerCommand<String>>,
-) -> impl IntoResponse
+    Json(command): Json<CrdtInnerCommand<K>>,
+) -> Result<impl IntoResponse, ApiError>
 where
-    NetworkMessage<String>: Debug + Serialize,
-    CrdtType<String>: Clone,
+    K: CrdtKey,
 {
-    let mut crdt = state.get_or_create_crdt_type(crdt_type.clone());
-
-    
-
-    let test_config = DeploymentConfig::new(
-        3,
-        crdt_type.as_str(),
-        sync_type.as_str(),
-        sync_mode.as_str(),
-        None,
-        None,
-    );
-
-    let _ = setup_remote_test_environement(&test_config).await;
-
-    let sender = NetworkSender::new(
-        get_current_pod_name(),
-        get_current_service_name(),
-        get_replica_pod_names().await.unwrap(),
-    );
-
-    
+    let sync_config = sync_config(SyncType::new(sync_type)?, SyncMode::new(sync_mode)?);
+    state.objects.declare(&crdt_type, &crdt_type)?;
+    apply_command_to_object(&state, &crdt_type, &command, sync_config).await
+}
 
-    let mut sync_config = SyncConfig {
-        sync_type: SyncType::new(sync_type),
-        sync_mode: SyncMode::new(sync_mode),
-        #[cfg(feature = "batch")]
-        batch_times: Some(3), 
-        #[cfg(feature = "batch")]
-        batching_interval: Some(Duration::from_secs(5)), 
-        #[cfg(feature = "batch")]
-        last_batch_check_timestamp: None, 
-    };
+pub async fn apply_command_to_object<K>(
+    state: &AppState<K>,
+    name: &str,
+    command: &CrdtInnerCommand<K>,
+    sync_config: SyncConfig,
+) -> Result<(StatusCode, Json<Value>), ApiError>
+where
+    K: CrdtKey,
+{
+    update_object(state, name, sync_config, |object, sync_config| {
+        object.apply_command(command, sync_config)
+    })
+    .await
+}
 
-    let message_option = handle_sync_message(
-        &mut crdt,
-        &command,
-        &mut sync_config,
-        get_current_pod_name(),
-    )
-    .await;
+pub async fn update_object<K, F>(
+    state: &AppState<K>,
+    name: &str,
+    mut sync_config: SyncConfig,
+    update: F,
+) -> Result<(StatusCode, Json<Value>), ApiError>
+where
+    K: CrdtKey,
+    F: FnOnce(
+        &mut ReplicatedObject<K>,
+        &mut SyncConfig,
+    ) -> Result<Option<NetworkMessage<K>>, CrustError>,
+{
+    let (crdt_type, message_option) = state.objects.with_object(name, |object| {
+        Ok((object.crdt_type(), update(object, &mut sync_config)?))
+    })?;
 
     if let Some(message) = message_option {
-        sender.broadcast_message(&message);
-        (
+        if let Ok(replica_pod_names) = state.replica_pod_names().await {
+            let sender = state.sender(replica_pod_names);
+            match state.dissemination {
+                Dissemination::Broadcast => {
+                    sender.broadcast_message(name, &crdt_type, &message).await;
+                }
+                Dissemination::Gossip(config) => {
+                    let rumor = NetworkMessage::Rumor {
+                        rumor_id: state.rumors.next_rumor(state.objects.replica()),
+                        hops: config.hops,
+                        payload: Box::new(message.clone()),
+                        sender_pod_name: state.objects.replica().clone(),
+                    };
+                    sender
+                        .gossip_message(name, &crdt_type, &rumor, config.fanout, &[])
+                        .await;
+                }
+            }
+        }
+        Ok((
             StatusCode::OK,
             Json(json!({"message":format!("{:?}", message)})),
-        )
+        ))
     } else {
-        (
+        Ok((
             StatusCode::OK,
-            Json(json!({"message":"No operation to sync"})), 
-        )
+            Json(json!({"message":"No operation to sync"})),
+        ))
     }
 }
 
-async fn handle_sync_message(
-    crdt: &mut CrdtType<String>,
-    command: &CrdtInnerCommand<String>,
-    sync_config: &mut SyncConfig,
-    pod_name: String,
-) -> Option<NetworkMessage<String>>
+pub async fn get_state<K>(
+    State(state): State<AppState<K>>,
+    Path(crdt_type): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
 where
-    CrdtType<String>: Clone,
+    K: CrdtKey,
 {
-    let operation = crdt.apply_command(command);
-    match sync_config.sync_mode {
-        SyncMode::Immediate => match sync_config.sync_type {
-            SyncType::Delta => Some(NetworkMessage::Delta {
-                payload: crdt.generate_delta(),
-                sender_pod_name: pod_name,
-            }),
-            SyncType::Operation => {
-                if let Some(operation) = operation {
-                    Some(NetworkMessage::Operation {
-                        payload: operation,
-                        sender_pod_name: pod_name,
-                    })
-                } else {
-                    None
-                }
-            }
-            SyncType::State => Some(NetworkMessage::State {
-                payload: crdt.clone(),
-                sender_pod_name: pod_name,
-            }),
-        },
-        #[cfg(feature = "batch")]
-        SyncMode::BatchTimeBased => match sync_config.sync_type {
-            SyncType::Delta => {
-                if let Some(delta) = crdt.generate_delta_time_based(sync_config) {
-                    Some(NetworkMessage::Delta {
-                        payload: delta,
-                        sender_pod_name: pod_name,
-                    })
-                } else {
-                    None
-                }
-            }
-            SyncType::Operation => {
-                if let Some(operation) = crdt.generate_operation_time_based(sync_config) {
-                    Some(NetworkMessage::Operation {
-                        payload: operation,
-                        sender_pod_name: pod_name,
-                    })
-                } else {
-                    None
-                }
-            }
-            SyncType::State => Some(NetworkMessage::State {
-                payload: crdt.clone(),
-                sender_pod_name: pod_name,
-            }),
-        },
-        #[cfg(feature = "batch")]
-        SyncMode::BatchCountBased => match sync_config.sync_type {
-            SyncType::Delta => {
-                if let Some(delta) = crdt.generate_delta_count_based(sync_config) {
-                    Some(NetworkMessage::Delta {
-                        payload: delta,
-                        sender_pod_name: pod_name,
-                    })
-                } else {
-                    None
-                }
-            }
-            SyncType::Operation => {
-                if let Some(operation) = crdt.generate_operation_count_based(sync_config) {
-                    Some(NetworkMessage::Operation {
-                        payload: operation,
-                        sender_pod_name: pod_name,
-                    })
-                } else {
-                    None
-                }
-            }
-            SyncType::State => Some(NetworkMessage::State {
-                payload: crdt.clone(),
-                sender_pod_name: pod_name,
-            }),
-        },
-    }
+    let crdt_state = state
+        .objects
+        .read_object(&crdt_type, |object| Ok(object.get_state()))?;
+    Ok((StatusCode::OK, Json(json!({"state": crdt_state}))))
 }
 
-#[debug_handler(state = AppState)]
-pub async fn get_state(
-    State(mut state): State<AppState>,
-    Path(crdt_type): Path<String>,
-) -> impl IntoResponse
+pub async fn get_compression_metrics<K>(State(state): State<AppState<K>>) -> impl IntoResponse
+where
+    K: CrdtKey,
+{
+    let metrics = &state.compression_metrics;
+    (
+        StatusCode::OK,
+        Json(json!({
+            "messages": metrics.messages(),
+            "compressed_messages": metrics.compressed_messages(),
+            "original_bytes": metrics.original_bytes(),
+            "sent_bytes": metrics.sent_bytes(),
+            "ratio": metrics.ratio(),
+        })),
+    )
+}
+
+pub async fn get_membership<K>(State(state): State<AppState<K>>) -> impl IntoResponse
+where
+    K: CrdtKey,
+{
+    (
+        StatusCode::OK,
+        Json(json!({
+            "replica": state.membership.replica(),
+            "incarnation": state.membership.incarnation(),
+            "members": state.membership.members(),
+        })),
+    )
+}
+
+pub async fn ping_member<K>(
+    State(state): State<AppState<K>>,
+    Json(message): Json<SwimMessage>,
+) -> Result<Json<SwimMessage>, ApiError>
 where
-    CrdtType<String>: Clone + Serialize,
+    K: CrdtKey,
 {
-    let crdt = state.get_or_create_crdt_type(crdt_type);
-    (StatusCode::OK, Json(json!({"state": crdt.get_state()})))
+    let SwimMessage::Ping {
+        sender_pod_name,
+        updates,
+    } = message
+    else {
+        return Err(
+            CrustError::InvalidOperation(format!("expected a ping, got {message:?}")).into(),
+        );
+    };
+    let membership = &state.membership;
+    membership.join(std::slice::from_ref(&sender_pod_name));
+    membership.apply_all(&updates);
+    Ok(Json(SwimMessage::Ack {
+        sender_pod_name: membership.replica().clone(),
+        updates: membership.piggyback(&sender_pod_name),
+    }))
+}
+
+pub async fn ping_member_indirectly<K>(
+    State(state): State<AppState<K>>,
+    Json(message): Json<SwimMessage>,
+) -> Result<Json<SwimMessage>, ApiError>
+where
+    K: CrdtKey,
+{
+    let SwimMessage::PingReq {
+        sender_pod_name,
+        target,
+        updates,
+    } = message
+    else {
+        return Err(CrustError::InvalidOperation(format!(
+            "expected a ping request, got {message:?}"
+        ))
+        .into());
+    };
+    let membership = &state.membership;
+    membership.join(std::slice::from_ref(&sender_pod_name));
+    membership.apply_all(&updates);
+    let sender = state.sender(vec![]);
+    let ping = SwimMessage::Ping {
+        sender_pod_name: membership.replica().clone(),
+        updates: membership.piggyback(&target),
+    };
+    let acked = match sender
+        .send_swim(
+            sender.membership_url(&target, "ping"),
+            &ping,
+            membership.config().ping_timeout,
+        )
+        .await
+    {
+        Some(SwimMessage::Ack { updates, .. }) => {
+            membership.apply_all(&updates);
+            true
+        }
+        _ => false,
+    };
+    let updates = membership.piggyback(&sender_pod_name);
+    let sender_pod_name = membership.replica().clone();
+    Ok(Json(if acked {
+        SwimMessage::Ack {
+            sender_pod_name,
+            updates,
+        }
+    } else {
+        SwimMessage::Nack {
+            sender_pod_name,
+            updates,
+        }
+    }))
 }
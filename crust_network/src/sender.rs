@@ -1,19 +1,30 @@
 use axum::{
     body::Body,
+    http::header::{CONTENT_ENCODING, CONTENT_TYPE},
     response::{IntoResponse, Response},
 };
+use futures::future::join_all;
 use reqwest::{Client, StatusCode};
 use serde::Serialize;
-use std::hash::Hash;
+use std::{hash::Hash, sync::Arc, time::Duration};
 
+use crate::compression::{Compression, CompressionConfig, CompressionMetrics};
+use crate::encoding::Encoding;
+use crate::gossip::sample_peers;
+use crate::membership::SwimMessage;
 use crate::message::NetworkMessage;
 use crate::PORT;
 
+pub const SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub struct NetworkSender {
     client: Client,
     replica_pod_name: String,
     replica_service_name: String,
     replica_pod_names: Vec<String>,
+    encoding: Encoding,
+    compression: CompressionConfig,
+    compression_metrics: Arc<CompressionMetrics>,
     #[cfg(any(
         feature = "byzantine",
         feature = "confidentiality",
@@ -30,13 +41,38 @@ impl NetworkSender {
         replica_pod_names: Vec<String>,
     ) -> Self {
         Self {
-            client: Client::new(),
+            client: Client::builder()
+                .timeout(SEND_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
             replica_pod_name,
             replica_service_name,
             replica_pod_names,
+            encoding: Encoding::default(),
+            compression: CompressionConfig::default(),
+            compression_metrics: Arc::new(CompressionMetrics::default()),
         }
     }
 
+    pub fn with_compression(
+        mut self,
+        compression: CompressionConfig,
+        compression_metrics: Arc<CompressionMetrics>,
+    ) -> Self {
+        self.compression = compression;
+        self.compression_metrics = compression_metrics;
+        self
+    }
+
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
     pub async fn send_message<K>(&self, url: String, message: &NetworkMessage<K>) -> Response
     where
         NetworkMessage<K>: Serialize,
@@ -48,32 +84,147 @@ impl NetworkSender {
         #[cfg(feature = "confidentiality")]
         let message = self.security.encrypt_data(message);
 
-        let res = self.client.get(url).json(message).send();
-        let request_response = match res.await {
-            Ok(res) => res,
-            Err(_) => return (StatusCode::BAD_REQUEST, Body::empty()).into_response(),
+        let (mut request_response, mut sent) = match self
+            .post(&url, self.encoding, self.compression, message)
+            .await
+        {
+            Some(res) => res,
+            None => return (StatusCode::BAD_REQUEST, Body::empty()).into_response(),
         };
+        if request_response.status() == StatusCode::UNSUPPORTED_MEDIA_TYPE
+            && (self.encoding, self.compression) != (Encoding::Json, CompressionConfig::default())
+        {
+            (request_response, sent) = match self
+                .post(&url, Encoding::Json, CompressionConfig::default(), message)
+                .await
+            {
+                Some(res) => res,
+                None => return (StatusCode::BAD_REQUEST, Body::empty()).into_response(),
+            };
+        }
+        let (compression, original_bytes, sent_bytes) = sent;
+        self.compression_metrics
+            .record(compression, original_bytes, sent_bytes);
         let mut response_builder = Response::builder().status(request_response.status());
         *response_builder.headers_mut().unwrap() = request_response.headers().clone();
         let body = Body::from(request_response.bytes().await.unwrap());
         response_builder.body(body).unwrap()
     }
 
-    pub fn broadcast_message<K>(&self, message: &NetworkMessage<K>)
+    async fn post<T>(
+        &self,
+        url: &str,
+        encoding: Encoding,
+        compression: CompressionConfig,
+        message: &T,
+    ) -> Option<(reqwest::Response, (Compression, usize, usize))>
+    where
+        T: Serialize,
+    {
+        let body = encoding.encode(message).ok()?;
+        let original_bytes = body.len();
+        let (compression, body) = compression.apply(body).ok()?;
+        let sent = (compression, original_bytes, body.len());
+        let mut request = self
+            .client
+            .post(url)
+            .header(CONTENT_TYPE, encoding.content_type());
+        if let Some(content_encoding) = compression.content_encoding() {
+            request = request.header(CONTENT_ENCODING, content_encoding);
+        }
+        let response = request.body(body).send().await.ok()?;
+        Some((response, sent))
+    }
+
+    pub fn peer_url(&self, pod_name: &str, object_name: &str, crdt_type: &str) -> String {
+        format!(
+            "http://{pod_name}.{service_name}.default.svc.cluster.local:{PORT}/objects/{object_name}/receive/{crdt_type}",
+            service_name = self.replica_service_name,
+        )
+    }
+
+    pub fn membership_url(&self, pod_name: &str, route: &str) -> String {
+        format!(
+            "http://{pod_name}.{service_name}.default.svc.cluster.local:{PORT}/membership/{route}",
+            service_name = self.replica_service_name,
+        )
+    }
+
+    pub async fn send_swim(
+        &self,
+        url: String,
+        message: &SwimMessage,
+        timeout: Duration,
+    ) -> Option<SwimMessage> {
+        self.client
+            .post(url)
+            .timeout(timeout)
+            .json(message)
+            .send()
+            .await
+            .ok()?
+            .error_for_status()
+            .ok()?
+            .json()
+            .await
+            .ok()
+    }
+
+    pub async fn send_to_peer<K>(
+        &self,
+        pod_name: &str,
+        object_name: &str,
+        crdt_type: &str,
+        message: &NetworkMessage<K>,
+    ) -> Response
     where
         NetworkMessage<K>: Serialize,
         K: Eq + Hash,
+    {
+        self.send_message(self.peer_url(pod_name, object_name, crdt_type), message)
+            .await
+    }
+
+    pub async fn broadcast_message<K>(
+        &self,
+        object_name: &str,
+        crdt_type: &str,
+        message: &NetworkMessage<K>,
+    ) where
+        NetworkMessage<K>: Serialize,
+        K: Eq + Hash,
     {
         for pod_name in &self.replica_pod_names {
-            let url = format!(
-                "http://{pod_name}.{service_name}.default.svc.cluster.local:{PORT}/receive",
-                pod_name = pod_name,
-                service_name = self.replica_service_name,
-            );
             if pod_name == &self.replica_pod_name {
                 continue;
             }
-            let _ = self.send_message(url, message);
+            let _ = self
+                .send_to_peer(pod_name, object_name, crdt_type, message)
+                .await;
         }
     }
+
+    pub async fn gossip_message<K>(
+        &self,
+        object_name: &str,
+        crdt_type: &str,
+        message: &NetworkMessage<K>,
+        fanout: usize,
+        excluded: &[&str],
+    ) -> Vec<String>
+    where
+        NetworkMessage<K>: Serialize,
+        K: Eq + Hash,
+    {
+        let mut excluded = excluded.to_vec();
+        excluded.push(&self.replica_pod_name);
+        let peers = sample_peers(&self.replica_pod_names, &excluded, fanout);
+        join_all(
+            peers
+                .iter()
+                .map(|pod_name| self.send_to_peer(pod_name, object_name, crdt_type, message)),
+        )
+        .await;
+        peers
+    }
 }
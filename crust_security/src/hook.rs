@@ -27,6 +27,15 @@ where
     _marker: PhantomData<K>,
 }
 
+impl<K> Default for CrustSecurityHook<K>
+where
+    K: Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<K> CrustSecurityHook<K>
 where
     K: Eq + Hash + Clone,
@@ -32,6 +32,12 @@ pub struct SecurityManager {
     access: access::AccessControl,
 }
 
+impl Default for SecurityManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SecurityManager {
     pub fn new() -> Self {
         SecurityManager {
@@ -0,0 +1,120 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+use crust_core::error::CrustError;
+
+use crate::{storage_error, Checkpoint, FsyncPolicy, LogRecord, Storage};
+
+static CHECKPOINT_FILE: &str = "snapshot.json";
+static LOG_FILE: &str = "wal.log";
+
+pub struct FileStorage {
+    data_dir: PathBuf,
+    log: File,
+    fsync_policy: FsyncPolicy,
+    unsynced: usize,
+}
+
+impl FileStorage {
+    pub fn open(data_dir: &Path, fsync_policy: FsyncPolicy) -> Result<Self, CrustError> {
+        fs::create_dir_all(data_dir).map_err(storage_error)?;
+        let path = data_dir.join(LOG_FILE);
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(storage_error)?;
+        let valid_length = valid_log_length(&path)?;
+        if log.metadata().map_err(storage_error)?.len() > valid_length {
+            log.set_len(valid_length).map_err(storage_error)?;
+            log.sync_all().map_err(storage_error)?;
+        }
+        Ok(FileStorage {
+            data_dir: data_dir.to_path_buf(),
+            log,
+            fsync_policy,
+            unsynced: 0,
+        })
+    }
+}
+
+fn valid_log_length(path: &Path) -> Result<u64, CrustError> {
+    let mut reader = BufReader::new(File::open(path).map_err(storage_error)?);
+    let mut length = 0;
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let read = reader.read_until(b'\n', &mut line).map_err(storage_error)?;
+        if line.last() != Some(&b'\n') || serde_json::from_slice::<LogRecord>(&line).is_err() {
+            return Ok(length);
+        }
+        length += read as u64;
+    }
+}
+
+impl Storage for FileStorage {
+    fn load_checkpoint(&mut self) -> Result<Option<Checkpoint>, CrustError> {
+        let path = self.data_dir.join(CHECKPOINT_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let file = File::open(path).map_err(storage_error)?;
+        Ok(Some(serde_json::from_reader(BufReader::new(file))?))
+    }
+
+    fn save_checkpoint(&mut self, checkpoint: &Checkpoint) -> Result<(), CrustError> {
+        let path = self.data_dir.join(CHECKPOINT_FILE);
+        let temporary_path = path.with_extension("tmp");
+        let mut file = File::create(&temporary_path).map_err(storage_error)?;
+        file.write_all(&serde_json::to_vec(checkpoint)?)
+            .map_err(storage_error)?;
+        file.sync_all().map_err(storage_error)?;
+        fs::rename(&temporary_path, &path).map_err(storage_error)?;
+        if let Ok(directory) = File::open(&self.data_dir) {
+            let _ = directory.sync_all();
+        }
+
+        self.log.set_len(0).map_err(storage_error)?;
+        self.log.sync_all().map_err(storage_error)?;
+        self.unsynced = 0;
+        Ok(())
+    }
+
+    fn read_log(&mut self) -> Result<Vec<LogRecord>, CrustError> {
+        let file = File::open(self.data_dir.join(LOG_FILE)).map_err(storage_error)?;
+        let mut records = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let Ok(record) = line
+                .map_err(storage_error)
+                .and_then(|line| Ok(serde_json::from_str::<LogRecord>(&line)?))
+            else {
+                break;
+            };
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    fn append_log(&mut self, records: &[LogRecord]) -> Result<(), CrustError> {
+        let mut buffer = Vec::new();
+        for record in records {
+            serde_json::to_writer(&mut buffer, record)?;
+            buffer.push(b'\n');
+        }
+        self.log.write_all(&buffer).map_err(storage_error)?;
+        self.unsynced += records.len();
+        let sync = match self.fsync_policy {
+            FsyncPolicy::Always => true,
+            FsyncPolicy::Every(count) => self.unsynced >= count,
+            FsyncPolicy::Never => false,
+        };
+        if sync {
+            self.log.sync_data().map_err(storage_error)?;
+            self.unsynced = 0;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use crust_core::error::CrustError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[cfg(feature = "sqlite")]
+use crate::sqlite::SqliteStorage;
+use crate::{file::FileStorage, memory::MemoryStorage};
+
+pub mod file;
+pub mod memory;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FsyncPolicy {
+    Always,
+    Every(usize),
+    Never,
+}
+
+impl FsyncPolicy {
+    pub fn new(name: &str) -> Result<Self, CrustError> {
+        match name {
+            "always" => Ok(FsyncPolicy::Always),
+            "never" => Ok(FsyncPolicy::Never),
+            _ => name
+                .strip_prefix("every:")
+                .and_then(|count| count.parse().ok())
+                .filter(|count| *count > 0)
+                .map(FsyncPolicy::Every)
+                .ok_or_else(|| CrustError::Storage(format!("unknown fsync policy `{name}`"))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub sequence: u64,
+    pub objects: BTreeMap<String, Value>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LogRecord {
+    pub sequence: u64,
+    pub entry: Value,
+}
+
+pub trait Storage: Send {
+    fn load_checkpoint(&mut self) -> Result<Option<Checkpoint>, CrustError>;
+    fn save_checkpoint(&mut self, checkpoint: &Checkpoint) -> Result<(), CrustError>;
+    fn read_log(&mut self) -> Result<Vec<LogRecord>, CrustError>;
+    fn append_log(&mut self, records: &[LogRecord]) -> Result<(), CrustError>;
+}
+
+#[derive(Clone, Debug)]
+pub enum StorageBackend {
+    Memory(MemoryStorage),
+    File(PathBuf),
+    #[cfg(feature = "sqlite")]
+    Sqlite(PathBuf),
+}
+
+impl StorageBackend {
+    pub fn new(name: &str, data_dir: Option<PathBuf>) -> Result<Self, CrustError> {
+        let data_dir = |name: &str| {
+            data_dir.clone().ok_or_else(|| {
+                CrustError::Storage(format!("storage backend `{name}` needs a data directory"))
+            })
+        };
+        match name {
+            "memory" => Ok(StorageBackend::Memory(MemoryStorage::new())),
+            "file" => Ok(StorageBackend::File(data_dir(name)?)),
+            #[cfg(feature = "sqlite")]
+            "sqlite" => Ok(StorageBackend::Sqlite(
+                data_dir(name)?.join("crust.sqlite3"),
+            )),
+            _ => Err(CrustError::Storage(format!(
+                "unknown storage backend `{name}`"
+            ))),
+        }
+    }
+
+    pub fn open(&self, fsync_policy: FsyncPolicy) -> Result<Box<dyn Storage>, CrustError> {
+        match self {
+            StorageBackend::Memory(storage) => Ok(Box::new(storage.clone())),
+            StorageBackend::File(data_dir) => {
+                Ok(Box::new(FileStorage::open(data_dir, fsync_policy)?))
+            }
+            #[cfg(feature = "sqlite")]
+            StorageBackend::Sqlite(path) => Ok(Box::new(SqliteStorage::open(path, fsync_policy)?)),
+        }
+    }
+}
+
+pub fn storage_error(error: impl ToString) -> CrustError {
+    CrustError::Storage(error.to_string())
+}
@@ -0,0 +1,54 @@
+use std::sync::{Arc, Mutex};
+
+use crust_core::error::CrustError;
+
+use crate::{Checkpoint, LogRecord, Storage};
+
+#[derive(Debug, Default)]
+struct MemoryState {
+    checkpoint: Option<Checkpoint>,
+    log: Vec<LogRecord>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStorage {
+    state: Arc<Mutex<MemoryState>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn log_len(&self) -> usize {
+        self.state.lock().unwrap().log.len()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn load_checkpoint(&mut self) -> Result<Option<Checkpoint>, CrustError> {
+        Ok(self.state.lock().unwrap().checkpoint.clone())
+    }
+
+    fn save_checkpoint(&mut self, checkpoint: &Checkpoint) -> Result<(), CrustError> {
+        let mut state = self.state.lock().unwrap();
+        state
+            .log
+            .retain(|record| record.sequence > checkpoint.sequence);
+        state.checkpoint = Some(checkpoint.clone());
+        Ok(())
+    }
+
+    fn read_log(&mut self) -> Result<Vec<LogRecord>, CrustError> {
+        Ok(self.state.lock().unwrap().log.clone())
+    }
+
+    fn append_log(&mut self, records: &[LogRecord]) -> Result<(), CrustError> {
+        self.state
+            .lock()
+            .unwrap()
+            .log
+            .extend(records.iter().cloned());
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use std::path::Path;
+
+use crust_core::error::CrustError;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::{storage_error, Checkpoint, FsyncPolicy, LogRecord, Storage};
+
+pub struct SqliteStorage {
+    connection: Connection,
+}
+
+impl SqliteStorage {
+    pub fn open(path: &Path, fsync_policy: FsyncPolicy) -> Result<Self, CrustError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(storage_error)?;
+        }
+        let connection = Connection::open(path).map_err(storage_error)?;
+        Self::initialize(connection, fsync_policy)
+    }
+
+    pub fn open_in_memory() -> Result<Self, CrustError> {
+        let connection = Connection::open_in_memory().map_err(storage_error)?;
+        Self::initialize(connection, FsyncPolicy::Never)
+    }
+
+    fn initialize(connection: Connection, fsync_policy: FsyncPolicy) -> Result<Self, CrustError> {
+        let synchronous = match fsync_policy {
+            FsyncPolicy::Always => "FULL",
+            FsyncPolicy::Every(_) => "NORMAL",
+            FsyncPolicy::Never => "OFF",
+        };
+        connection
+            .execute_batch(&format!(
+                "PRAGMA journal_mode = WAL;
+                 PRAGMA synchronous = {synchronous};
+                 CREATE TABLE IF NOT EXISTS checkpoint (
+                     id INTEGER PRIMARY KEY CHECK (id = 0),
+                     sequence INTEGER NOT NULL
+                 );
+                 CREATE TABLE IF NOT EXISTS objects (
+                     name TEXT PRIMARY KEY,
+                     state TEXT NOT NULL
+                 );
+                 CREATE TABLE IF NOT EXISTS log (
+                     sequence INTEGER PRIMARY KEY,
+                     entry TEXT NOT NULL
+                 );"
+            ))
+            .map_err(storage_error)?;
+        Ok(SqliteStorage { connection })
+    }
+}
+
+impl Storage for SqliteStorage {
+    fn load_checkpoint(&mut self) -> Result<Option<Checkpoint>, CrustError> {
+        let Some(sequence) = self
+            .connection
+            .query_row("SELECT sequence FROM checkpoint WHERE id = 0", [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .optional()
+            .map_err(storage_error)?
+        else {
+            return Ok(None);
+        };
+        let mut statement = self
+            .connection
+            .prepare("SELECT name, state FROM objects")
+            .map_err(storage_error)?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(storage_error)?;
+        let mut checkpoint = Checkpoint {
+            sequence: sequence as u64,
+            ..Checkpoint::default()
+        };
+        for row in rows {
+            let (name, state) = row.map_err(storage_error)?;
+            checkpoint
+                .objects
+                .insert(name, serde_json::from_str(&state)?);
+        }
+        Ok(Some(checkpoint))
+    }
+
+    fn save_checkpoint(&mut self, checkpoint: &Checkpoint) -> Result<(), CrustError> {
+        let transaction = self.connection.transaction().map_err(storage_error)?;
+        transaction
+            .execute(
+                "INSERT OR REPLACE INTO checkpoint (id, sequence) VALUES (0, ?1)",
+                params![checkpoint.sequence as i64],
+            )
+            .map_err(storage_error)?;
+        transaction
+            .execute("DELETE FROM objects", [])
+            .map_err(storage_error)?;
+        for (name, state) in &checkpoint.objects {
+            transaction
+                .execute(
+                    "INSERT INTO objects (name, state) VALUES (?1, ?2)",
+                    params![name, serde_json::to_string(state)?],
+                )
+                .map_err(storage_error)?;
+        }
+        transaction
+            .execute(
+                "DELETE FROM log WHERE sequence <= ?1",
+                params![checkpoint.sequence as i64],
+            )
+            .map_err(storage_error)?;
+        transaction.commit().map_err(storage_error)
+    }
+
+    fn read_log(&mut self) -> Result<Vec<LogRecord>, CrustError> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT sequence, entry FROM log ORDER BY sequence")
+            .map_err(storage_error)?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(storage_error)?;
+        let mut records = Vec::new();
+        for row in rows {
+            let (sequence, entry) = row.map_err(storage_error)?;
+            records.push(LogRecord {
+                sequence: sequence as u64,
+                entry: serde_json::from_str(&entry)?,
+            });
+        }
+        Ok(records)
+    }
+
+    fn append_log(&mut self, records: &[LogRecord]) -> Result<(), CrustError> {
+        let transaction = self.connection.transaction().map_err(storage_error)?;
+        for record in records {
+            transaction
+                .execute(
+                    "INSERT OR REPLACE INTO log (sequence, entry) VALUES (?1, ?2)",
+                    params![
+                        record.sequence as i64,
+                        serde_json::to_string(&record.entry)?
+                    ],
+                )
+                .map_err(storage_error)?;
+        }
+        transaction.commit().map_err(storage_error)
+    }
+}
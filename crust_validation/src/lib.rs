@@ -1,4 +1,5 @@
 mod local_tests;
-mod local_validation;
+pub mod local_validation;
 mod remote_tests;
-mod remote_validation;
+#[allow(async_fn_in_trait)]
+pub mod remote_validation;
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        anti_entropy::delta_interval::{DeltaInterval, DeltaIntervalMessage},
+        core::counter::gcounter::{GCounter, GCounterDelta},
+        sync::{Crdt, DeltaBased, StateBased},
+    };
+
+    type Log = DeltaInterval<String, GCounterDelta<String>>;
+    type Message = DeltaIntervalMessage<GCounterDelta<String>, GCounter<String>>;
+
+    fn increment(counter: &mut GCounter<String>, log: &mut Log, key: &str) {
+        counter.increment(key.to_string());
+        log.record_delta(GCounterDelta {
+            increment_map: [(key.to_string(), counter.counter[key])].into(),
+        });
+    }
+
+    fn prepare(counter: &GCounter<String>, log: &Log, peer: &str) -> Option<Message> {
+        log.prepare(
+            &peer.to_string(),
+            |deltas| counter.clone().aggregate_deltas(deltas),
+            || counter.clone(),
+        )
+    }
+
+    fn deliver(peer: &mut GCounter<String>, message: Message) -> u64 {
+        match message {
+            DeltaIntervalMessage::Delta { delta, sequence } => {
+                peer.merge_delta(&delta);
+                sequence
+            }
+            DeltaIntervalMessage::State { state, sequence } => {
+                peer.merge(&state);
+                sequence
+            }
+            DeltaIntervalMessage::Ack { sequence } => sequence,
+        }
+    }
+
+    #[test]
+    fn test_delta_interval_resends_lost_deltas() {
+        let mut a = GCounter::<String>::new();
+        let mut b = GCounter::<String>::new();
+        let mut log = Log::new();
+
+        increment(&mut a, &mut log, "a");
+        let lost = prepare(&a, &log, "b");
+        assert!(lost.is_some());
+        increment(&mut a, &mut log, "a");
+
+        let message = prepare(&a, &log, "b").unwrap();
+        assert!(matches!(message, DeltaIntervalMessage::Delta { .. }));
+        let sequence = deliver(&mut b, message);
+        log.receive_ack("b".to_string(), sequence);
+
+        assert_eq!(a, b);
+        assert!(prepare(&a, &log, "b").is_none());
+    }
+
+    #[test]
+    fn test_delta_interval_only_sends_unacknowledged_deltas() {
+        let mut a = GCounter::<String>::new();
+        let mut log = Log::new();
+        increment(&mut a, &mut log, "a");
+        log.receive_ack("b".to_string(), 1);
+        increment(&mut a, &mut log, "x");
+
+        match prepare(&a, &log, "b").unwrap() {
+            DeltaIntervalMessage::Delta { delta, sequence } => {
+                assert_eq!(sequence, 2);
+                assert!(!delta.increment_map.contains_key("a"));
+                assert_eq!(delta.increment_map["x"], 1);
+            }
+            _ => panic!("expected a delta interval"),
+        }
+    }
+
+    #[test]
+    fn test_delta_interval_garbage_collects_acknowledged_prefix() {
+        let mut a = GCounter::<String>::new();
+        let mut log = Log::new();
+        increment(&mut a, &mut log, "a");
+        increment(&mut a, &mut log, "a");
+        log.receive_ack("b".to_string(), 2);
+        log.receive_ack("c".to_string(), 1);
+
+        log.garbage_collect(&["b".to_string(), "c".to_string()]);
+        assert_eq!(log.log_len(), 1);
+        log.receive_ack("c".to_string(), 2);
+        log.garbage_collect(&["b".to_string(), "c".to_string()]);
+        assert_eq!(log.log_len(), 0);
+    }
+
+    #[test]
+    fn test_delta_interval_falls_back_to_full_state_after_truncation() {
+        let mut a = GCounter::<String>::new();
+        let mut c = GCounter::<String>::new();
+        let mut log = Log::new();
+        increment(&mut a, &mut log, "a");
+        increment(&mut a, &mut log, "a");
+        log.receive_ack("b".to_string(), 2);
+        log.garbage_collect(&["b".to_string()]);
+        increment(&mut a, &mut log, "a");
+
+        let message = prepare(&a, &log, "c").unwrap();
+        assert!(matches!(message, DeltaIntervalMessage::State { .. }));
+        let sequence = deliver(&mut c, message);
+        log.receive_ack("c".to_string(), sequence);
+
+        assert_eq!(a, c);
+        assert!(prepare(&a, &log, "c").is_none());
+    }
+}
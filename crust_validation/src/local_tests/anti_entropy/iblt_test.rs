@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::anti_entropy::iblt::{Iblt, MAX_IBLT_CELLS};
+
+    #[test]
+    fn test_decode_recovers_the_symmetric_difference() {
+        let shared = 1000..1200u64;
+        let local = Iblt::from_items(60, shared.clone().chain([1, 2, 3])).unwrap();
+        let remote = Iblt::from_items(60, shared.chain([7, 8])).unwrap();
+
+        let mut difference = local.subtract(&remote).unwrap().decode().unwrap();
+        difference.local.sort();
+        difference.remote.sort();
+        assert_eq!(difference.local, vec![1, 2, 3]);
+        assert_eq!(difference.remote, vec![7, 8]);
+
+        let same = local.subtract(&local).unwrap().decode().unwrap();
+        assert!(same.is_empty());
+    }
+
+    #[test]
+    fn test_decode_fails_when_the_difference_overflows_the_sketch() {
+        let local = Iblt::from_items(6, 0..100u64).unwrap();
+        let remote = Iblt::from_items(6, Vec::new()).unwrap();
+        assert!(local.subtract(&remote).unwrap().decode().is_none());
+    }
+
+    #[test]
+    fn test_sketch_sizes_are_validated() {
+        assert!(Iblt::new(0).is_err());
+        assert!(Iblt::new(MAX_IBLT_CELLS + 1).is_err());
+        assert_eq!(Iblt::new(10).unwrap().len(), 12);
+        let small = Iblt::new(6).unwrap();
+        let large = Iblt::new(12).unwrap();
+        assert!(small.subtract(&large).is_err());
+    }
+
+    #[test]
+    fn test_insert_and_remove_cancel_out() {
+        let mut iblt = Iblt::new(30).unwrap();
+        iblt.insert(42);
+        iblt.insert(43);
+        iblt.remove(42);
+        iblt.remove(43);
+        assert!(iblt.is_empty());
+    }
+}
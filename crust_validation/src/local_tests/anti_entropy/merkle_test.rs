@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        anti_entropy::merkle::{merkle_bucket, MerkleStep, MerkleTree, MAX_MERKLE_DEPTH},
+        core::set::orset::ORSet,
+        registry::{CrdtObject, Reconcilable},
+    };
+
+    fn tree(depth: u32, entries: &[(&str, u64)]) -> MerkleTree {
+        MerkleTree::new(depth, entries.iter().copied()).unwrap()
+    }
+
+    fn descend(local: &MerkleTree, remote: &MerkleTree) -> Vec<usize> {
+        let mut level = 0;
+        let mut nodes = vec![(0, remote.root())];
+        loop {
+            match local.compare(level, &nodes) {
+                MerkleStep::Converged => return Vec::new(),
+                MerkleStep::Divergent { buckets } => return buckets,
+                MerkleStep::Descend {
+                    level: next,
+                    nodes: children,
+                } => {
+                    level = next;
+                    nodes = children
+                        .into_iter()
+                        .map(|(index, _)| (index, remote.hash(level, index).unwrap()))
+                        .collect();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_identical_entries_produce_identical_roots() {
+        let a = tree(4, &[("x", 1), ("y", 2), ("z", 3)]);
+        let b = tree(4, &[("z", 3), ("x", 1), ("y", 2)]);
+        assert_eq!(a.root(), b.root());
+        assert_eq!(a.compare(0, &[(0, b.root())]), MerkleStep::Converged);
+        assert_eq!(tree(4, &[]).root(), 0);
+    }
+
+    #[test]
+    fn test_descent_isolates_the_divergent_bucket() {
+        let a = tree(4, &[("x", 1), ("y", 2), ("z", 3)]);
+        let b = tree(4, &[("x", 1), ("y", 5), ("z", 3)]);
+        assert_ne!(a.root(), b.root());
+        assert_eq!(descend(&a, &b), vec![merkle_bucket(4, "y")]);
+
+        let c = tree(4, &[("x", 1), ("z", 3)]);
+        assert_eq!(descend(&a, &c), vec![merkle_bucket(4, "y")]);
+    }
+
+    #[test]
+    fn test_depth_is_bounded() {
+        assert!(MerkleTree::new(MAX_MERKLE_DEPTH + 1, Vec::<(String, u64)>::new()).is_err());
+        let flat = tree(0, &[("x", 1)]);
+        assert_eq!(
+            flat.compare(0, &[(0, 7)]),
+            MerkleStep::Divergent { buckets: vec![0] }
+        );
+    }
+
+    #[test]
+    fn test_merging_entries_repairs_only_the_selected_keys() {
+        let mut a = ORSet::<String>::with_replica("a");
+        let mut b = ORSet::<String>::with_replica("b");
+        a.add("shared".to_string());
+        CrdtObject::merge(&mut b, &a.to_state().unwrap()).unwrap();
+        a.remove(&"shared".to_string());
+        a.add("only-a".to_string());
+        b.add("only-b".to_string());
+
+        let scope = |key: &str| key != "\"only-a\"";
+        let entries = a.entries_state(&scope).unwrap();
+        b.merge_entries(&scope, &entries).unwrap();
+
+        assert!(!b.contains(&"shared".to_string()));
+        assert!(b.contains(&"only-b".to_string()));
+        assert!(!b.contains(&"only-a".to_string()));
+
+        let scope = |_: &str| true;
+        let entries = a.entries_state(&scope).unwrap();
+        b.merge_entries(&scope, &entries).unwrap();
+        let entries = b.entries_state(&scope).unwrap();
+        a.merge_entries(&scope, &entries).unwrap();
+
+        let (mut a_digests, mut b_digests) = (a.entry_digests(), b.entry_digests());
+        a_digests.sort();
+        b_digests.sort();
+        assert_eq!(a_digests, b_digests);
+        assert_eq!(a.len(), 2);
+    }
+}
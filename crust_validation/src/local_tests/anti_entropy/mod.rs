@@ -0,0 +1,3 @@
+mod delta_interval_test;
+mod merkle_test;
+mod iblt_test;
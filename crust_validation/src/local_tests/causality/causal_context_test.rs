@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::causality::{causal_context::CausalContext, dot::Dot, CausalOrdering};
+
+    #[test]
+    fn test_causal_context_next_dot_is_contiguous() {
+        let mut cc = CausalContext::<String>::new();
+        assert_eq!(cc.next_dot("a".to_string()), Dot::new("a".to_string(), 1));
+        assert_eq!(cc.next_dot("a".to_string()), Dot::new("a".to_string(), 2));
+        assert!(cc.dot_cloud.is_empty());
+        assert_eq!(cc.version_vector.get(&"a".to_string()), 2);
+    }
+
+    #[test]
+    fn test_causal_context_compacts_dot_cloud() {
+        let mut cc = CausalContext::<String>::new();
+        cc.insert(Dot::new("a".to_string(), 3));
+        cc.insert(Dot::new("a".to_string(), 2));
+        assert_eq!(cc.dot_cloud.len(), 2);
+        assert!(!cc.contains(&Dot::new("a".to_string(), 1)));
+
+        cc.insert(Dot::new("a".to_string(), 1));
+        assert!(cc.dot_cloud.is_empty());
+        assert_eq!(cc.version_vector.get(&"a".to_string()), 3);
+    }
+
+    #[test]
+    fn test_causal_context_merge_and_dominance() {
+        let mut a = CausalContext::<String>::new();
+        let mut b = CausalContext::<String>::new();
+        a.next_dot("a".to_string());
+        b.insert(Dot::new("a".to_string(), 2));
+        assert_eq!(a.compare(&b), CausalOrdering::Concurrent);
+
+        let mut merged = a.clone();
+        merged.merge(&b);
+        assert!(merged.dot_cloud.is_empty());
+        assert_eq!(merged.compare(&a), CausalOrdering::After);
+        assert_eq!(b.compare(&merged), CausalOrdering::Before);
+        assert_eq!(merged.max(&"a".to_string()), 2);
+    }
+
+    #[test]
+    fn test_causal_context_serde_round_trip() {
+        let mut cc = CausalContext::<String>::new();
+        cc.next_dot("a".to_string());
+        cc.insert(Dot::new("b".to_string(), 4));
+
+        let json = serde_json::to_string(&cc).unwrap();
+        let decoded: CausalContext<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(cc, decoded);
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::causality::causal_delivery::CausalDeliveryBuffer;
+
+    #[test]
+    fn test_causal_delivery_releases_in_order() {
+        let mut a = CausalDeliveryBuffer::<String, u64>::new("a".to_string());
+        let mut b = CausalDeliveryBuffer::<String, u64>::new("b".to_string());
+        let first = a.prepare(1);
+        let second = a.prepare(2);
+
+        assert!(b.receive(second).is_empty());
+        assert_eq!(b.pending_len(), 1);
+        assert_eq!(b.receive(first), vec![1, 2]);
+        assert_eq!(b.pending_len(), 0);
+        assert_eq!(b.delivered().get(&"a".to_string()), 2);
+    }
+
+    #[test]
+    fn test_causal_delivery_waits_for_transitive_dependency() {
+        let mut a = CausalDeliveryBuffer::<String, &str>::new("a".to_string());
+        let mut b = CausalDeliveryBuffer::<String, &str>::new("b".to_string());
+        let mut c = CausalDeliveryBuffer::<String, &str>::new("c".to_string());
+
+        let from_a = a.prepare("a1");
+        assert_eq!(b.receive(from_a.clone()), vec!["a1"]);
+        let from_b = b.prepare("b1");
+
+        assert!(c.receive(from_b).is_empty());
+        assert_eq!(c.receive(from_a), vec!["a1", "b1"]);
+    }
+
+    #[test]
+    fn test_causal_delivery_concurrent_messages_are_released_immediately() {
+        let mut a = CausalDeliveryBuffer::<String, &str>::new("a".to_string());
+        let mut b = CausalDeliveryBuffer::<String, &str>::new("b".to_string());
+        let mut c = CausalDeliveryBuffer::<String, &str>::new("c".to_string());
+
+        assert_eq!(c.receive(b.prepare("b1")), vec!["b1"]);
+        assert_eq!(c.receive(a.prepare("a1")), vec!["a1"]);
+    }
+
+    #[test]
+    fn test_causal_delivery_drops_duplicates() {
+        let mut a = CausalDeliveryBuffer::<String, u64>::new("a".to_string());
+        let mut b = CausalDeliveryBuffer::<String, u64>::new("b".to_string());
+        let first = a.prepare(1);
+        let second = a.prepare(2);
+
+        assert!(b.receive(second.clone()).is_empty());
+        assert!(b.receive(second.clone()).is_empty());
+        assert_eq!(b.pending_len(), 1);
+        assert_eq!(b.receive(first.clone()), vec![1, 2]);
+        assert!(b.receive(first).is_empty());
+        assert!(b.receive(second).is_empty());
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        causality::{deduplication::OperationDeduplicator, version_vector::VersionVector},
+        operation::OperationId,
+    };
+
+    #[test]
+    fn test_deduplicator_records_each_operation_once() {
+        let mut dedup = OperationDeduplicator::<String>::new();
+        let id = OperationId::new("a".to_string(), 1);
+        assert!(dedup.record(id.clone()));
+        assert!(dedup.is_duplicate(&id));
+        assert!(!dedup.record(id));
+    }
+
+    #[test]
+    fn test_deduplicator_compacts_contiguous_ids() {
+        let mut dedup = OperationDeduplicator::<String>::new();
+        assert!(dedup.record(OperationId::new("a".to_string(), 2)));
+        assert!(dedup.record(OperationId::new("a".to_string(), 3)));
+        assert_eq!(dedup.pending_len(), 2);
+        assert!(!dedup.is_duplicate(&OperationId::new("a".to_string(), 1)));
+
+        assert!(dedup.record(OperationId::new("a".to_string(), 1)));
+        assert_eq!(dedup.pending_len(), 0);
+        assert_eq!(dedup.seen().version_vector.get(&"a".to_string()), 3);
+    }
+
+    #[test]
+    fn test_deduplicator_compacts_against_stable_version_vector() {
+        let mut dedup = OperationDeduplicator::<String>::new();
+        dedup.record(OperationId::new("a".to_string(), 5));
+        dedup.record(OperationId::new("b".to_string(), 2));
+
+        let mut stable = VersionVector::new();
+        stable.observe(&OperationId::new("a".to_string(), 4));
+        stable.observe(&OperationId::new("b".to_string(), 1));
+        dedup.compact(&stable);
+
+        assert_eq!(dedup.pending_len(), 0);
+        assert!(dedup.is_duplicate(&OperationId::new("a".to_string(), 3)));
+        assert!(dedup.is_duplicate(&OperationId::new("b".to_string(), 2)));
+        assert!(!dedup.is_duplicate(&OperationId::new("a".to_string(), 6)));
+    }
+}
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::causality::{
+        dot::Dot, dotted_version_vector::DottedVersionVector, version_vector::VersionVector,
+        CausalOrdering,
+    };
+
+    #[test]
+    fn test_dvv_update_allocates_dot_after_local_and_context() {
+        let mut local = VersionVector::<String>::new();
+        local.increment("server".to_string());
+        local.increment("server".to_string());
+        let context = VersionVector::<String>::new();
+
+        let dvv = DottedVersionVector::update(&context, &local, "server".to_string());
+        assert_eq!(dvv.dot, Dot::new("server".to_string(), 3));
+        assert!(dvv.contains(&Dot::new("server".to_string(), 3)));
+        assert!(!dvv.contains(&Dot::new("server".to_string(), 1)));
+    }
+
+    #[test]
+    fn test_dvv_concurrent_writes_with_same_context() {
+        let context = VersionVector::<String>::new();
+        let mut local = VersionVector::<String>::new();
+        let first = DottedVersionVector::update(&context, &local, "server".to_string());
+        local.observe(&first.dot);
+        let second = DottedVersionVector::update(&context, &local, "server".to_string());
+
+        assert_eq!(first.compare(&second), CausalOrdering::Concurrent);
+    }
+
+    #[test]
+    fn test_dvv_write_with_observed_context_descends() {
+        let context = VersionVector::<String>::new();
+        let local = VersionVector::<String>::new();
+        let first = DottedVersionVector::update(&context, &local, "a".to_string());
+        let second = DottedVersionVector::update(
+            &first.to_version_vector(),
+            &first.to_version_vector(),
+            "b".to_string(),
+        );
+
+        assert_eq!(second.compare(&first), CausalOrdering::After);
+        assert_eq!(first.compare(&second), CausalOrdering::Before);
+        assert_eq!(first.compare(&first.clone()), CausalOrdering::Equal);
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::causality::{
+        interval_tree_clock::{EventTree, IdTree, IntervalTreeClock},
+        CausalOrdering,
+    };
+
+    #[test]
+    fn test_itc_event_advances_clock() {
+        let mut clock = IntervalTreeClock::seed();
+        let before = clock.clone();
+        clock.event().unwrap();
+        assert_eq!(before.compare(&clock), CausalOrdering::Before);
+        assert_eq!(clock.compare(&before), CausalOrdering::After);
+    }
+
+    #[test]
+    fn test_itc_forked_events_are_concurrent() {
+        let (mut a, mut b) = IntervalTreeClock::seed().fork();
+        a.event().unwrap();
+        b.event().unwrap();
+        assert!(a.is_concurrent(&b));
+
+        let joined = a.join(&b);
+        assert!(a.leq(&joined) && b.leq(&joined));
+        assert_eq!(joined.id, IdTree::One);
+    }
+
+    #[test]
+    fn test_itc_receive_orders_later_events() {
+        let (mut a, mut b) = IntervalTreeClock::seed().fork();
+        a.event().unwrap();
+        b.receive(&a.peek());
+        b.event().unwrap();
+        assert_eq!(a.compare(&b), CausalOrdering::Before);
+    }
+
+    #[test]
+    fn test_itc_retired_replica_id_is_reused() {
+        let (a, b) = IntervalTreeClock::seed().fork();
+        let (mut b, mut c) = b.fork();
+        c.event().unwrap();
+        b.event().unwrap();
+
+        let mut a = a.join(&c);
+        a.event().unwrap();
+        assert!(c.leq(&a));
+        assert!(a.is_concurrent(&b));
+
+        let mut all = a.join(&b);
+        all.event().unwrap();
+        assert_eq!(all.id, IdTree::One);
+        assert!(matches!(all.event, EventTree::Leaf(_)));
+        assert!(b.leq(&all) && c.leq(&all));
+    }
+
+    #[test]
+    fn test_itc_anonymous_stamp_rejects_events() {
+        let clock = IntervalTreeClock::seed();
+        let mut anonymous = clock.peek();
+        assert!(anonymous.is_anonymous());
+        assert!(anonymous.event().is_err());
+        assert_eq!(anonymous.compare(&clock), CausalOrdering::Equal);
+    }
+
+    #[test]
+    fn test_itc_serde_round_trip() {
+        let (mut a, _) = IntervalTreeClock::seed().fork();
+        a.event().unwrap();
+        a.event().unwrap();
+        let json = serde_json::to_string(&a).unwrap();
+        let decoded: IntervalTreeClock = serde_json::from_str(&json).unwrap();
+        assert_eq!(a, decoded);
+    }
+}
@@ -0,0 +1,7 @@
+mod causal_context_test;
+mod causal_delivery_test;
+mod deduplication_test;
+mod dotted_version_vector_test;
+mod interval_tree_clock_test;
+mod stability_test;
+mod version_vector_test;
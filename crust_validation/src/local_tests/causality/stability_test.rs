@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        causality::{
+            deduplication::OperationDeduplicator,
+            dot::Dot,
+            stability::{CausalStability, StablePurge},
+            version_vector::VersionVector,
+        },
+        core::set::awset::AWSet,
+        dot_store::DotStore,
+        sync::StateBased,
+    };
+
+    fn version_vector(entries: &[(&str, u64)]) -> VersionVector<String> {
+        let mut version_vector = VersionVector::new();
+        for (replica, counter) in entries {
+            version_vector.observe(&Dot::new(replica.to_string(), *counter));
+        }
+        version_vector
+    }
+
+    #[test]
+    fn test_stable_version_vector_is_the_meet_of_all_replicas() {
+        let mut stability = CausalStability::new("a".to_string());
+        stability.observe("a".to_string(), &version_vector(&[("a", 3), ("b", 2)]));
+        stability.observe("b".to_string(), &version_vector(&[("a", 2), ("b", 4)]));
+        assert_eq!(stability.stable(), version_vector(&[("a", 2), ("b", 2)]));
+        assert!(stability.is_stable(&Dot::new("a".to_string(), 2)));
+        assert!(!stability.is_stable(&Dot::new("a".to_string(), 3)));
+    }
+
+    #[test]
+    fn test_silent_replicas_block_stability() {
+        let mut stability = CausalStability::new("a".to_string());
+        stability.observe("a".to_string(), &version_vector(&[("a", 3)]));
+        stability.add_replica("c".to_string());
+        assert!(stability.stable().is_empty());
+        assert!(!stability.is_stable(&Dot::new("a".to_string(), 1)));
+
+        stability.remove_replica(&"c".to_string());
+        stability.remove_replica(&"a".to_string());
+        assert_eq!(stability.stable(), version_vector(&[("a", 3)]));
+        assert_eq!(stability.replicas().count(), 1);
+    }
+
+    #[test]
+    fn test_observations_never_move_backwards() {
+        let mut stability = CausalStability::new("a".to_string());
+        stability.observe("b".to_string(), &version_vector(&[("a", 5)]));
+        stability.observe("b".to_string(), &version_vector(&[("a", 1)]));
+        assert_eq!(
+            stability.observed(&"b".to_string()),
+            Some(&version_vector(&[("a", 5)]))
+        );
+    }
+
+    #[test]
+    fn test_deduplicator_purges_stable_dots() {
+        let mut dedup = OperationDeduplicator::<String>::new();
+        dedup.record(Dot::new("a".to_string(), 2));
+        dedup.record(Dot::new("a".to_string(), 4));
+        assert_eq!(dedup.purge_stable(&version_vector(&[("a", 1)])), 1);
+        assert_eq!(dedup.pending_len(), 1);
+        assert_eq!(dedup.purge_stable(&version_vector(&[("a", 4)])), 1);
+        assert_eq!(dedup.pending_len(), 0);
+        assert!(dedup.is_duplicate(&Dot::new("a".to_string(), 3)));
+    }
+
+    #[test]
+    fn test_dot_stores_collapse_stable_concurrent_dots() {
+        let element = "x".to_string();
+        let mut a = AWSet::<String, String>::default();
+        let mut b = AWSet::<String, String>::default();
+        a.add("a".to_string(), element.clone());
+        b.add("b".to_string(), element.clone());
+        a.merge(&b);
+        b.merge(&a);
+        let mut remover = b.clone();
+        remover.remove(&element);
+
+        assert_eq!(a.purge_stable(&version_vector(&[("a", 1)])), 0);
+        assert_eq!(a.purge_stable(&version_vector(&[("a", 1), ("b", 1)])), 1);
+        assert_eq!(a.state.store.dots().len(), 1);
+        assert_eq!(a.purge_stable(&version_vector(&[("a", 1), ("b", 1)])), 0);
+
+        let mut unpurged = b.clone();
+        unpurged.merge(&a);
+        a.merge(&b);
+        assert_eq!(a.state, unpurged.state);
+        assert!(a.contains(&element));
+
+        a.merge(&remover);
+        assert!(!a.contains(&element));
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::causality::{dot::Dot, version_vector::VersionVector, CausalOrdering};
+
+    #[test]
+    fn test_version_vector_increment_returns_next_dot() {
+        let mut vv = VersionVector::<String>::new();
+        assert_eq!(vv.increment("a".to_string()), Dot::new("a".to_string(), 1));
+        assert_eq!(vv.increment("a".to_string()), Dot::new("a".to_string(), 2));
+        assert_eq!(vv.get(&"a".to_string()), 2);
+        assert_eq!(vv.get(&"b".to_string()), 0);
+    }
+
+    #[test]
+    fn test_version_vector_compare() {
+        let mut a = VersionVector::<String>::new();
+        let mut b = VersionVector::<String>::new();
+        assert_eq!(a.compare(&b), CausalOrdering::Equal);
+
+        a.increment("a".to_string());
+        assert_eq!(a.compare(&b), CausalOrdering::After);
+        assert_eq!(b.compare(&a), CausalOrdering::Before);
+        assert!(a > b);
+
+        b.increment("b".to_string());
+        assert_eq!(a.compare(&b), CausalOrdering::Concurrent);
+        assert!(a.is_concurrent(&b));
+        assert_eq!(a.partial_cmp(&b), None);
+    }
+
+    #[test]
+    fn test_version_vector_equality_ignores_zero_entries() {
+        let mut a = VersionVector::<String>::new();
+        a.clock.insert("a".to_string(), 0);
+        let b = VersionVector::<String>::new();
+        assert_eq!(a, b);
+        assert_eq!(a.compare(&b), CausalOrdering::Equal);
+
+        a.increment("a".to_string());
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_version_vector_merge_is_least_upper_bound() {
+        let mut a = VersionVector::<String>::new();
+        let mut b = VersionVector::<String>::new();
+        a.increment("a".to_string());
+        a.increment("a".to_string());
+        b.increment("a".to_string());
+        b.increment("b".to_string());
+
+        let mut ab = a.clone();
+        ab.merge(&b);
+        let mut ba = b.clone();
+        ba.merge(&a);
+
+        assert_eq!(ab, ba);
+        assert!(ab.dominates(&a) && ab.dominates(&b));
+        assert_eq!(ab.get(&"a".to_string()), 2);
+        assert_eq!(ab.get(&"b".to_string()), 1);
+        assert_eq!(a.meet(&b).get(&"a".to_string()), 1);
+        assert_eq!(a.meet(&b).get(&"b".to_string()), 0);
+    }
+
+    #[test]
+    fn test_version_vector_contains_and_serde_round_trip() {
+        let mut vv = VersionVector::<String>::new();
+        vv.observe(&Dot::new("a".to_string(), 3));
+        assert!(vv.contains(&Dot::new("a".to_string(), 2)));
+        assert!(!vv.contains(&Dot::new("a".to_string(), 4)));
+        assert_eq!(vv.dots().count(), 3);
+
+        let json = serde_json::to_string(&vv).unwrap();
+        let decoded: VersionVector<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(vv, decoded);
+    }
+}
@@ -0,0 +1,227 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        command::{CounterInnerCommand, CrdtInnerCommand, SetInnerCommand},
+        constraint::{check_rules, ConstraintRule, ConstraintView, RepairPolicy},
+        error::CrustError,
+        r#type::CrdtType,
+        sync::{SyncMode, SyncType},
+    };
+    use crust_network::{
+        message::NetworkMessage, object_store::ObjectStore, receiver::sync_config,
+    };
+    use serde_json::json;
+
+    fn increment(value: &str) -> CrdtInnerCommand<String> {
+        CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+            value: value.to_string(),
+        })
+    }
+
+    fn decrement(value: &str) -> CrdtInnerCommand<String> {
+        CrdtInnerCommand::Counter(CounterInnerCommand::Decrement {
+            value: value.to_string(),
+        })
+    }
+
+    fn counter(increments: &[&str]) -> CrdtType<String> {
+        let mut crdt = CrdtType::<String>::new("pncounter".to_string()).unwrap();
+        for value in increments {
+            crdt.apply_command(&increment(value)).unwrap();
+        }
+        crdt
+    }
+
+    fn bounded(policy: RepairPolicy) -> CrdtType<String> {
+        counter(&["a"])
+            .with_constraints(vec![ConstraintRule::RangeValue(0, 2)], policy)
+            .unwrap()
+    }
+
+    fn elements(values: &[&str]) -> Option<Vec<String>> {
+        Some(values.iter().map(|value| value.to_string()).collect())
+    }
+
+    #[test]
+    fn test_rules_against_views() {
+        let set = ConstraintView {
+            elements: elements(&["x", "y", "z"]),
+            ..ConstraintView::default()
+        };
+        assert!(ConstraintRule::MaxCardinality(3).check(&set).is_ok());
+        assert!(ConstraintRule::MaxCardinality(2).check(&set).is_err());
+        assert!(
+            ConstraintRule::AllowedValues(vec!["x".to_string(), "y".to_string()])
+                .check(&set)
+                .is_err()
+        );
+        assert!(!ConstraintRule::<String>::MaxValue(1).applies_to(&set));
+
+        let counter = ConstraintView::<String> {
+            value: Some(5),
+            ..ConstraintView::default()
+        };
+        assert!(check_rules(
+            &[ConstraintRule::MinValue(0), ConstraintRule::MaxValue(5)],
+            &counter
+        )
+        .is_ok());
+        assert!(check_rules(&[ConstraintRule::RangeValue(6, 9)], &counter).is_err());
+    }
+
+    #[test]
+    fn test_commands_are_checked_before_they_apply() {
+        let mut crdt = bounded(RepairPolicy::Reject);
+        crdt.apply_command(&increment("a")).unwrap();
+        assert!(matches!(
+            crdt.apply_command(&increment("a")),
+            Err(CrustError::ConstraintViolation(_))
+        ));
+        assert_eq!(crdt.get_state()["value"], "2");
+        assert_eq!(crdt.operation_history.len(), 2);
+
+        crdt.apply_command(&decrement("a")).unwrap();
+        crdt.apply_command(&decrement("a")).unwrap();
+        assert!(crdt.apply_command(&decrement("a")).is_err());
+        assert_eq!(crdt.get_state()["value"], "0");
+    }
+
+    #[test]
+    fn test_rules_must_fit_the_type() {
+        let mut crdt = counter(&[]);
+        assert!(matches!(
+            crdt.set_constraint_rules(vec![ConstraintRule::MaxCardinality(1)]),
+            Err(CrustError::ConstraintViolation(_))
+        ));
+        assert!(crdt.get_constraint_rules().is_empty());
+        crdt.set_constraint_rules(vec![ConstraintRule::MaxValue(1)])
+            .unwrap();
+        assert_eq!(crdt.get_constraint_rules(), &[ConstraintRule::MaxValue(1)]);
+    }
+
+    #[test]
+    fn test_replicated_updates_follow_the_repair_policy() {
+        let remote = counter(&["b", "b", "c"]);
+
+        let mut accept = bounded(RepairPolicy::Accept);
+        accept.merge(&remote).unwrap();
+        assert_eq!(accept.get_state()["value"], "4");
+
+        let mut reject = bounded(RepairPolicy::Reject);
+        assert!(matches!(
+            reject.merge(&remote),
+            Err(CrustError::ConstraintViolation(_))
+        ));
+        assert_eq!(reject.get_state()["value"], "1");
+        assert!(reject.check_constraints().is_ok());
+
+        let mut repair = bounded(RepairPolicy::Repair);
+        repair.assign_replica("a");
+        repair.merge(&remote).unwrap();
+        assert_eq!(repair.get_state()["value"], "2");
+        assert_eq!(repair.get_state()["state"]["decrements"], json!({"a": 2}));
+        assert!(repair.check_constraints().is_ok());
+    }
+
+    #[test]
+    fn test_failed_repair_keeps_merged_state() {
+        let mut crdt = CrdtType::<String>::new("gcounter".to_string())
+            .unwrap()
+            .with_constraints(vec![ConstraintRule::MaxValue(1)], RepairPolicy::Repair)
+            .unwrap();
+        let mut remote = CrdtType::<String>::new("gcounter".to_string()).unwrap();
+        remote.apply_command(&increment("b")).unwrap();
+        remote.apply_command(&increment("b")).unwrap();
+        crdt.merge(&remote).unwrap();
+        assert_eq!(crdt.get_state()["value"], "2");
+        assert!(crdt.check_constraints().is_err());
+    }
+
+    #[test]
+    fn test_set_rules_are_checked_and_repaired() {
+        let add = |value: &str| {
+            CrdtInnerCommand::Set(SetInnerCommand::Add {
+                value: value.to_string(),
+            })
+        };
+        let rules = vec![
+            ConstraintRule::MaxCardinality(2),
+            ConstraintRule::AllowedValues(vec!["x".to_string(), "y".to_string(), "z".to_string()]),
+        ];
+        let mut local = CrdtType::<String>::new("orset".to_string())
+            .unwrap()
+            .with_constraints(rules.clone(), RepairPolicy::Repair)
+            .unwrap();
+        local.assign_replica("a");
+        local.apply_command(&add("x")).unwrap();
+        assert!(local.apply_command(&add("w")).is_err());
+        local.apply_command(&add("y")).unwrap();
+        assert!(local.apply_command(&add("z")).is_err());
+
+        let mut remote = CrdtType::<String>::new("orset".to_string()).unwrap();
+        remote.assign_replica("b");
+        remote.apply_command(&add("w")).unwrap();
+        remote.apply_command(&add("z")).unwrap();
+        local.merge(&remote).unwrap();
+        assert_eq!(local.get_state()["value"], json!(["x", "y"]));
+        assert!(local.check_constraints().is_ok());
+
+        assert!(CrdtType::<String>::new("mvmap".to_string())
+            .unwrap()
+            .with_constraints(rules, RepairPolicy::Reject)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_only_the_lowest_replica_repairs_concurrent_violations() {
+        let stores: Vec<ObjectStore<String>> = ["replica-a", "replica-b"]
+            .into_iter()
+            .map(|replica| ObjectStore::new(replica.to_string()))
+            .collect();
+        let mut states = Vec::new();
+        for (store, peer) in stores.iter().zip(["replica-b", "replica-a"]) {
+            store.declare("likes", "pncounter").unwrap();
+            let mut config = sync_config(SyncType::State, SyncMode::Immediate);
+            let state = store
+                .with_object("likes", |object| {
+                    object.add_stability_replicas(&[peer.to_string()]);
+                    object.set_constraints(
+                        vec![ConstraintRule::RangeValue(0, 2)],
+                        RepairPolicy::Repair,
+                    )?;
+                    object.apply_command(&increment(store.replica()), &mut config)?;
+                    object.apply_command(&increment(store.replica()), &mut config)
+                })
+                .unwrap()
+                .unwrap();
+            states.push(state);
+        }
+        let value = |store: &ObjectStore<String>| {
+            store
+                .read_object("likes", |object| Ok(object.get_state()["value"].clone()))
+                .unwrap()
+        };
+        let receive = |store: &ObjectStore<String>, message| {
+            store
+                .with_object("likes", |object| object.receive(message))
+                .unwrap()
+        };
+
+        receive(&stores[0], &states[1]);
+        receive(&stores[1], &states[0]);
+        assert_eq!(value(&stores[0]), "2");
+        assert_eq!(value(&stores[1]), "4");
+
+        let repaired = stores[0]
+            .read_object("likes", |object| {
+                Ok(NetworkMessage::State {
+                    payload: object.crdt().clone(),
+                    sender_pod_name: object.replica().clone(),
+                })
+            })
+            .unwrap();
+        receive(&stores[1], &repaired);
+        assert_eq!(value(&stores[0]), "2");
+        assert_eq!(value(&stores[1]), "2");
+    }
+}
@@ -0,0 +1 @@
+mod constraint_rule_test;
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        causality::{dot::Dot, version_vector::VersionVector},
+        core::counter::gcounter::{GCounter, GCounterDelta},
+        registry::{to_value, Retirable},
+        sync::{Crdt, DeltaBased, StateBased},
+    };
+
+    fn counter(entries: &[(&str, u64)]) -> GCounter<String> {
+        let mut counter = GCounter::<String>::new();
+        for (key, times) in entries {
+            for _ in 0..*times {
+                counter.increment(key.to_string());
+            }
+        }
+        counter
+    }
+
+    #[test]
+    fn test_retiring_keeps_the_value() {
+        let mut a = counter(&[("a", 1), ("gone", 3)]);
+        assert!(a.retire(&"gone".to_string()));
+        assert!(!a.retire(&"missing".to_string()));
+        assert_eq!(a.value(), 4);
+        assert!(a.fold_retired());
+        assert_eq!(a.value(), 4);
+        assert_eq!(a.counter.len(), 1);
+        assert!(a.retirement.retired.is_empty());
+        assert_eq!(a.retirement.base, 3);
+        assert!(!a.fold_retired());
+    }
+
+    #[test]
+    fn test_lagging_states_are_not_counted_twice() {
+        let mut a = counter(&[("a", 1), ("b", 1), ("gone", 3)]);
+        let mut b = a.clone();
+        a.retire(&"gone".to_string());
+        b.retire(&"gone".to_string());
+        let lagging = b.clone();
+        a.fold_retired();
+
+        StateBased::merge(&mut a, &lagging);
+        assert_eq!(a.value(), 5);
+        assert!(a.retirement.retired.is_empty());
+
+        b.increment("b".to_string());
+        StateBased::merge(&mut b, &a);
+        assert_eq!(b.value(), 6);
+        assert_eq!(b.retirement.epoch, 1);
+        assert!(!b.counter.contains_key("gone"));
+
+        StateBased::merge(&mut a, &b);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_unseen_contributions_survive_a_fold() {
+        let mut a = counter(&[("gone", 3)]);
+        let mut late = counter(&[("gone", 4)]);
+        a.retire(&"gone".to_string());
+        a.fold_retired();
+
+        StateBased::merge(&mut a, &late);
+        assert_eq!(a.value(), 4);
+        StateBased::merge(&mut late, &a);
+        assert_eq!(late.value(), 4);
+        assert_eq!(late.counter.get("gone"), Some(&1));
+    }
+
+    #[test]
+    fn test_deltas_update_retired_entries() {
+        let mut a = counter(&[("gone", 1)]);
+        a.retire(&"gone".to_string());
+        DeltaBased::merge_delta(
+            &mut a,
+            &GCounterDelta {
+                increment_map: [("gone".to_string(), 2)].into(),
+            },
+        );
+        assert_eq!(a.retirement.retired.get("gone"), Some(&2));
+        assert!(a.counter.is_empty());
+        assert_eq!(a.value(), 2);
+        assert!(DeltaBased::generate_delta(&a).increment_map.is_empty());
+    }
+
+    #[test]
+    fn test_fold_requires_every_peer_to_agree() {
+        let mut a = counter(&[("a", 1), ("gone", 2)]);
+        let mut b = a.clone();
+        let departed = ["gone".to_string()];
+        let mut stable = VersionVector::new();
+        stable.observe(&Dot::new("gone".to_string(), 1));
+        assert!(Retirable::retire_departed(&mut a, &departed, &stable).is_empty());
+        assert!(a.retirement.retired.is_empty());
+        stable.observe(&Dot::new("gone".to_string(), 2));
+        assert_eq!(
+            Retirable::retire_departed(&mut a, &departed, &stable),
+            departed
+        );
+        let report = to_value(&b.retirement).unwrap();
+        assert!(!Retirable::fold_retired(&mut a, &[report]).unwrap());
+
+        b.retire(&"gone".to_string());
+        let report = Retirable::retirement(&b).unwrap();
+        assert!(Retirable::fold_retired(&mut a, &[report]).unwrap());
+        assert_eq!(a.retirement.base, 2);
+        assert_eq!(a.value(), 3);
+    }
+
+    #[test]
+    fn test_client_keys_are_never_retired() {
+        let mut a = counter(&[("a", 1), ("clicks", 2)]);
+        assert_eq!(Retirable::contributions(&a).get(&"clicks".to_string()), 2);
+        let stable = Retirable::contributions(&a);
+        assert_eq!(
+            Retirable::retire_departed(&mut a, &["gone".to_string()], &stable),
+            vec!["gone".to_string()]
+        );
+        assert!(a.retirement.retired.is_empty());
+        assert_eq!(a.counter.len(), 2);
+    }
+}
@@ -62,8 +62,10 @@ mod tests {
             let op2 = CounterOperation::Increment {
                 value: "b".to_string(),
             };
-            a.apply(&op1).apply(&op2);
-            b.apply(&op2).apply(&op1);
+            a.apply(&op1);
+            a.apply(&op2);
+            b.apply(&op2);
+            b.apply(&op1);
             a == b
         }
 
@@ -1 +1,2 @@
 mod gcounter_test;
+mod gcounter_retirement_test;
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        command::{CounterInnerCommand, CrdtInnerCommand, SetInnerCommand},
+        error::CrustError,
+        r#type::CrdtType,
+        sync::{SyncMode, SyncType},
+    };
+
+    #[test]
+    fn test_unknown_names_are_reported() {
+        assert_eq!(
+            CrdtType::<String>::new("none".to_string()).err(),
+            Some(CrustError::UnknownCrdtType("none".to_string()))
+        );
+        assert_eq!(
+            SyncType::new("gossip".to_string()).err(),
+            Some(CrustError::UnknownSyncType("gossip".to_string()))
+        );
+        assert_eq!(
+            SyncMode::new("eventually".to_string()).err(),
+            Some(CrustError::UnknownSyncMode("eventually".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_apply_command_rejects_invalid_commands() {
+        let mut crdt = CrdtType::<String>::new("gcounter".to_string()).unwrap();
+        let error = crdt
+            .apply_command(&CrdtInnerCommand::Set(SetInnerCommand::Add {
+                value: "a".to_string(),
+            }))
+            .unwrap_err();
+        assert_eq!(error.kind(), "invalid_command");
+
+        let error = crdt
+            .apply_command(&CrdtInnerCommand::Counter(CounterInnerCommand::Decrement {
+                value: "a".to_string(),
+            }))
+            .unwrap_err();
+        assert!(matches!(error, CrustError::InvalidCommand { .. }));
+    }
+
+    #[test]
+    fn test_apply_command_returns_operation() {
+        let mut crdt = CrdtType::<String>::new("gcounter".to_string()).unwrap();
+        assert!(crdt
+            .apply_command(&CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+                value: "a".to_string(),
+            }))
+            .is_ok());
+        assert_eq!(crdt.get_state()["value"], "1");
+    }
+}
@@ -0,0 +1 @@
+mod crust_error_test;
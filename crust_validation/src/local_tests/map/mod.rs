@@ -0,0 +1 @@
+mod ormap_test;
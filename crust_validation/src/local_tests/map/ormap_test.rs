@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        core::{
+            map::ormap::ORMap,
+            register::mvregister::{write_delta, MVRegisterStore},
+            set::awset::{add_delta, AWSetStore},
+        },
+        sync::{Crdt, DeltaBased, StateBased},
+    };
+
+    type SetMap = ORMap<String, AWSetStore<String, String>, String>;
+    type RegisterMap = ORMap<String, MVRegisterStore<String, u64>, String>;
+
+    #[test]
+    fn test_ormap_nested_awset_updates() {
+        let mut a = SetMap::new();
+        a.apply("k".to_string(), |set| {
+            add_delta(set, "a".to_string(), "x".to_string())
+        });
+        let mut b = SetMap::new();
+        b.apply("k".to_string(), |set| {
+            add_delta(set, "b".to_string(), "y".to_string())
+        });
+        a.merge(&b);
+        let set = a.get(&"k".to_string()).unwrap();
+        assert_eq!(set.entries.len(), 2);
+    }
+
+    #[test]
+    fn test_ormap_remove_drops_observed_entry() {
+        let mut a = RegisterMap::new();
+        a.apply("k".to_string(), |register| {
+            write_delta(register, "a".to_string(), 1)
+        });
+        let mut b = a.clone();
+        b.remove(&"k".to_string());
+        a.merge(&b);
+        assert!(a.get(&"k".to_string()).is_none());
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn test_ormap_concurrent_update_survives_remove() {
+        let mut a = RegisterMap::new();
+        a.apply("k".to_string(), |register| {
+            write_delta(register, "a".to_string(), 1)
+        });
+        let mut b = a.clone();
+        a.remove(&"k".to_string());
+        let delta = b.apply("k".to_string(), |register| {
+            write_delta(register, "b".to_string(), 2)
+        });
+        a.merge_delta(&delta);
+        let register = a.get(&"k".to_string()).unwrap();
+        assert_eq!(
+            register.values.values().copied().collect::<Vec<_>>(),
+            vec![2]
+        );
+    }
+
+    #[test]
+    fn test_ormap_serde_round_trip() {
+        let mut a = RegisterMap::new();
+        a.apply("k".to_string(), |register| {
+            write_delta(register, "a".to_string(), 7)
+        });
+        let json = serde_json::to_string(&a).unwrap();
+        let decoded: RegisterMap = serde_json::from_str(&json).unwrap();
+        assert_eq!(a, decoded);
+    }
+}
@@ -1 +1,12 @@
+mod anti_entropy;
+mod causality;
+mod constraint;
 mod counter;
+mod error;
+mod map;
+mod network;
+mod register;
+mod registry;
+mod reversible;
+mod set;
+mod storage;
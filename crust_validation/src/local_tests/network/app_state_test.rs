@@ -0,0 +1,314 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        command::{CounterInnerCommand, CrdtInnerCommand},
+        registry::CrdtKey,
+        sync::{SyncMode, SyncType},
+    };
+    use crust_network::{
+        message::NetworkMessage, object_store::ObjectStore, receiver::sync_config,
+        receiver::AppState, serve,
+    };
+    use reqwest::{Client, StatusCode};
+    use serde_json::{json, Value};
+    use tokio::net::TcpListener;
+
+    async fn spawn_node<K>(replica: &str) -> String
+    where
+        K: CrdtKey,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(serve(
+            listener,
+            AppState::<K>::with_peers(replica.to_string(), Vec::new()).unwrap(),
+        ));
+        format!("http://{address}")
+    }
+
+    fn increment(value: &str) -> CrdtInnerCommand<String> {
+        CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+            value: value.to_string(),
+        })
+    }
+
+    async fn counter_value(client: &Client, url: String) -> String {
+        let body: Value = client.get(url).send().await.unwrap().json().await.unwrap();
+        body["state"]["value"].as_str().unwrap().to_string()
+    }
+
+    fn remote_message(remote: &ObjectStore<String>, sync_type: SyncType) -> NetworkMessage<String> {
+        let mut config = sync_config(sync_type, SyncMode::Immediate);
+        remote
+            .with_object("gcounter", |object| {
+                object.apply_command(&increment("replica-2"), &mut config)
+            })
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_commands_are_visible_through_state() {
+        let node = spawn_node::<String>("replica-1").await;
+        let client = Client::new();
+        let response = client
+            .get(format!("{node}/state/gcounter"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        for sync_type in ["state", "delta", "operation"] {
+            let response = client
+                .post(format!(
+                    "{node}/receive/internal/gcounter/{sync_type}/immediate"
+                ))
+                .json(&increment("replica-1"))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+        assert_eq!(
+            counter_value(&client, format!("{node}/state/gcounter")).await,
+            "3"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_incoming_merges_are_visible_through_state() {
+        let node = spawn_node::<String>("replica-1").await;
+        let client = Client::new();
+        let remote = ObjectStore::<String>::new("replica-2".to_string());
+        remote.declare("gcounter", "gcounter").unwrap();
+        for sync_type in [SyncType::State, SyncType::Delta, SyncType::Operation] {
+            let response = client
+                .post(format!("{node}/receive/gcounter"))
+                .json(&remote_message(&remote, sync_type))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+        assert_eq!(
+            counter_value(&client, format!("{node}/state/gcounter")).await,
+            "3"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_named_objects_over_http() {
+        let node = spawn_node::<String>("replica-1").await;
+        let client = Client::new();
+
+        let response = client
+            .put(format!("{node}/objects/likes"))
+            .json(&json!({"crdt_type": "gcounter"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let response = client
+            .post(format!("{node}/objects/likes/command/delta/immediate"))
+            .json(&increment("replica-1"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            counter_value(&client, format!("{node}/objects/likes")).await,
+            "1"
+        );
+
+        let response = client
+            .get(format!("{node}/objects/views"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["kind"], "object_not_found");
+
+        let response = client
+            .post(format!("{node}/objects/likes/command/gossip/immediate"))
+            .json(&increment("replica-1"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_integer_keyed_node() {
+        let node = spawn_node::<u64>("replica-1").await;
+        let client = Client::new();
+        for value in [7u64, 7, 9] {
+            let response = client
+                .post(format!(
+                    "{node}/receive/internal/gcounter/operation/immediate"
+                ))
+                .json(&CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+                    value,
+                }))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let remote = ObjectStore::<u64>::new("replica-2".to_string());
+        remote.declare("gcounter", "gcounter").unwrap();
+        let mut config = sync_config(SyncType::State, SyncMode::Immediate);
+        let message = remote
+            .with_object("gcounter", |object| {
+                object.apply_command(
+                    &CrdtInnerCommand::Counter(CounterInnerCommand::Increment { value: 11 }),
+                    &mut config,
+                )
+            })
+            .unwrap()
+            .unwrap();
+        let response = client
+            .post(format!("{node}/receive/gcounter"))
+            .json(&message)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body: Value = client
+            .get(format!("{node}/state/gcounter"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["state"]["value"], "4");
+        assert_eq!(body["state"]["state"], json!({"7": 2, "9": 1, "11": 1}));
+
+        let response = client
+            .post(format!("{node}/receive/internal/gcounter/state/immediate"))
+            .json(&increment("not-a-number"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_client_error());
+    }
+
+    #[tokio::test]
+    async fn test_undo_and_redo_over_http() {
+        let node = spawn_node::<String>("replica-1").await;
+        let client = Client::new();
+        for (name, crdt_type) in [("likes", "pncounter"), ("views", "gcounter")] {
+            client
+                .put(format!("{node}/objects/{name}"))
+                .json(&json!({ "crdt_type": crdt_type }))
+                .send()
+                .await
+                .unwrap();
+            client
+                .post(format!("{node}/objects/{name}/command/operation/immediate"))
+                .json(&increment("replica-1"))
+                .send()
+                .await
+                .unwrap();
+        }
+
+        let response = client
+            .post(format!("{node}/objects/likes/undo/operation/immediate"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            counter_value(&client, format!("{node}/objects/likes")).await,
+            "0"
+        );
+
+        let response = client
+            .post(format!("{node}/objects/likes/redo/state/immediate"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            counter_value(&client, format!("{node}/objects/likes")).await,
+            "1"
+        );
+
+        let response = client
+            .post(format!("{node}/objects/likes/redo/state/immediate"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let response = client
+            .post(format!("{node}/objects/views/undo/operation/immediate"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["kind"], "not_reversible");
+
+        let response = client
+            .post(format!("{node}/objects/missing/undo/operation/immediate"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_object_constraints_over_http() {
+        let node = spawn_node::<String>("replica-1").await;
+        let client = Client::new();
+        client
+            .put(format!("{node}/objects/seats"))
+            .json(&json!({"crdt_type": "pncounter"}))
+            .send()
+            .await
+            .unwrap();
+
+        let response = client
+            .put(format!("{node}/objects/seats/constraints"))
+            .json(&json!({"rules": [{"MaxCardinality": 2}]}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let response = client
+            .put(format!("{node}/objects/seats/constraints"))
+            .json(&json!({"rules": [{"MaxValue": 1}], "repair_policy": "Reject"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let statuses = [StatusCode::OK, StatusCode::UNPROCESSABLE_ENTITY];
+        for status in statuses {
+            let response = client
+                .post(format!("{node}/objects/seats/command/state/immediate"))
+                .json(&increment("replica-1"))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), status);
+        }
+        let response = client
+            .post(format!("{node}/objects/seats/command/state/immediate"))
+            .json(&increment("replica-1"))
+            .send()
+            .await
+            .unwrap();
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["kind"], "constraint_violation");
+        assert_eq!(
+            counter_value(&client, format!("{node}/objects/seats")).await,
+            "1"
+        );
+    }
+}
@@ -0,0 +1,248 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crust_core::{
+        command::{CounterInnerCommand, CrdtInnerCommand},
+        error::CrustError,
+        r#type::CrdtType,
+    };
+    use crust_network::{
+        compression::{Compression, CompressionConfig, CompressionMetrics, MAX_DECOMPRESSED_BYTES},
+        encoding::Encoding,
+        message::NetworkMessage,
+        receiver::AppState,
+        sender::NetworkSender,
+        serve,
+    };
+    use reqwest::{
+        header::{CONTENT_ENCODING, CONTENT_TYPE},
+        Client, StatusCode,
+    };
+    use serde_json::Value;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    fn state_message(replicas: usize) -> NetworkMessage<String> {
+        let mut crdt = CrdtType::<String>::new("gcounter".to_string()).unwrap();
+        for replica in 0..replicas {
+            crdt.apply_command(&CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+                value: format!("replica-{replica}"),
+            }))
+            .unwrap();
+        }
+        NetworkMessage::State {
+            payload: crdt,
+            sender_pod_name: "replica-1".to_string(),
+        }
+    }
+
+    async fn spawn_node(compression: CompressionConfig) -> (String, AppState<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node = format!("http://{}", listener.local_addr().unwrap());
+        let state = AppState::<String>::with_peers("replica-2".to_string(), Vec::new())
+            .unwrap()
+            .with_compression(compression);
+        tokio::spawn(serve(listener, state.clone()));
+        (node, state)
+    }
+
+    async fn counter_value(client: &Client, node: &str) -> String {
+        let body: Value = client
+            .get(format!("{node}/state/gcounter"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        body["state"]["value"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_compression_round_trips() {
+        let bytes = Encoding::Json.encode(&state_message(100)).unwrap();
+        for compression in [Compression::None, Compression::Zstd, Compression::Lz4] {
+            let compressed = compression.compress(&bytes).unwrap();
+            assert_eq!(compression.decompress(&compressed).unwrap(), bytes);
+            if compression != Compression::None {
+                assert!(compressed.len() < bytes.len());
+            }
+        }
+        assert!(matches!(
+            Compression::new("br"),
+            Err(CrustError::UnsupportedEncoding(_))
+        ));
+    }
+
+    #[test]
+    fn test_decompression_is_bounded() {
+        let mut bomb = ((MAX_DECOMPRESSED_BYTES + 1) as u32).to_le_bytes().to_vec();
+        bomb.extend_from_slice(&[0; 16]);
+        assert!(matches!(
+            Compression::Lz4.decompress(&bomb),
+            Err(CrustError::Serialization(_))
+        ));
+
+        let bytes = vec![0; MAX_DECOMPRESSED_BYTES + 1];
+        for compression in [Compression::Zstd, Compression::Lz4] {
+            let compressed = compression.compress(&bytes).unwrap();
+            assert!(compressed.len() < 1024 * 1024);
+            assert!(matches!(
+                compression.decompress(&compressed),
+                Err(CrustError::Serialization(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_small_payloads_are_sent_uncompressed() {
+        let bytes = Encoding::Json.encode(&state_message(100)).unwrap();
+        let config = CompressionConfig::new(Compression::Zstd, bytes.len() + 1);
+        let (compression, sent) = config.apply(bytes.clone()).unwrap();
+        assert_eq!(compression, Compression::None);
+        assert_eq!(sent, bytes);
+
+        let config = CompressionConfig::new(Compression::Zstd, 16);
+        let (compression, sent) = config.apply(bytes.clone()).unwrap();
+        assert_eq!(compression, Compression::Zstd);
+        assert!(sent.len() < bytes.len());
+
+        let (compression, _) = CompressionConfig::default().apply(bytes).unwrap();
+        assert_eq!(compression, Compression::None);
+    }
+
+    #[test]
+    fn test_metrics_track_the_compression_ratio() {
+        let metrics = CompressionMetrics::default();
+        assert_eq!(metrics.ratio(), 1.0);
+        metrics.record(Compression::Lz4, 1000, 250);
+        metrics.record(Compression::None, 100, 100);
+        assert_eq!(metrics.messages(), 2);
+        assert_eq!(metrics.compressed_messages(), 1);
+        assert_eq!(metrics.ratio(), 350.0 / 1100.0);
+    }
+
+    #[tokio::test]
+    async fn test_receive_decompresses_bodies() {
+        let (node, _) = spawn_node(CompressionConfig::default()).await;
+        let client = Client::new();
+        let body = Encoding::Cbor.encode(&state_message(50)).unwrap();
+
+        let response = client
+            .post(format!("{node}/receive/gcounter"))
+            .header(CONTENT_TYPE, Encoding::Cbor.content_type())
+            .header(CONTENT_ENCODING, "lz4")
+            .body(Compression::Lz4.compress(&body).unwrap())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(counter_value(&client, &node).await, "50");
+
+        let response = client
+            .post(format!("{node}/receive/gcounter"))
+            .header(CONTENT_ENCODING, "br")
+            .body(body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[tokio::test]
+    async fn test_sender_compresses_large_messages() {
+        let (node, state) = spawn_node(CompressionConfig::new(Compression::Zstd, 64)).await;
+        let metrics = Arc::new(CompressionMetrics::default());
+        let sender = NetworkSender::new("replica-1".to_string(), "none".to_string(), vec![])
+            .with_compression(
+                CompressionConfig::new(Compression::Zstd, 64),
+                metrics.clone(),
+            );
+        let response = sender
+            .send_message(format!("{node}/receive/gcounter"), &state_message(100))
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(metrics.compressed_messages(), 1);
+        assert!(metrics.ratio() < 1.0);
+
+        let client = Client::new();
+        assert_eq!(counter_value(&client, &node).await, "100");
+
+        let sender = state.sender(vec![]);
+        sender
+            .send_message(format!("{node}/receive/gcounter"), &state_message(10))
+            .await;
+        let body: Value = client
+            .get(format!("{node}/metrics/compression"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["messages"], 1);
+        assert_eq!(body["compressed_messages"], 1);
+        assert!(body["ratio"].as_f64().unwrap() < 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_to_json_is_recorded_once() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 4096];
+                let (header_end, content_length) = loop {
+                    let read = stream.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                    if let Some(end) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+                        let headers = String::from_utf8_lossy(&request[..end]).to_lowercase();
+                        let content_length = headers
+                            .lines()
+                            .find_map(|line| line.strip_prefix("content-length:"))
+                            .map_or(0, |length| length.trim().parse().unwrap());
+                        break (end + 4, content_length);
+                    }
+                };
+                while request.len() < header_end + content_length {
+                    let read = stream.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                }
+                let headers = String::from_utf8_lossy(&request[..header_end]).to_lowercase();
+                let status = if headers.contains("content-encoding:") {
+                    "415 Unsupported Media Type"
+                } else {
+                    "200 OK"
+                };
+                stream
+                    .write_all(
+                        format!(
+                            "HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                        )
+                        .as_bytes(),
+                    )
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let metrics = Arc::new(CompressionMetrics::default());
+        let sender = NetworkSender::new("replica-1".to_string(), "none".to_string(), vec![])
+            .with_compression(
+                CompressionConfig::new(Compression::Zstd, 64),
+                metrics.clone(),
+            );
+        let response = sender
+            .send_message(format!("{node}/receive/gcounter"), &state_message(100))
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(metrics.messages(), 1);
+        assert_eq!(metrics.compressed_messages(), 0);
+        assert_eq!(metrics.ratio(), 1.0);
+    }
+}
@@ -0,0 +1,136 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use crust_config::{convergence::wait_for_convergence, instance::get_digest_from_instance};
+    use crust_core::{
+        command::{CounterInnerCommand, CrdtInnerCommand, SetInnerCommand},
+        digest::canonical_digest,
+        sync::{SyncMode, SyncType},
+    };
+    use crust_network::{
+        object_store::ObjectStore,
+        receiver::{sync_config, AppState},
+        serve,
+    };
+    use reqwest::Client;
+    use serde_json::json;
+    use tokio::net::TcpListener;
+
+    async fn spawn_node(replica: &str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(serve(
+            listener,
+            AppState::<String>::with_peers(replica.to_string(), Vec::new()).unwrap(),
+        ));
+        format!("http://{address}")
+    }
+
+    async fn increment(client: &Client, node: &str, value: &str) {
+        client
+            .post(format!("{node}/objects/likes/command/state/immediate"))
+            .json(&CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+                value: value.to_string(),
+            }))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn test_canonical_digest_ignores_key_order() {
+        let a = json!({"value": "2", "state": {"a": 1, "b": 1}});
+        let b = json!({"state": {"b": 1, "a": 1}, "value": "2"});
+        assert_eq!(canonical_digest(&a), canonical_digest(&b));
+        assert_ne!(
+            canonical_digest(&a),
+            canonical_digest(&json!({"value": "3", "state": {"a": 2, "b": 1}}))
+        );
+        assert_ne!(
+            canonical_digest(&json!(["a", "b"])),
+            canonical_digest(&json!(["b", "a"]))
+        );
+    }
+
+    #[test]
+    fn test_digests_carry_the_object_version_vector() {
+        let store = ObjectStore::<String>::new("replica-a".to_string());
+        store.declare("tags", "orset").unwrap();
+        let mut config = sync_config(SyncType::State, SyncMode::Immediate);
+        store
+            .with_object("tags", |object| {
+                object.apply_command(
+                    &CrdtInnerCommand::Set(SetInnerCommand::Add {
+                        value: "a".to_string(),
+                    }),
+                    &mut config,
+                )
+            })
+            .unwrap();
+        let digest = store
+            .read_object("tags", |object| Ok(object.digest()))
+            .unwrap();
+        assert_eq!(digest.version_vector.get(&"replica-a".to_string()), 1);
+    }
+
+    #[tokio::test]
+    async fn test_digests_report_convergence_across_nodes() {
+        let client = Client::new();
+        let mut nodes = HashMap::new();
+        for replica in ["replica-1", "replica-2"] {
+            let node = spawn_node(replica).await;
+            client
+                .put(format!("{node}/objects/likes"))
+                .json(&json!({"crdt_type": "gcounter"}))
+                .send()
+                .await
+                .unwrap();
+            nodes.insert(replica.to_string(), node);
+        }
+
+        increment(&client, &nodes["replica-1"], "replica-1").await;
+        let report = wait_for_convergence(
+            "likes",
+            &nodes,
+            Duration::from_secs(1),
+            Duration::from_millis(100),
+        )
+        .await;
+        assert!(!report.converged);
+        assert!(report.attempts > 1);
+        assert_eq!(report.distinct_digests(), 2);
+        assert_eq!(report.lagging_instances().len(), 1);
+
+        increment(&client, &nodes["replica-2"], "replica-1").await;
+        let report = wait_for_convergence(
+            "likes",
+            &nodes,
+            Duration::from_secs(2),
+            Duration::from_millis(50),
+        )
+        .await;
+        assert!(report.converged);
+        assert_eq!(report.attempts, 1);
+        assert!(report.lagging_instances().is_empty());
+        let digest = &report.digests["replica-1"];
+        assert_eq!(digest.replica, "replica-1");
+        assert_eq!(digest.crdt_type, "gcounter");
+        assert_eq!(digest.digest, report.digests["replica-2"].digest);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_objects_prevent_convergence() {
+        let node = spawn_node("replica-1").await;
+        assert!(get_digest_from_instance("missing", &node).await.is_err());
+
+        let nodes = HashMap::from([("replica-1".to_string(), node)]);
+        let report =
+            wait_for_convergence("missing", &nodes, Duration::ZERO, Duration::from_millis(10))
+                .await;
+        assert!(!report.converged);
+        assert_eq!(report.attempts, 1);
+        assert!(report.errors.contains_key("replica-1"));
+        assert_eq!(report.lagging_instances(), vec!["replica-1".to_string()]);
+    }
+}
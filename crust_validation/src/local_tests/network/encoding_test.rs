@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        anti_entropy::delta_interval::DeltaIntervalMessage,
+        command::{CounterInnerCommand, CrdtInnerCommand},
+        delta::CrdtDelta,
+        error::CrustError,
+        r#type::CrdtType,
+        sync::{SyncMode, SyncType},
+    };
+    use crust_network::{
+        encoding::Encoding,
+        message::NetworkMessage,
+        object_store::ObjectStore,
+        receiver::{sync_config, AppState},
+        serve,
+    };
+    use reqwest::{header::CONTENT_TYPE, Client, StatusCode};
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::Value;
+    use tokio::net::TcpListener;
+
+    fn increment(value: &str) -> CrdtInnerCommand<String> {
+        CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+            value: value.to_string(),
+        })
+    }
+
+    fn counter(replicas: usize) -> CrdtType<String> {
+        let mut crdt = CrdtType::<String>::new("gcounter".to_string()).unwrap();
+        for replica in 0..replicas {
+            crdt.apply_command(&increment(&format!("replica-{replica}")))
+                .unwrap();
+        }
+        crdt
+    }
+
+    fn messages() -> Vec<NetworkMessage<String>> {
+        let store = ObjectStore::<String>::new("replica-1".to_string());
+        store.declare("likes", "gcounter").unwrap();
+        let mut messages: Vec<NetworkMessage<String>> =
+            [SyncType::Operation, SyncType::Delta, SyncType::State]
+                .into_iter()
+                .map(|sync_type| {
+                    let mut config = sync_config(sync_type, SyncMode::Immediate);
+                    store
+                        .with_object("likes", |object| {
+                            object.apply_command(&increment("replica-1"), &mut config)
+                        })
+                        .unwrap()
+                        .unwrap()
+                })
+                .collect();
+        messages.push(
+            store
+                .with_object("likes", |object| Ok(object.prepare_stability()))
+                .unwrap(),
+        );
+        messages.push(NetworkMessage::DeltaInterval {
+            payload: DeltaIntervalMessage::Ack { sequence: 4 },
+            sender_pod_name: "replica-1".to_string(),
+        });
+        messages
+    }
+
+    fn round_trip<T>(encoding: Encoding, value: &T) -> Value
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let decoded: T = encoding.decode(&encoding.encode(value).unwrap()).unwrap();
+        serde_json::to_value(decoded).unwrap()
+    }
+
+    #[test]
+    fn test_messages_round_trip_in_every_encoding() {
+        for encoding in [Encoding::Json, Encoding::Cbor] {
+            for message in messages() {
+                assert_eq!(
+                    round_trip(encoding, &message),
+                    serde_json::to_value(&message).unwrap()
+                );
+            }
+            let crdt = counter(3);
+            assert_eq!(
+                round_trip(encoding, &crdt),
+                serde_json::to_value(&crdt).unwrap()
+            );
+            let delta: CrdtDelta = crdt.generate_delta().unwrap();
+            assert_eq!(
+                round_trip(encoding, &delta),
+                serde_json::to_value(&delta).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_cbor_is_smaller_than_json_for_large_states() {
+        let message = NetworkMessage::State {
+            payload: counter(200),
+            sender_pod_name: "replica-1".to_string(),
+        };
+        let json = Encoding::Json.encode(&message).unwrap();
+        let cbor = Encoding::Cbor.encode(&message).unwrap();
+        assert!(cbor.len() < json.len());
+    }
+
+    #[test]
+    fn test_encodings_follow_content_types() {
+        assert_eq!(
+            Encoding::from_content_type("application/cbor").unwrap(),
+            Encoding::Cbor
+        );
+        assert_eq!(
+            Encoding::from_content_type("application/json; charset=utf-8").unwrap(),
+            Encoding::Json
+        );
+        assert!(matches!(
+            Encoding::from_content_type("text/plain"),
+            Err(CrustError::UnsupportedEncoding(_))
+        ));
+        assert!(matches!(
+            Encoding::new("yaml"),
+            Err(CrustError::UnsupportedEncoding(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_receive_accepts_cbor_bodies() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(serve(
+            listener,
+            AppState::<String>::with_peers("replica-2".to_string(), Vec::new()).unwrap(),
+        ));
+        let client = Client::new();
+        let message = NetworkMessage::State {
+            payload: counter(2),
+            sender_pod_name: "replica-1".to_string(),
+        };
+
+        let response = client
+            .post(format!("{node}/receive/gcounter"))
+            .header(CONTENT_TYPE, Encoding::Cbor.content_type())
+            .body(Encoding::Cbor.encode(&message).unwrap())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = client
+            .post(format!("{node}/receive/gcounter"))
+            .header(CONTENT_TYPE, "text/plain")
+            .body("hello")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let state: Value = client
+            .get(format!("{node}/state/gcounter"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(state["state"]["value"], "2");
+    }
+}
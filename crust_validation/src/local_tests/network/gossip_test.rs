@@ -0,0 +1,217 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, thread::sleep, time::Duration};
+
+    use crust_core::{
+        command::{CounterInnerCommand, CrdtInnerCommand},
+        operation::OperationId,
+        r#type::CrdtType,
+    };
+    use crust_network::{
+        gossip::{
+            sample_peers, Dissemination, GossipConfig, RumorLog, DEFAULT_GOSSIP_FANOUT,
+            SEEN_RUMORS_CAPACITY,
+        },
+        message::NetworkMessage,
+        object_store::ObjectStore,
+        receiver::AppState,
+        serve,
+    };
+    use reqwest::Client;
+    use serde_json::Value;
+    use tokio::net::TcpListener;
+
+    fn peers(count: usize) -> Vec<String> {
+        (0..count).map(|peer| format!("replica-{peer}")).collect()
+    }
+
+    fn rumor(origin: &str, counter: u64, hops: u32) -> NetworkMessage<String> {
+        let mut crdt = CrdtType::<String>::new("gcounter".to_string()).unwrap();
+        crdt.apply_command(&CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+            value: origin.to_string(),
+        }))
+        .unwrap();
+        NetworkMessage::Rumor {
+            rumor_id: OperationId::new(origin.to_string(), counter),
+            hops,
+            payload: Box::new(NetworkMessage::State {
+                payload: crdt,
+                sender_pod_name: origin.to_string(),
+            }),
+            sender_pod_name: "replica-relay".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_dissemination_strategies_are_selectable_by_name() {
+        assert_eq!(
+            Dissemination::new("broadcast").unwrap(),
+            Dissemination::Broadcast
+        );
+        assert_eq!(
+            Dissemination::new("gossip").unwrap(),
+            Dissemination::Gossip(GossipConfig::default())
+        );
+        assert!(Dissemination::new("flood").is_err());
+        assert_eq!(Dissemination::default(), Dissemination::Broadcast);
+
+        let all = peers(10);
+        assert_eq!(Dissemination::Broadcast.round_peers(&all), all);
+        assert_eq!(
+            Dissemination::Gossip(GossipConfig::new(4, 2))
+                .round_peers(&all)
+                .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_sampling_respects_fanout_and_exclusions() {
+        let all = peers(10);
+        for _ in 0..20 {
+            let sample = sample_peers(&all, &["replica-0", "replica-1"], DEFAULT_GOSSIP_FANOUT);
+            assert_eq!(sample.len(), DEFAULT_GOSSIP_FANOUT);
+            assert_eq!(sample.iter().collect::<HashSet<_>>().len(), sample.len());
+            assert!(sample
+                .iter()
+                .all(|peer| peer != "replica-0" && peer != "replica-1"));
+        }
+        let sample = sample_peers(&all[..3], &["replica-2"], 5);
+        assert_eq!(
+            sample.into_iter().collect::<HashSet<_>>(),
+            HashSet::from(["replica-0".to_string(), "replica-1".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_rumor_log_deduplicates_and_stays_bounded() {
+        let rumors = RumorLog::default();
+        let first = rumors.next_rumor("replica-a");
+        let second = rumors.next_rumor("replica-a");
+        assert_eq!(first.counter + 1, second.counter);
+        assert!(!rumors.observe(&first));
+
+        let remote = OperationId::new("replica-b".to_string(), 1);
+        assert!(rumors.observe(&remote));
+        assert!(!rumors.observe(&remote));
+
+        for counter in 0..SEEN_RUMORS_CAPACITY as u64 {
+            rumors.observe(&OperationId::new("replica-c".to_string(), counter));
+        }
+        assert_eq!(rumors.len(), SEEN_RUMORS_CAPACITY);
+        assert!(rumors.observe(&first));
+    }
+
+    #[test]
+    fn test_rumor_ids_are_not_reused_after_a_restart() {
+        let before = RumorLog::default();
+        let last = (0..100)
+            .map(|_| before.next_rumor("replica-a"))
+            .last()
+            .unwrap();
+        sleep(Duration::from_millis(1));
+        let restarted = RumorLog::default();
+        let first = restarted.next_rumor("replica-a");
+        assert!(first.counter > last.counter);
+        assert!(restarted.observe(&last));
+    }
+
+    #[test]
+    fn test_rumors_deliver_their_payload() {
+        let store = ObjectStore::<String>::new("replica-b".to_string());
+        store.declare("likes", "gcounter").unwrap();
+        store
+            .with_object("likes", |object| object.receive(&rumor("replica-a", 1, 2)))
+            .unwrap();
+        let state = store
+            .with_object("likes", |object| Ok(object.get_state()))
+            .unwrap();
+        assert_eq!(state["value"], "1");
+
+        store
+            .with_object("likes", |object| object.receive(&rumor("replica-b", 1, 2)))
+            .unwrap();
+        let state = store
+            .with_object("likes", |object| Ok(object.get_state()))
+            .unwrap();
+        assert_eq!(state["value"], "1");
+    }
+
+    #[tokio::test]
+    async fn test_nodes_drop_rumors_they_have_already_seen() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node = format!("http://{}", listener.local_addr().unwrap());
+        let state = AppState::<String>::with_peers("replica-b".to_string(), Vec::new())
+            .unwrap()
+            .with_dissemination(Dissemination::Gossip(GossipConfig::default()));
+        tokio::spawn(serve(listener, state.clone()));
+
+        let client = Client::new();
+        let message = rumor("replica-a", 7, 0);
+        let mut replies = Vec::new();
+        for _ in 0..2 {
+            let body: Value = client
+                .post(format!("{node}/objects/likes/receive/gcounter"))
+                .json(&message)
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            replies.push(body["message"].clone());
+        }
+        assert_ne!(replies[0], "Rumor already delivered");
+        assert_eq!(replies[1], "Rumor already delivered");
+        assert!(!state
+            .rumors
+            .observe(&OperationId::new("replica-a".to_string(), 7)));
+
+        let body: Value = client
+            .get(format!("{node}/objects/likes"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["state"]["value"], "1");
+    }
+
+    #[tokio::test]
+    async fn test_rumors_are_marked_seen_only_once_delivered() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node = format!("http://{}", listener.local_addr().unwrap());
+        let state = AppState::<String>::with_peers("replica-b".to_string(), Vec::new())
+            .unwrap()
+            .with_dissemination(Dissemination::Gossip(GossipConfig::default()));
+        tokio::spawn(serve(listener, state.clone()));
+
+        let client = Client::new();
+        let message = rumor("replica-a", 3, 0);
+        let response = client
+            .post(format!("{node}/objects/tags/receive/orset"))
+            .json(&message)
+            .send()
+            .await
+            .unwrap();
+        assert!(!response.status().is_success());
+        assert!(!state
+            .rumors
+            .contains(&OperationId::new("replica-a".to_string(), 3)));
+
+        let body: Value = client
+            .post(format!("{node}/objects/likes/receive/gcounter"))
+            .json(&message)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_ne!(body["message"], "Rumor already delivered");
+        assert!(state
+            .rumors
+            .contains(&OperationId::new("replica-a".to_string(), 3)));
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        anti_entropy::iblt::ReconcileMessage,
+        command::{CrdtInnerCommand, SetInnerCommand},
+        sync::{SyncMode, SyncType},
+    };
+    use crust_network::{
+        anti_entropy::Reconciliation, message::NetworkMessage, object_store::ObjectStore,
+        receiver::sync_config,
+    };
+    use serde_json::Value;
+
+    fn apply(store: &ObjectStore<String>, command: CrdtInnerCommand<String>) {
+        let mut config = sync_config(SyncType::Delta, SyncMode::Immediate);
+        store
+            .with_object("tags", |object| object.apply_command(&command, &mut config))
+            .unwrap();
+    }
+
+    fn add(value: &str) -> CrdtInnerCommand<String> {
+        CrdtInnerCommand::Set(SetInnerCommand::Add {
+            value: value.to_string(),
+        })
+    }
+
+    fn state(store: &ObjectStore<String>) -> Value {
+        store
+            .with_object("tags", |object| Ok(object.get_state()))
+            .unwrap()
+    }
+
+    fn replicas() -> (ObjectStore<String>, ObjectStore<String>) {
+        let a = ObjectStore::<String>::new("replica-a".to_string());
+        let b = ObjectStore::<String>::new("replica-b".to_string());
+        a.declare("tags", "orset").unwrap();
+        b.declare("tags", "orset").unwrap();
+        (a, b)
+    }
+
+    fn reconcile(
+        initiator: &ObjectStore<String>,
+        responder: &ObjectStore<String>,
+        reconciliation: Reconciliation,
+    ) -> Vec<&'static str> {
+        let mut message = initiator
+            .with_object("tags", |object| reconciliation.prepare(object))
+            .unwrap();
+        let mut stores = [responder, initiator];
+        let mut kinds = Vec::new();
+        while let Some(current) = message {
+            if let NetworkMessage::Reconcile { payload, .. } = &current {
+                kinds.push(match payload {
+                    ReconcileMessage::Sketch { .. } => "sketch",
+                    ReconcileMessage::Entries { .. } => "entries",
+                    ReconcileMessage::Fallback { .. } => "fallback",
+                });
+            }
+            message = stores[0]
+                .with_object("tags", |object| object.receive(&current))
+                .unwrap();
+            stores.swap(0, 1);
+        }
+        kinds
+    }
+
+    #[test]
+    fn test_sketch_exchange_transfers_only_missing_elements() {
+        let (a, b) = replicas();
+        for value in 0..100 {
+            apply(&a, add(&format!("tag-{value}")));
+        }
+        let message = a
+            .with_object("tags", |object| {
+                Ok(NetworkMessage::State {
+                    payload: object.crdt().clone(),
+                    sender_pod_name: "replica-a".to_string(),
+                })
+            })
+            .unwrap();
+        b.with_object("tags", |object| object.receive(&message))
+            .unwrap();
+        apply(
+            &a,
+            CrdtInnerCommand::Set(SetInnerCommand::Remove {
+                value: "tag-3".to_string(),
+            }),
+        );
+        apply(&a, add("tag-a"));
+        apply(&b, add("tag-b"));
+
+        let kinds = reconcile(&a, &b, Reconciliation::Iblt { cells: 30 });
+        assert_eq!(kinds, vec!["sketch", "entries", "entries"]);
+        assert_eq!(state(&a), state(&b));
+        assert_eq!(state(&a)["len"], 101);
+        assert_eq!(
+            reconcile(&b, &a, Reconciliation::Iblt { cells: 30 }),
+            vec!["sketch"]
+        );
+    }
+
+    #[test]
+    fn test_undecodable_sketch_falls_back_to_full_state() {
+        let (a, b) = replicas();
+        for value in 0..40 {
+            apply(&a, add(&format!("a-{value}")));
+            apply(&b, add(&format!("b-{value}")));
+        }
+
+        let kinds = reconcile(&a, &b, Reconciliation::Iblt { cells: 3 });
+        assert_eq!(kinds, vec!["sketch", "fallback", "fallback"]);
+        assert_eq!(state(&a), state(&b));
+        assert_eq!(state(&a)["len"], 80);
+    }
+
+    #[test]
+    fn test_reconciliation_modes_are_parsed_by_name() {
+        assert!(matches!(
+            Reconciliation::new("merkle").unwrap(),
+            Reconciliation::Merkle { .. }
+        ));
+        assert!(matches!(
+            Reconciliation::new("iblt").unwrap(),
+            Reconciliation::Iblt { .. }
+        ));
+        assert!(Reconciliation::new("gossip").is_err());
+        assert_eq!(
+            Reconciliation::default(),
+            Reconciliation::new("merkle").unwrap()
+        );
+    }
+}
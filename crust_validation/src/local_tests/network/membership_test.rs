@@ -0,0 +1,256 @@
+#[cfg(test)]
+mod tests {
+    use std::{thread::sleep, time::Duration};
+
+    use crust_network::{
+        membership::{
+            MemberState, Membership, MembershipUpdate, SwimConfig, SwimMessage,
+            RETRANSMIT_MULTIPLIER,
+        },
+        receiver::AppState,
+        serve,
+    };
+    use reqwest::Client;
+    use tokio::net::TcpListener;
+
+    fn pods(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn update(pod_name: &str, state: MemberState, incarnation: u64) -> MembershipUpdate {
+        MembershipUpdate {
+            pod_name: pod_name.to_string(),
+            state,
+            incarnation,
+        }
+    }
+
+    fn config(suspicion_timeout: Duration) -> SwimConfig {
+        SwimConfig {
+            ping_timeout: Duration::from_millis(200),
+            suspicion_timeout,
+            ..SwimConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_updates_follow_incarnation_precedence() {
+        let membership = Membership::new("replica-a".to_string(), SwimConfig::default());
+        membership.join(&pods(&["replica-a", "replica-b", "replica-c"]));
+        assert_eq!(membership.members().len(), 2);
+        assert_eq!(membership.state("replica-b"), Some(MemberState::Alive));
+
+        assert!(membership.apply(&update("replica-b", MemberState::Suspect, 0)));
+        assert!(!membership.apply(&update("replica-b", MemberState::Alive, 0)));
+        assert_eq!(membership.state("replica-b"), Some(MemberState::Suspect));
+        assert!(membership.apply(&update("replica-b", MemberState::Alive, 1)));
+        assert_eq!(membership.state("replica-b"), Some(MemberState::Alive));
+        assert!(!membership.apply(&update("replica-b", MemberState::Suspect, 0)));
+
+        assert!(membership.apply(&update("replica-c", MemberState::Dead, 0)));
+        assert!(!membership.apply(&update("replica-c", MemberState::Suspect, 0)));
+        assert_eq!(membership.state("replica-c"), Some(MemberState::Dead));
+        assert!(membership.apply(&update("replica-c", MemberState::Alive, 1)));
+        assert!(membership.is_live("replica-c"));
+
+        assert!(membership.apply(&update("replica-d", MemberState::Alive, 3)));
+        assert_eq!(membership.members().len(), 3);
+    }
+
+    #[test]
+    fn test_members_refute_suspicion_about_themselves() {
+        let membership = Membership::new("replica-a".to_string(), SwimConfig::default());
+        membership.join(&pods(&["replica-b"]));
+        assert!(membership.apply(&update("replica-a", MemberState::Suspect, 0)));
+        assert_eq!(membership.incarnation(), 1);
+        assert!(!membership.apply(&update("replica-a", MemberState::Suspect, 0)));
+        assert!(membership.piggyback("replica-b").contains(&update(
+            "replica-a",
+            MemberState::Alive,
+            1
+        )));
+        assert!(membership.state("replica-a").is_none());
+    }
+
+    #[test]
+    fn test_piggybacked_updates_are_retransmitted_a_bounded_number_of_times() {
+        let membership = Membership::new("replica-a".to_string(), SwimConfig::default());
+        membership.join(&pods(&["replica-b", "replica-c"]));
+        membership.suspect("replica-c");
+        let mut transmissions = 0;
+        while membership.piggyback("replica-b").contains(&update(
+            "replica-c",
+            MemberState::Suspect,
+            0,
+        )) {
+            transmissions += 1;
+        }
+        assert_eq!(transmissions, RETRANSMIT_MULTIPLIER * 2);
+        assert_eq!(
+            membership.piggyback("replica-c"),
+            vec![update("replica-c", MemberState::Suspect, 0)]
+        );
+    }
+
+    #[test]
+    fn test_suspects_are_declared_dead_after_the_timeout() {
+        let membership =
+            Membership::new("replica-a".to_string(), config(Duration::from_millis(50)));
+        membership.join(&pods(&["replica-b", "replica-c"]));
+        assert!(membership.suspect("replica-c"));
+        assert!(!membership.suspect("replica-c"));
+        assert!(membership.expire_suspicions().is_empty());
+        assert!(membership.is_live("replica-c"));
+
+        sleep(Duration::from_millis(60));
+        assert_eq!(membership.expire_suspicions(), pods(&["replica-c"]));
+        assert!(!membership.is_live("replica-c"));
+        assert_eq!(
+            membership.live(pods(&["replica-a", "replica-b", "replica-c"])),
+            pods(&["replica-a", "replica-b"])
+        );
+        assert_eq!(
+            membership.indirect_probers("replica-b"),
+            Vec::<String>::new()
+        );
+        for _ in 0..4 {
+            assert_eq!(membership.next_probe_target().unwrap(), "replica-b");
+        }
+    }
+
+    #[test]
+    fn test_dead_members_are_confirmed_departed_and_pruned_after_their_ttl() {
+        let membership = Membership::new(
+            "replica-a".to_string(),
+            SwimConfig {
+                confirmation_timeout: Duration::from_millis(50),
+                tombstone_ttl: Duration::from_millis(100),
+                ..SwimConfig::default()
+            },
+        );
+        membership.join(&pods(&["replica-b", "replica-c"]));
+        assert!(membership.apply(&update("replica-c", MemberState::Dead, 0)));
+        assert!(!membership.is_live("replica-c"));
+        assert!(!membership.is_departed("replica-c"));
+        assert_eq!(
+            membership.retained(pods(&["replica-b", "replica-c"])),
+            pods(&["replica-b", "replica-c"])
+        );
+
+        sleep(Duration::from_millis(60));
+        assert!(membership.is_departed("replica-c"));
+        assert_eq!(
+            membership.retained(pods(&["replica-b", "replica-c"])),
+            pods(&["replica-b"])
+        );
+        assert!(membership.prune_dead().is_empty());
+
+        assert!(membership.apply(&update("replica-b", MemberState::Dead, 0)));
+        assert!(membership.apply(&update("replica-b", MemberState::Alive, 1)));
+        sleep(Duration::from_millis(50));
+        assert_eq!(membership.prune_dead(), pods(&["replica-c"]));
+        assert_eq!(membership.state("replica-c"), None);
+        assert!(!membership.is_departed("replica-b"));
+        assert_eq!(
+            membership.members(),
+            vec![update("replica-b", MemberState::Alive, 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dead_peers_are_excluded_from_replica_pod_names() {
+        let state = AppState::<String>::with_peers(
+            "replica-a".to_string(),
+            pods(&["replica-a", "replica-b", "replica-c"]),
+        )
+        .unwrap();
+        assert_eq!(state.replica_pod_names().await.unwrap().len(), 3);
+        state
+            .membership
+            .apply(&update("replica-c", MemberState::Dead, 0));
+        assert_eq!(
+            state.replica_pod_names().await.unwrap(),
+            pods(&["replica-a", "replica-b"])
+        );
+        assert_eq!(state.retained_pod_names().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_pings_are_acknowledged_with_piggybacked_updates() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node = format!("http://{}", listener.local_addr().unwrap());
+        let state = AppState::<String>::with_peers("replica-b".to_string(), Vec::new())
+            .unwrap()
+            .with_swim(config(Duration::from_secs(5)));
+        tokio::spawn(serve(listener, state.clone()));
+        let client = Client::new();
+
+        let ack: SwimMessage = client
+            .post(format!("{node}/membership/ping"))
+            .json(&SwimMessage::Ping {
+                sender_pod_name: "replica-a".to_string(),
+                updates: vec![update("replica-b", MemberState::Suspect, 0)],
+            })
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(ack.sender_pod_name(), "replica-b");
+        assert!(ack
+            .updates()
+            .contains(&update("replica-b", MemberState::Alive, 1)));
+        assert_eq!(
+            state.membership.state("replica-a"),
+            Some(MemberState::Alive)
+        );
+
+        let response = client
+            .post(format!("{node}/membership/ping"))
+            .json(&SwimMessage::Ack {
+                sender_pod_name: "replica-a".to_string(),
+                updates: Vec::new(),
+            })
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_client_error());
+
+        let members: serde_json::Value = client
+            .get(format!("{node}/membership"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(members["incarnation"], 1);
+        assert_eq!(members["members"][0]["pod_name"], "replica-a");
+    }
+
+    #[tokio::test]
+    async fn test_indirect_pings_report_unreachable_targets() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node = format!("http://{}", listener.local_addr().unwrap());
+        let state = AppState::<String>::with_peers("replica-b".to_string(), Vec::new())
+            .unwrap()
+            .with_swim(config(Duration::from_secs(5)));
+        tokio::spawn(serve(listener, state));
+
+        let reply: SwimMessage = Client::new()
+            .post(format!("{node}/membership/ping-req"))
+            .json(&SwimMessage::PingReq {
+                sender_pod_name: "replica-a".to_string(),
+                target: "replica-unreachable".to_string(),
+                updates: Vec::new(),
+            })
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert!(matches!(reply, SwimMessage::Nack { .. }));
+    }
+}
@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        command::{CrdtInnerCommand, MapInnerCommand, SetInnerCommand},
+        sync::{SyncMode, SyncType},
+    };
+    use crust_network::{
+        message::NetworkMessage, object_store::ObjectStore, receiver::sync_config,
+    };
+    use serde_json::json;
+
+    fn apply(store: &ObjectStore<String>, name: &str, command: CrdtInnerCommand<String>) {
+        let mut config = sync_config(SyncType::Delta, SyncMode::Immediate);
+        store
+            .with_object(name, |object| object.apply_command(&command, &mut config))
+            .unwrap();
+    }
+
+    fn add(value: &str) -> CrdtInnerCommand<String> {
+        CrdtInnerCommand::Set(SetInnerCommand::Add {
+            value: value.to_string(),
+        })
+    }
+
+    fn remove(value: &str) -> CrdtInnerCommand<String> {
+        CrdtInnerCommand::Set(SetInnerCommand::Remove {
+            value: value.to_string(),
+        })
+    }
+
+    fn state(store: &ObjectStore<String>, name: &str) -> serde_json::Value {
+        store
+            .with_object(name, |object| Ok(object.get_state()))
+            .unwrap()
+    }
+
+    fn synchronize(
+        initiator: &ObjectStore<String>,
+        responder: &ObjectStore<String>,
+        name: &str,
+        depth: u32,
+    ) -> usize {
+        let mut message = initiator
+            .with_object(name, |object| object.prepare_merkle_digest(depth))
+            .unwrap();
+        let mut stores = [responder, initiator];
+        let mut rounds = 0;
+        while let Some(current) = message {
+            rounds += 1;
+            message = stores[0]
+                .with_object(name, |object| object.receive(&current))
+                .unwrap();
+            stores.swap(0, 1);
+        }
+        rounds
+    }
+
+    fn replicas(crdt_type: &str) -> (ObjectStore<String>, ObjectStore<String>) {
+        let a = ObjectStore::<String>::new("replica-a".to_string());
+        let b = ObjectStore::<String>::new("replica-b".to_string());
+        a.declare("tags", crdt_type).unwrap();
+        b.declare("tags", crdt_type).unwrap();
+        (a, b)
+    }
+
+    #[test]
+    fn test_merkle_exchange_repairs_lost_set_updates() {
+        let (a, b) = replicas("orset");
+        for value in 0..50 {
+            apply(&a, "tags", add(&format!("tag-{value}")));
+        }
+        let message = a
+            .with_object("tags", |object| {
+                Ok(NetworkMessage::State {
+                    payload: object.crdt().clone(),
+                    sender_pod_name: "replica-a".to_string(),
+                })
+            })
+            .unwrap();
+        b.with_object("tags", |object| object.receive(&message))
+            .unwrap();
+        assert_eq!(synchronize(&a, &b, "tags", 4), 1);
+
+        apply(&a, "tags", remove("tag-7"));
+        apply(&a, "tags", add("tag-a"));
+        apply(&b, "tags", add("tag-b"));
+        assert_ne!(state(&a, "tags"), state(&b, "tags"));
+
+        assert!(synchronize(&a, &b, "tags", 4) > 1);
+        assert_eq!(state(&a, "tags"), state(&b, "tags"));
+        assert_eq!(state(&a, "tags")["len"], json!(51));
+        assert_eq!(synchronize(&b, &a, "tags", 4), 1);
+    }
+
+    #[test]
+    fn test_merkle_exchange_converges_maps() {
+        let (a, b) = replicas("mvmap");
+        apply(
+            &a,
+            "tags",
+            CrdtInnerCommand::Map(MapInnerCommand::Put {
+                key: "color".to_string(),
+                value: "red".to_string(),
+            }),
+        );
+        apply(
+            &b,
+            "tags",
+            CrdtInnerCommand::Map(MapInnerCommand::Put {
+                key: "color".to_string(),
+                value: "blue".to_string(),
+            }),
+        );
+        apply(
+            &b,
+            "tags",
+            CrdtInnerCommand::Map(MapInnerCommand::Put {
+                key: "size".to_string(),
+                value: "large".to_string(),
+            }),
+        );
+
+        synchronize(&b, &a, "tags", 2);
+        assert_eq!(state(&a, "tags"), state(&b, "tags"));
+        assert_eq!(
+            state(&a, "tags")["value"],
+            json!([["color", ["blue", "red"]], ["size", ["large"]]])
+        );
+    }
+
+    #[test]
+    fn test_counters_do_not_take_part_in_merkle_exchange() {
+        let store = ObjectStore::<String>::new("replica-a".to_string());
+        store.declare("likes", "gcounter").unwrap();
+        assert!(store
+            .with_object("likes", |object| object.prepare_merkle_digest(4))
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,10 @@
+mod app_state_test;
+mod object_store_test;
+mod persistence_test;
+mod encoding_test;
+mod compression_test;
+mod merkle_anti_entropy_test;
+mod iblt_reconciliation_test;
+mod digest_test;
+mod gossip_test;
+mod membership_test;
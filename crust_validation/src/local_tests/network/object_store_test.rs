@@ -0,0 +1,368 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        causality::version_vector::VersionVector,
+        command::{CounterInnerCommand, CrdtInnerCommand, SetInnerCommand},
+        error::CrustError,
+        operation::{CounterOperation, CrdtOperation, OperationId, SetOperation},
+        sync::{SyncMode, SyncType},
+    };
+    use crust_network::{
+        message::NetworkMessage, object_store::ObjectStore, receiver::sync_config,
+    };
+    use serde_json::json;
+
+    fn increment(value: &str) -> CrdtInnerCommand<String> {
+        CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+            value: value.to_string(),
+        })
+    }
+
+    fn counter_value(store: &ObjectStore<String>, name: &str) -> String {
+        store
+            .with_object(name, |object| Ok(object.get_state()["value"].clone()))
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    fn command(
+        store: &ObjectStore<String>,
+        name: &str,
+        value: &str,
+        sync_type: SyncType,
+    ) -> Option<NetworkMessage<String>> {
+        let mut config = sync_config(sync_type, SyncMode::Immediate);
+        store
+            .with_object(name, |object| {
+                object.apply_command(&increment(value), &mut config)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_named_objects_are_independent() {
+        let store = ObjectStore::<String>::new("replica-1".to_string());
+        assert!(store.declare("likes", "gcounter").unwrap());
+        assert!(store.declare("views", "gcounter").unwrap());
+        assert!(!store.declare("likes", "gcounter").unwrap());
+
+        command(&store, "likes", "replica-1", SyncType::State);
+        command(&store, "likes", "replica-1", SyncType::State);
+        command(&store, "views", "replica-1", SyncType::State);
+
+        assert_eq!(counter_value(&store, "likes"), "2");
+        assert_eq!(counter_value(&store, "views"), "1");
+        assert_eq!(
+            store.objects(),
+            vec![
+                ("likes".to_string(), "gcounter".to_string()),
+                ("views".to_string(), "gcounter".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_declare_rejects_conflicting_or_unknown_types() {
+        let store = ObjectStore::<String>::new("replica-1".to_string());
+        store.declare("likes", "gcounter").unwrap();
+        assert_eq!(
+            store.declare("likes", "gset"),
+            Err(CrustError::TypeMismatch {
+                expected: "gcounter".to_string(),
+                found: "gset".to_string(),
+            })
+        );
+        assert_eq!(
+            store.declare("tags", "unknown"),
+            Err(CrustError::UnknownCrdtType("unknown".to_string()))
+        );
+        assert!(!store.contains("tags"));
+    }
+
+    #[test]
+    fn test_missing_objects_are_reported() {
+        let store = ObjectStore::<String>::new("replica-1".to_string());
+        assert_eq!(
+            store.with_object("likes", |_| Ok(())),
+            Err(CrustError::ObjectNotFound("likes".to_string()))
+        );
+        store.declare("likes", "gcounter").unwrap();
+        store.remove("likes").unwrap();
+        assert!(store.is_empty());
+        assert!(store.remove("likes").is_err());
+    }
+
+    #[test]
+    fn test_objects_replicate_between_stores() {
+        let a = ObjectStore::<String>::new("replica-a".to_string());
+        let b = ObjectStore::<String>::new("replica-b".to_string());
+        a.declare("likes", "gcounter").unwrap();
+        b.declare("likes", "gcounter").unwrap();
+        b.declare("views", "gcounter").unwrap();
+
+        for sync_type in [SyncType::Operation, SyncType::Delta, SyncType::State] {
+            let message = command(&a, "likes", "replica-a", sync_type).unwrap();
+            let reply = b
+                .with_object("likes", |object| object.receive(&message))
+                .unwrap();
+            assert!(reply.is_none());
+        }
+
+        assert_eq!(counter_value(&b, "likes"), "3");
+        assert_eq!(counter_value(&b, "views"), "0");
+    }
+
+    #[test]
+    fn test_delta_interval_is_tracked_per_object() {
+        let a = ObjectStore::<String>::new("replica-a".to_string());
+        let b = ObjectStore::<String>::new("replica-b".to_string());
+        a.declare("likes", "gcounter").unwrap();
+        a.declare("views", "gcounter").unwrap();
+        b.declare("likes", "gcounter").unwrap();
+
+        command(&a, "likes", "replica-a", SyncType::Delta);
+        let message = a
+            .with_object("likes", |object| {
+                Ok(object.prepare_delta_interval("replica-b"))
+            })
+            .unwrap()
+            .unwrap();
+        assert!(a
+            .with_object("views", |object| Ok(
+                object.prepare_delta_interval("replica-b")
+            ))
+            .unwrap()
+            .is_none());
+
+        let ack = b
+            .with_object("likes", |object| object.receive(&message))
+            .unwrap()
+            .unwrap();
+        a.with_object("likes", |object| object.receive(&ack))
+            .unwrap();
+        assert!(a
+            .with_object("likes", |object| Ok(
+                object.prepare_delta_interval("replica-b")
+            ))
+            .unwrap()
+            .is_none());
+        assert_eq!(counter_value(&b, "likes"), "1");
+    }
+
+    #[test]
+    fn test_stability_is_exchanged_between_replicas() {
+        let stores: Vec<ObjectStore<String>> = ["replica-a", "replica-b", "replica-c"]
+            .iter()
+            .map(|replica| {
+                let store = ObjectStore::<String>::new(replica.to_string());
+                store.declare("likes", "gcounter").unwrap();
+                store
+            })
+            .collect();
+        let replicas = ["replica-b".to_string(), "replica-c".to_string()];
+        stores[0]
+            .with_object("likes", |object| {
+                object.add_stability_replicas(&replicas);
+                Ok(())
+            })
+            .unwrap();
+
+        let operation = command(&stores[0], "likes", "replica-a", SyncType::Operation).unwrap();
+        stores[1]
+            .with_object("likes", |object| object.receive(&operation))
+            .unwrap();
+
+        let exchange = |from: usize| {
+            let message = stores[from]
+                .with_object("likes", |object| Ok(object.prepare_stability()))
+                .unwrap();
+            stores[0]
+                .with_object("likes", |object| object.receive(&message))
+                .unwrap();
+        };
+        let stable = || {
+            stores[0]
+                .with_object("likes", |object| Ok(object.stable_version_vector()))
+                .unwrap()
+        };
+
+        exchange(1);
+        exchange(2);
+        assert!(stable().is_empty());
+
+        stores[2]
+            .with_object("likes", |object| object.receive(&operation))
+            .unwrap();
+        exchange(2);
+        assert_eq!(stable().get(&"replica-a".to_string()), 1);
+    }
+
+    #[test]
+    fn test_departed_replicas_are_folded_once_peers_agree() {
+        let a = ObjectStore::<String>::new("replica-a".to_string());
+        let b = ObjectStore::<String>::new("replica-b".to_string());
+        for store in [&a, &b] {
+            store.declare("likes", "gcounter").unwrap();
+        }
+        for value in ["replica-a", "replica-c", "replica-c", "clicks"] {
+            let message = command(&a, "likes", value, SyncType::State).unwrap();
+            b.with_object("likes", |object| object.receive(&message))
+                .unwrap();
+        }
+
+        let retire = |store: &ObjectStore<String>, live: &[&str]| {
+            let live: Vec<String> = live.iter().map(|replica| replica.to_string()).collect();
+            store
+                .with_object("likes", |object| {
+                    object.add_stability_replicas(&live);
+                    object.retire_departed(&live)
+                })
+                .unwrap()
+        };
+        let report = |from: &ObjectStore<String>, to: &ObjectStore<String>| {
+            let message = from
+                .with_object("likes", |object| Ok(object.prepare_stability()))
+                .unwrap();
+            to.with_object("likes", |object| object.receive(&message))
+                .unwrap();
+        };
+        let state = |store: &ObjectStore<String>| {
+            store
+                .with_object("likes", |object| Ok(object.get_state()))
+                .unwrap()
+        };
+
+        assert!(!retire(&a, &["replica-a", "replica-b", "replica-c"]));
+        assert!(!retire(&b, &["replica-a", "replica-b", "replica-c"]));
+        assert!(!retire(&a, &["replica-a", "replica-b"]));
+        assert_eq!(state(&a)["retired"], json!({}));
+
+        report(&b, &a);
+        assert!(!retire(&a, &["replica-a", "replica-b"]));
+        assert_eq!(state(&a)["retired"], json!({"replica-c": 2}));
+        assert!(!retire(&b, &["replica-a", "replica-b"]));
+        assert_eq!(state(&b)["retired"], json!({}));
+
+        report(&a, &b);
+        assert!(retire(&b, &["replica-a", "replica-b"]));
+        report(&b, &a);
+        retire(&a, &["replica-a", "replica-b"]);
+        for store in [&a, &b] {
+            let state = state(store);
+            assert_eq!(state["value"], "4");
+            assert_eq!(state["base"], 2);
+            assert_eq!(state["state"], json!({"replica-a": 1, "clicks": 1}));
+        }
+    }
+
+    #[test]
+    fn test_shipped_deltas_drain_the_delta_buffer() {
+        let a = ObjectStore::<String>::new("replica-a".to_string());
+        let b = ObjectStore::<String>::new("replica-b".to_string());
+        for store in [&a, &b] {
+            store.declare("tags", "orset").unwrap();
+        }
+        let mut config = sync_config(SyncType::Delta, SyncMode::Immediate);
+        let mut add = |value: &str| {
+            a.with_object("tags", |object| {
+                object.apply_command(
+                    &CrdtInnerCommand::Set(SetInnerCommand::Add {
+                        value: value.to_string(),
+                    }),
+                    &mut config,
+                )
+            })
+            .unwrap()
+            .unwrap()
+        };
+        add("x");
+        let delta = add("y");
+        b.with_object("tags", |object| object.receive(&delta))
+            .unwrap();
+        let state = b
+            .with_object("tags", |object| Ok(object.get_state()))
+            .unwrap();
+        assert_eq!(state["value"], json!(["y"]));
+    }
+
+    #[test]
+    fn test_failed_operations_are_rejected_without_blocking_their_batch() {
+        let store = ObjectStore::<String>::new("replica-b".to_string());
+        store.declare("likes", "gcounter").unwrap();
+        let operation = |counter: u64, payload: CrdtOperation<String>| {
+            let mut clock = VersionVector::new();
+            for _ in 0..counter {
+                clock.increment("replica-a".to_string());
+            }
+            NetworkMessage::Operation {
+                payload,
+                sender_pod_name: "replica-a".to_string(),
+                operation_id: OperationId::new("replica-a".to_string(), counter),
+                clock,
+            }
+        };
+        let increment = CrdtOperation::Counter(CounterOperation::Increment {
+            value: "replica-a".to_string(),
+        });
+        let invalid = CrdtOperation::Set(SetOperation::Add {
+            value: "replica-a".to_string(),
+        });
+
+        store
+            .with_object("likes", |object| object.receive(&operation(2, increment)))
+            .unwrap();
+        assert!(matches!(
+            store.with_object("likes", |object| object.receive(&operation(1, invalid.clone()))),
+            Err(CrustError::InvalidOperation(message)) if message.contains("replica-a:1 was rejected")
+        ));
+        assert_eq!(counter_value(&store, "likes"), "1");
+        store
+            .with_object("likes", |object| object.receive(&operation(1, invalid)))
+            .unwrap();
+        assert_eq!(counter_value(&store, "likes"), "1");
+    }
+
+    #[test]
+    fn test_stability_tracks_state_synced_dot_stores() {
+        let a = ObjectStore::<String>::new("replica-a".to_string());
+        let b = ObjectStore::<String>::new("replica-b".to_string());
+        for store in [&a, &b] {
+            store.declare("tags", "orset").unwrap();
+        }
+        a.with_object("tags", |object| {
+            object.add_stability_replicas(&["replica-b".to_string()]);
+            Ok(())
+        })
+        .unwrap();
+        let mut config = sync_config(SyncType::State, SyncMode::Immediate);
+        let state = a
+            .with_object("tags", |object| {
+                object.apply_command(
+                    &CrdtInnerCommand::Set(SetInnerCommand::Add {
+                        value: "x".to_string(),
+                    }),
+                    &mut config,
+                )
+            })
+            .unwrap()
+            .unwrap();
+        let stable = || {
+            a.with_object("tags", |object| Ok(object.stable_version_vector()))
+                .unwrap()
+        };
+        assert!(stable().is_empty());
+
+        b.with_object("tags", |object| object.receive(&state))
+            .unwrap();
+        let stability = b
+            .with_object("tags", |object| Ok(object.prepare_stability()))
+            .unwrap();
+        a.with_object("tags", |object| object.receive(&stability))
+            .unwrap();
+        let mut expected = VersionVector::new();
+        expected.increment("replica-a".to_string());
+        assert_eq!(stable(), expected);
+    }
+}
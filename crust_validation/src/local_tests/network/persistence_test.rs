@@ -0,0 +1,308 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::{self, OpenOptions},
+        io::Write,
+        path::{Path, PathBuf},
+    };
+
+    use crust_core::{
+        command::{CounterInnerCommand, CrdtInnerCommand},
+        constraint::{ConstraintRule, RepairPolicy},
+        error::CrustError,
+        sync::{SyncMode, SyncType},
+    };
+    use crust_network::{
+        object_store::ObjectStore, persistence::StorageConfig, receiver::sync_config,
+    };
+    use crust_storage::{memory::MemoryStorage, FsyncPolicy, StorageBackend};
+
+    fn data_dir(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("crust-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        path
+    }
+
+    fn open(path: &Path, snapshot_threshold: usize) -> ObjectStore<String> {
+        open_backend(StorageBackend::File(path.to_path_buf()), snapshot_threshold)
+    }
+
+    fn open_backend(backend: StorageBackend, snapshot_threshold: usize) -> ObjectStore<String> {
+        ObjectStore::open(
+            "replica-1".to_string(),
+            StorageConfig::new(backend)
+                .with_fsync_policy(FsyncPolicy::Never)
+                .with_snapshot_threshold(snapshot_threshold),
+        )
+        .unwrap()
+    }
+
+    fn increment(store: &ObjectStore<String>, name: &str, sync_type: SyncType) {
+        let mut config = sync_config(sync_type, SyncMode::Immediate);
+        store
+            .with_object(name, |object| {
+                object.apply_command(
+                    &CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+                        value: "replica-1".to_string(),
+                    }),
+                    &mut config,
+                )
+            })
+            .unwrap();
+    }
+
+    fn value(store: &ObjectStore<String>, name: &str) -> String {
+        store
+            .with_object(name, |object| Ok(object.get_state()["value"].clone()))
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_fsync_policies_parse() {
+        assert_eq!(FsyncPolicy::new("always").unwrap(), FsyncPolicy::Always);
+        assert_eq!(FsyncPolicy::new("never").unwrap(), FsyncPolicy::Never);
+        assert_eq!(FsyncPolicy::new("every:8").unwrap(), FsyncPolicy::Every(8));
+        assert!(matches!(
+            FsyncPolicy::new("every:0"),
+            Err(CrustError::Storage(_))
+        ));
+        assert!(FsyncPolicy::new("sometimes").is_err());
+    }
+
+    #[test]
+    fn test_restart_replays_the_write_ahead_log() {
+        let path = data_dir("replay");
+        {
+            let store = open(&path, 1000);
+            store.declare("likes", "pncounter").unwrap();
+            store.declare("views", "gcounter").unwrap();
+            store.declare("scratch", "gcounter").unwrap();
+            store.remove("scratch").unwrap();
+            increment(&store, "likes", SyncType::Operation);
+            increment(&store, "likes", SyncType::Delta);
+            increment(&store, "views", SyncType::State);
+            store
+                .with_object("likes", |object| {
+                    object.set_constraints(vec![ConstraintRule::MaxValue(2)], RepairPolicy::Reject)
+                })
+                .unwrap();
+        }
+
+        let store = open(&path, 1000);
+        assert_eq!(
+            store.objects(),
+            vec![
+                ("likes".to_string(), "pncounter".to_string()),
+                ("views".to_string(), "gcounter".to_string()),
+            ]
+        );
+        assert_eq!(value(&store, "likes"), "2");
+        assert_eq!(value(&store, "views"), "1");
+        let mut config = sync_config(SyncType::Operation, SyncMode::Immediate);
+        let rejected = store.with_object("likes", |object| {
+            object.apply_command(
+                &CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+                    value: "replica-1".to_string(),
+                }),
+                &mut config,
+            )
+        });
+        assert!(matches!(rejected, Err(CrustError::ConstraintViolation(_))));
+        let _ = fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_rejected_writes_are_logged_ahead_and_skipped_on_replay() {
+        let memory = MemoryStorage::new();
+        {
+            let store = open_backend(StorageBackend::Memory(memory.clone()), 1000);
+            store.declare("likes", "gcounter").unwrap();
+            increment(&store, "likes", SyncType::State);
+            let logged = memory.log_len();
+            let mut config = sync_config(SyncType::State, SyncMode::Immediate);
+            let rejected = store.with_object("likes", |object| {
+                object.apply_command(
+                    &CrdtInnerCommand::Counter(CounterInnerCommand::Decrement {
+                        value: "replica-1".to_string(),
+                    }),
+                    &mut config,
+                )
+            });
+            assert!(rejected.is_err());
+            assert_eq!(memory.log_len(), logged + 1);
+
+            store
+                .read_object("likes", |object| Ok(object.digest()))
+                .unwrap();
+            assert_eq!(value(&store, "likes"), "1");
+            assert_eq!(memory.log_len(), logged + 1);
+        }
+
+        let store = open_backend(StorageBackend::Memory(memory), 1000);
+        assert_eq!(value(&store, "likes"), "1");
+    }
+
+    #[test]
+    fn test_records_logged_after_a_remove_are_skipped_on_replay() {
+        let path = data_dir("removed");
+        {
+            let store = open(&path, 1000);
+            store.declare("likes", "gcounter").unwrap();
+            increment(&store, "likes", SyncType::State);
+            store.remove("likes").unwrap();
+            store.declare("views", "gcounter").unwrap();
+        }
+        let wal = fs::read_to_string(path.join("wal.log")).unwrap();
+        let mut records: Vec<serde_json::Value> = wal
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let record = records[1]["entry"].take();
+        records[1]["entry"] = records[2]["entry"].take();
+        records[2]["entry"] = record;
+        let wal: String = records.iter().map(|record| format!("{record}\n")).collect();
+        fs::write(path.join("wal.log"), wal).unwrap();
+
+        let store = open(&path, 1000);
+        assert_eq!(
+            store.objects(),
+            vec![("views".to_string(), "gcounter".to_string())]
+        );
+        let _ = fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_snapshots_compact_the_log() {
+        let path = data_dir("snapshot");
+        {
+            let store = open(&path, 3);
+            store.declare("likes", "gcounter").unwrap();
+            for _ in 0..4 {
+                increment(&store, "likes", SyncType::Operation);
+            }
+            let wal = fs::read_to_string(path.join("wal.log")).unwrap();
+            assert_eq!(wal.lines().count(), 2);
+            assert!(path.join("snapshot.json").exists());
+        }
+
+        let store = open(&path, 3);
+        assert_eq!(value(&store, "likes"), "4");
+        assert_eq!(fs::read_to_string(path.join("wal.log")).unwrap(), "");
+        increment(&store, "likes", SyncType::Operation);
+        drop(store);
+        assert_eq!(value(&open(&path, 3), "likes"), "5");
+        let _ = fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_torn_log_tail_is_ignored() {
+        let path = data_dir("torn");
+        {
+            let store = open(&path, 1000);
+            store.declare("likes", "gcounter").unwrap();
+            increment(&store, "likes", SyncType::State);
+        }
+        OpenOptions::new()
+            .append(true)
+            .open(path.join("wal.log"))
+            .unwrap()
+            .write_all(b"{\"sequence\":9,\"entry\":{\"Rec")
+            .unwrap();
+
+        let store = open(&path, 1000);
+        assert_eq!(value(&store, "likes"), "1");
+        increment(&store, "likes", SyncType::State);
+        drop(store);
+        assert_eq!(value(&open(&path, 1000), "likes"), "2");
+        let _ = fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_delivered_operations_stay_deduplicated_after_restart() {
+        let path = data_dir("dedup");
+        let peer = ObjectStore::<String>::new("replica-2".to_string());
+        peer.declare("likes", "gcounter").unwrap();
+        let mut config = sync_config(SyncType::Operation, SyncMode::Immediate);
+        let message = peer
+            .with_object("likes", |object| {
+                object.apply_command(
+                    &CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+                        value: "replica-2".to_string(),
+                    }),
+                    &mut config,
+                )
+            })
+            .unwrap()
+            .unwrap();
+
+        {
+            let store = open(&path, 1);
+            store.declare("likes", "gcounter").unwrap();
+            store
+                .with_object("likes", |object| object.receive(&message))
+                .unwrap();
+        }
+        let store = open(&path, 1);
+        store
+            .with_object("likes", |object| object.receive(&message))
+            .unwrap();
+        assert_eq!(value(&store, "likes"), "1");
+        let _ = fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_undo_history_survives_a_restart() {
+        let backend = StorageBackend::Memory(MemoryStorage::new());
+        let undo = |store: &ObjectStore<String>| {
+            let mut config = sync_config(SyncType::Delta, SyncMode::Immediate);
+            store
+                .with_object("likes", |object| object.undo(&mut config))
+                .unwrap();
+        };
+        {
+            let store = open_backend(backend.clone(), 1);
+            store.declare("likes", "pncounter").unwrap();
+            for _ in 0..3 {
+                increment(&store, "likes", SyncType::Delta);
+            }
+            undo(&store);
+        }
+        let store = open_backend(backend.clone(), 1);
+        assert_eq!(value(&store, "likes"), "2");
+        undo(&store);
+        assert_eq!(value(&store, "likes"), "1");
+        drop(store);
+
+        let store = open_backend(backend, 1);
+        let mut config = sync_config(SyncType::Delta, SyncMode::Immediate);
+        store
+            .with_object("likes", |object| object.redo(&mut config))
+            .unwrap();
+        assert_eq!(value(&store, "likes"), "2");
+    }
+
+    #[test]
+    fn test_restart_with_every_backend() {
+        let path = data_dir("backends");
+        let backends = [
+            StorageBackend::Memory(MemoryStorage::new()),
+            StorageBackend::File(path.join("file")),
+            StorageBackend::new("sqlite", Some(path.clone())).unwrap(),
+        ];
+        for backend in backends {
+            {
+                let store = open_backend(backend.clone(), 2);
+                store.declare("likes", "gcounter").unwrap();
+                for _ in 0..3 {
+                    increment(&store, "likes", SyncType::Delta);
+                }
+            }
+            let store = open_backend(backend, 2);
+            assert_eq!(value(&store, "likes"), "3");
+        }
+        let _ = fs::remove_dir_all(&path);
+    }
+}
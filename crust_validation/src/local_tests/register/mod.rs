@@ -0,0 +1 @@
+mod mvregister_test;
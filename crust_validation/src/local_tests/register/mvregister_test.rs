@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        core::register::mvregister::MVRegister,
+        sync::{Crdt, DeltaBased, StateBased},
+    };
+
+    type StringRegister = MVRegister<String, String>;
+
+    fn sorted(register: &StringRegister) -> Vec<String> {
+        let mut values: Vec<String> = register.read().into_iter().cloned().collect();
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn test_mvregister_write_overwrites_observed_value() {
+        let mut a = StringRegister::new();
+        a.write("a".to_string(), "1".to_string());
+        a.write("a".to_string(), "2".to_string());
+        assert_eq!(sorted(&a), vec!["2".to_string()]);
+    }
+
+    #[test]
+    fn test_mvregister_keeps_concurrent_values() {
+        let mut a = StringRegister::new();
+        let mut b = StringRegister::new();
+        a.write("a".to_string(), "1".to_string());
+        b.write("b".to_string(), "2".to_string());
+        a.merge(&b);
+        assert_eq!(sorted(&a), vec!["1".to_string(), "2".to_string()]);
+
+        a.write("a".to_string(), "3".to_string());
+        b.merge(&a);
+        assert_eq!(sorted(&b), vec!["3".to_string()]);
+    }
+
+    #[test]
+    fn test_mvregister_delta_matches_state_merge() {
+        let mut a = StringRegister::new();
+        let mut b = StringRegister::new();
+        b.write("b".to_string(), "0".to_string());
+        a.merge(&b);
+        let delta = a.write("a".to_string(), "1".to_string());
+        let via_delta = b.clone().merge_delta(&delta);
+        let via_state = b.merge(&a);
+        assert_eq!(via_delta, via_state);
+        assert_eq!(sorted(&via_delta), vec!["1".to_string()]);
+    }
+
+    #[test]
+    fn test_mvregister_clear() {
+        let mut a = StringRegister::new();
+        a.write("a".to_string(), "1".to_string());
+        let mut b = a.clone();
+        b.clear();
+        a.merge(&b);
+        assert!(a.read().is_empty());
+    }
+}
@@ -0,0 +1,192 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crust_core::{
+        command::{CounterInnerCommand, CrdtInnerCommand, SetInnerCommand},
+        error::CrustError,
+        operation::{CrdtOperation, SetOperation},
+        r#type::CrdtType,
+        registry::{
+            create_crdt_object, from_value, is_registered, register_crdt_type,
+            registered_crdt_types, to_value, CrdtObject,
+        },
+    };
+    use serde_json::{json, Value};
+
+    #[derive(Clone, Default)]
+    struct GSet {
+        elements: BTreeSet<String>,
+    }
+
+    impl CrdtObject<String> for GSet {
+        fn type_name(&self) -> String {
+            "gset".to_string()
+        }
+
+        fn commands(&self, value: String) -> Vec<CrdtInnerCommand<String>> {
+            vec![CrdtInnerCommand::Set(SetInnerCommand::Add { value })]
+        }
+
+        fn apply_command(
+            &mut self,
+            command: &CrdtInnerCommand<String>,
+        ) -> Result<CrdtOperation<String>, CrustError> {
+            match command {
+                CrdtInnerCommand::Set(SetInnerCommand::Add { value }) => {
+                    self.elements.insert(value.clone());
+                    Ok(CrdtOperation::Set(SetOperation::Add {
+                        value: value.clone(),
+                    }))
+                }
+                _ => Err(CrustError::InvalidCommand {
+                    crdt_type: self.type_name(),
+                    command: format!("{:?}", command),
+                }),
+            }
+        }
+
+        fn apply(&mut self, operation: &CrdtOperation<String>) -> Result<(), CrustError> {
+            match operation {
+                CrdtOperation::Set(SetOperation::Add { value }) => {
+                    self.elements.insert(value.clone());
+                    Ok(())
+                }
+                _ => Err(CrustError::InvalidOperation(format!("{:?}", operation))),
+            }
+        }
+
+        fn get_state(&self) -> Value {
+            json!({ "elements": self.elements })
+        }
+
+        fn to_state(&self) -> Result<Value, CrustError> {
+            to_value(&self.elements)
+        }
+
+        fn merge(&mut self, state: &Value) -> Result<(), CrustError> {
+            let elements: BTreeSet<String> = from_value(state)?;
+            self.elements.extend(elements);
+            Ok(())
+        }
+
+        fn generate_delta(&self) -> Result<Value, CrustError> {
+            self.to_state()
+        }
+
+        fn merge_delta(&mut self, delta: &Value) -> Result<(), CrustError> {
+            self.merge(delta)
+        }
+
+        fn aggregate_deltas(&self, deltas: Vec<Value>) -> Result<Option<Value>, CrustError> {
+            if deltas.is_empty() {
+                return Ok(None);
+            }
+            let mut aggregate = GSet::default();
+            for delta in &deltas {
+                aggregate.merge(delta)?;
+            }
+            aggregate.to_state().map(Some)
+        }
+
+        fn clone_object(&self) -> Box<dyn CrdtObject<String>> {
+            Box::new(self.clone())
+        }
+    }
+
+    fn register_gset() {
+        if !is_registered::<String>("gset") {
+            let _ = register_crdt_type::<String>("gset", || Box::new(GSet::default()));
+        }
+    }
+
+    #[test]
+    fn test_builtin_types_are_registered() {
+        assert!(is_registered::<String>("gcounter"));
+        assert!(registered_crdt_types::<String>().contains(&"gcounter".to_string()));
+        assert_eq!(
+            create_crdt_object::<String>("missing").err(),
+            Some(CrustError::UnknownCrdtType("missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_duplicate_registration_is_rejected() {
+        assert_eq!(
+            register_crdt_type::<String>("gcounter", || Box::new(GSet::default())),
+            Err(CrustError::DuplicateCrdtType("gcounter".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_concurrent_registrations_admit_exactly_one() {
+        let registrations: Vec<_> = (0..8)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    register_crdt_type::<String>("racing-gset", || Box::new(GSet::default()))
+                })
+            })
+            .collect();
+        let admitted = registrations
+            .into_iter()
+            .map(|registration| registration.join().unwrap())
+            .filter(Result::is_ok)
+            .count();
+        assert_eq!(admitted, 1);
+        assert!(is_registered::<String>("racing-gset"));
+    }
+
+    #[test]
+    fn test_registered_type_replicates_through_crdt_type() {
+        register_gset();
+        let mut a = CrdtType::<String>::new("gset".to_string()).unwrap();
+        let mut b = CrdtType::<String>::new("gset".to_string()).unwrap();
+
+        let add = a.validate_command("x".to_string()).remove(0);
+        let operation = a.apply_command(&add).unwrap();
+        b.apply(&operation).unwrap();
+        b.apply_command(&CrdtInnerCommand::Set(SetInnerCommand::Add {
+            value: "y".to_string(),
+        }))
+        .unwrap();
+
+        let delta = b.generate_delta().unwrap();
+        a.merge_delta(&delta).unwrap();
+        assert_eq!(a.get_state(), json!({ "elements": ["x", "y"] }));
+        assert!(a
+            .apply_command(&CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+                value: "a".to_string(),
+            }))
+            .is_err());
+    }
+
+    #[test]
+    fn test_crdt_type_serialization_roundtrip() {
+        register_gset();
+        let mut crdt = CrdtType::<String>::new("gset".to_string()).unwrap();
+        crdt.apply_command(&CrdtInnerCommand::Set(SetInnerCommand::Add {
+            value: "x".to_string(),
+        }))
+        .unwrap();
+
+        let encoded = serde_json::to_string(&crdt).unwrap();
+        let decoded: CrdtType<String> = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(decoded.name(), "gset");
+        assert_eq!(decoded.get_state(), crdt.get_state());
+    }
+
+    #[test]
+    fn test_merging_different_types_is_a_type_mismatch() {
+        register_gset();
+        let mut counter = CrdtType::<String>::new("gcounter".to_string()).unwrap();
+        let set = CrdtType::<String>::new("gset".to_string()).unwrap();
+        assert_eq!(
+            counter.merge(&set),
+            Err(CrustError::TypeMismatch {
+                expected: "gcounter".to_string(),
+                found: "gset".to_string(),
+            })
+        );
+        assert!(counter.merge_delta(&set.generate_delta().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,2 @@
+mod crdt_registry_test;
+mod schema_test;
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        command::{CounterInnerCommand, CrdtInnerCommand},
+        error::CrustError,
+        r#type::CrdtType,
+        schema::{migrate_state, register_migration, LEGACY_SCHEMA_VERSION, SCHEMA_VERSION},
+    };
+    use serde_json::{json, Value};
+
+    fn rename_total(mut state: Value) -> Result<Value, CrustError> {
+        if let Some(total) = state
+            .as_object_mut()
+            .and_then(|state| state.remove("total"))
+        {
+            state["sum"] = total;
+        }
+        Ok(state)
+    }
+
+    #[test]
+    fn test_serialized_states_carry_the_schema_version() {
+        let mut crdt = CrdtType::<String>::new("gcounter".to_string()).unwrap();
+        crdt.apply_command(&CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+            value: "replica-1".to_string(),
+        }))
+        .unwrap();
+        let serialized = serde_json::to_value(&crdt).unwrap();
+        assert_eq!(serialized["version"], SCHEMA_VERSION);
+
+        let decoded: CrdtType<String> = serde_json::from_value(serialized).unwrap();
+        assert_eq!(decoded.get_state()["value"], "1");
+    }
+
+    #[test]
+    fn test_legacy_states_are_migrated() {
+        let legacy = json!({
+            "crdt_type": "gcounter",
+            "state": {"counter": {"replica-1": 2, "replica-2": 1}},
+        });
+        let crdt: CrdtType<String> = serde_json::from_value(legacy).unwrap();
+        assert_eq!(crdt.get_state()["value"], "3");
+
+        let migrated = migrate_state(
+            "gcounter",
+            LEGACY_SCHEMA_VERSION,
+            json!({"counter": {"replica-1": 2}}),
+        )
+        .unwrap();
+        assert_eq!(migrated["retirement"]["epoch"], 0);
+        assert_eq!(migrated["counter"]["replica-1"], 2);
+    }
+
+    #[test]
+    fn test_registered_migrations_run_in_order() {
+        register_migration("summing-counter", LEGACY_SCHEMA_VERSION, rename_total).unwrap();
+        assert_eq!(
+            migrate_state(
+                "summing-counter",
+                LEGACY_SCHEMA_VERSION,
+                json!({"total": 4})
+            )
+            .unwrap(),
+            json!({"sum": 4})
+        );
+        assert_eq!(
+            migrate_state("summing-counter", SCHEMA_VERSION, json!({"total": 4})).unwrap(),
+            json!({"total": 4})
+        );
+        assert_eq!(
+            register_migration("summing-counter", SCHEMA_VERSION, rename_total),
+            Err(CrustError::IncompatibleSchema {
+                found: SCHEMA_VERSION,
+                supported: SCHEMA_VERSION,
+            })
+        );
+    }
+
+    #[test]
+    fn test_newer_versions_are_rejected() {
+        let future = json!({
+            "version": SCHEMA_VERSION + 1,
+            "crdt_type": "gcounter",
+            "state": {"counter": {}},
+        });
+        let error = serde_json::from_value::<CrdtType<String>>(future)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains(&format!(
+            "schema version {} is incompatible",
+            SCHEMA_VERSION + 1
+        )));
+        assert!(matches!(
+            migrate_state("gcounter", 0, json!({})),
+            Err(CrustError::IncompatibleSchema { found: 0, .. })
+        ));
+    }
+}
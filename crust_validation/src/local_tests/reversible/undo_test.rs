@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod tests {
+    use crust_core::{
+        command::{CounterInnerCommand, CrdtInnerCommand, SetInnerCommand},
+        error::CrustError,
+        r#type::CrdtType,
+        sync::{SyncMode, SyncType},
+    };
+    use crust_network::{
+        message::NetworkMessage, object_store::ObjectStore, receiver::sync_config,
+    };
+    use serde_json::json;
+
+    fn increment(value: &str) -> CrdtInnerCommand<String> {
+        CrdtInnerCommand::Counter(CounterInnerCommand::Increment {
+            value: value.to_string(),
+        })
+    }
+
+    fn counter_value(store: &ObjectStore<String>) -> String {
+        store
+            .with_object("likes", |object| Ok(object.get_state()["value"].clone()))
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    fn replica(name: &str) -> ObjectStore<String> {
+        let store = ObjectStore::<String>::new(name.to_string());
+        store.declare("likes", "pncounter").unwrap();
+        store
+    }
+
+    fn command(store: &ObjectStore<String>, value: &str) -> NetworkMessage<String> {
+        let mut config = sync_config(SyncType::Operation, SyncMode::Immediate);
+        store
+            .with_object("likes", |object| {
+                object.apply_command(&increment(value), &mut config)
+            })
+            .unwrap()
+            .unwrap()
+    }
+
+    fn undo(store: &ObjectStore<String>) -> NetworkMessage<String> {
+        let mut config = sync_config(SyncType::Operation, SyncMode::Immediate);
+        store
+            .with_object("likes", |object| object.undo(&mut config))
+            .unwrap()
+            .unwrap()
+    }
+
+    fn receive(store: &ObjectStore<String>, message: &NetworkMessage<String>) {
+        store
+            .with_object("likes", |object| object.receive(message))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_undo_and_redo_local_operations() {
+        let mut crdt = CrdtType::<String>::new("pncounter".to_string()).unwrap();
+        crdt.apply_command(&increment("a")).unwrap();
+        crdt.apply_command(&increment("a")).unwrap();
+        assert_eq!(crdt.get_state()["value"], "2");
+
+        crdt.undo().unwrap();
+        crdt.undo().unwrap();
+        assert_eq!(crdt.get_state()["value"], "0");
+        assert!(matches!(crdt.undo(), Err(CrustError::InvalidOperation(_))));
+
+        crdt.redo().unwrap();
+        assert_eq!(crdt.get_state()["value"], "1");
+
+        crdt.apply_command(&increment("a")).unwrap();
+        assert!(matches!(crdt.redo(), Err(CrustError::InvalidOperation(_))));
+        assert_eq!(crdt.operation_history.len(), 2);
+    }
+
+    #[test]
+    fn test_remote_operations_are_not_undone() {
+        let mut crdt = CrdtType::<String>::new("pncounter".to_string()).unwrap();
+        let mut remote = CrdtType::<String>::new("pncounter".to_string()).unwrap();
+        let operation = remote.apply_command(&increment("b")).unwrap();
+        crdt.apply(&operation).unwrap();
+        assert!(crdt.undo().is_err());
+        assert_eq!(crdt.get_state()["value"], "1");
+    }
+
+    #[test]
+    fn test_irreversible_operations_stay_in_history() {
+        let mut crdt = CrdtType::<String>::new("gcounter".to_string()).unwrap();
+        crdt.apply_command(&increment("a")).unwrap();
+        assert!(matches!(crdt.undo(), Err(CrustError::NotReversible(_))));
+        assert!(matches!(crdt.redo(), Err(CrustError::NotReversible(_))));
+        assert!(matches!(
+            crdt.revert_operation(0),
+            Err(CrustError::NotReversible(_))
+        ));
+        assert_eq!(crdt.operation_history.len(), 1);
+        assert_eq!(crdt.get_state()["value"], "1");
+    }
+
+    #[test]
+    fn test_undo_and_redo_set_additions_and_removals() {
+        let mut crdt = CrdtType::<String>::new("orset".to_string()).unwrap();
+        crdt.assign_replica("a");
+        for value in ["x", "y"] {
+            crdt.apply_command(&CrdtInnerCommand::Set(SetInnerCommand::Add {
+                value: value.to_string(),
+            }))
+            .unwrap();
+        }
+        crdt.apply_command(&CrdtInnerCommand::Set(SetInnerCommand::Remove {
+            value: "x".to_string(),
+        }))
+        .unwrap();
+        assert_eq!(crdt.get_state()["value"], json!(["y"]));
+
+        crdt.undo().unwrap();
+        assert_eq!(crdt.get_state()["value"], json!(["x", "y"]));
+        crdt.undo().unwrap();
+        assert_eq!(crdt.get_state()["value"], json!(["x"]));
+
+        crdt.redo().unwrap();
+        assert_eq!(crdt.get_state()["value"], json!(["x", "y"]));
+        assert_eq!(crdt.operation_history.len(), 2);
+    }
+
+    #[test]
+    fn test_revert_operation_by_index() {
+        let mut crdt = CrdtType::<String>::new("pncounter".to_string()).unwrap();
+        crdt.apply_command(&increment("a")).unwrap();
+        crdt.apply_command(&increment("b")).unwrap();
+        crdt.revert_operation(0).unwrap();
+        assert_eq!(
+            crdt.get_state()["state"],
+            json!({"increments": {"a": 1, "b": 1}, "decrements": {"a": 1}})
+        );
+        assert_eq!(crdt.operation_history.len(), 1);
+        assert!(crdt.revert_operation(5).is_err());
+    }
+
+    #[test]
+    fn test_undo_keeps_concurrent_remote_updates() {
+        let a = replica("a");
+        let b = replica("b");
+        let from_a = command(&a, "a");
+        let from_b = command(&b, "b");
+        receive(&a, &from_b);
+        receive(&b, &from_a);
+
+        let undo_a = undo(&a);
+        receive(&b, &undo_a);
+
+        assert_eq!(counter_value(&a), "1");
+        assert_eq!(counter_value(&b), "1");
+    }
+
+    #[test]
+    fn test_undo_is_delivered_after_the_undone_operation() {
+        let a = replica("a");
+        let c = replica("c");
+        let original = command(&a, "a");
+        let undo_a = undo(&a);
+
+        receive(&c, &undo_a);
+        assert_eq!(
+            c.with_object("likes", |object| Ok(object.get_state()["state"].clone()))
+                .unwrap(),
+            json!({"increments": {}, "decrements": {}})
+        );
+
+        receive(&c, &original);
+        assert_eq!(
+            c.with_object("likes", |object| Ok(object.get_state()["state"].clone()))
+                .unwrap(),
+            json!({"increments": {"a": 1}, "decrements": {"a": 1}})
+        );
+        assert_eq!(counter_value(&c), "0");
+    }
+}
@@ -0,0 +1,163 @@
+mod tests {
+    use crust_core::{
+        core::set::awset::AWSet,
+        sync::{Crdt, DeltaBased, StateBased},
+    };
+
+    use crate::local_validation::{DeltaBasedValidation, StateBasedValidation};
+
+    type StringSet = AWSet<String, String>;
+
+    impl StateBasedValidation<StringSet> for StringSet {
+        fn state_associativity() -> bool {
+            let mut a = StringSet::new();
+            let mut b = StringSet::new();
+            let mut c = StringSet::new();
+            a.add("a".to_string(), "x".to_string());
+            b.add("b".to_string(), "y".to_string());
+            c.add("c".to_string(), "x".to_string());
+            c.remove(&"x".to_string());
+            let ab_c = a.clone().merge(&b).merge(&c);
+            let a_bc = a.merge(&b.merge(&c));
+            ab_c == a_bc
+        }
+
+        fn state_commutativity() -> bool {
+            let mut a = StringSet::new();
+            let mut b = StringSet::new();
+            a.add("a".to_string(), "x".to_string());
+            b.add("b".to_string(), "x".to_string());
+            b.remove(&"x".to_string());
+            let ab = a.clone().merge(&b);
+            let ba = b.merge(&a);
+            ab == ba
+        }
+
+        fn state_idempotence() -> bool {
+            let mut a = StringSet::new();
+            a.add("a".to_string(), "x".to_string());
+            let aa = a.clone().merge(&a);
+            aa == a
+        }
+
+        fn state_monotonicity() -> bool {
+            let mut a = StringSet::new();
+            let mut b = StringSet::new();
+            a.add("a".to_string(), "x".to_string());
+            let before = a.clone();
+            b.add("b".to_string(), "y".to_string());
+            let after = a.merge(&b);
+            after.state.context.dominates(&before.state.context)
+        }
+    }
+
+    impl DeltaBasedValidation<StringSet> for StringSet {
+        fn delta_associativity() -> bool {
+            let mut set = StringSet::new();
+            let delta_a = set.add("a".to_string(), "x".to_string());
+            let delta_b = set.add("a".to_string(), "y".to_string());
+            let delta_c = set.remove(&"x".to_string());
+            let mut set1 = StringSet::new();
+            set1.merge_delta(&delta_a);
+            set1.merge_delta(&delta_b);
+            set1.merge_delta(&delta_c);
+            let mut set2 = StringSet::new();
+            let combined = set2
+                .aggregate_deltas(vec![delta_b.clone(), delta_c.clone()])
+                .unwrap();
+            set2.merge_delta(&delta_a);
+            set2.merge_delta(&combined);
+            set1 == set2 && set1 == set
+        }
+
+        fn delta_commutativity() -> bool {
+            let mut a = StringSet::new();
+            let delta1 = a.add("a".to_string(), "x".to_string());
+            let delta2 = a.add("a".to_string(), "y".to_string());
+            let a1 = StringSet::new().merge_delta(&delta1).merge_delta(&delta2);
+            let a2 = StringSet::new().merge_delta(&delta2).merge_delta(&delta1);
+            a1 == a2
+        }
+
+        fn delta_idempotence() -> bool {
+            let mut a = StringSet::new();
+            let delta = a.add("a".to_string(), "x".to_string());
+            let a1 = a.clone().merge_delta(&delta).merge_delta(&delta);
+            a1 == a
+        }
+
+        fn delta_state_composability() -> bool {
+            let mut a = StringSet::new();
+            let mut b = StringSet::new();
+            a.add("a".to_string(), "x".to_string());
+            a.add("a".to_string(), "y".to_string());
+            let delta = a.generate_delta();
+            b.add("b".to_string(), "z".to_string());
+            let path1 = b.clone().merge_delta(&delta);
+            let path2 = b.merge(&a);
+            path1 == path2
+        }
+    }
+
+    #[test]
+    fn test_awset_state_associativity() {
+        assert!(StringSet::state_associativity());
+    }
+
+    #[test]
+    fn test_awset_state_commutativity() {
+        assert!(StringSet::state_commutativity());
+    }
+
+    #[test]
+    fn test_awset_state_idempotence() {
+        assert!(StringSet::state_idempotence());
+    }
+
+    #[test]
+    fn test_awset_state_monotonicity() {
+        assert!(StringSet::state_monotonicity());
+    }
+
+    #[test]
+    fn test_awset_delta_associativity() {
+        assert!(StringSet::delta_associativity());
+    }
+
+    #[test]
+    fn test_awset_delta_commutativity() {
+        assert!(StringSet::delta_commutativity());
+    }
+
+    #[test]
+    fn test_awset_delta_idempotence() {
+        assert!(StringSet::delta_idempotence());
+    }
+
+    #[test]
+    fn test_awset_delta_state_composability() {
+        assert!(StringSet::delta_state_composability());
+    }
+
+    #[test]
+    fn test_awset_concurrent_add_wins_over_remove() {
+        let mut a = StringSet::new();
+        a.add("a".to_string(), "x".to_string());
+        let mut b = a.clone();
+        a.remove(&"x".to_string());
+        b.add("b".to_string(), "x".to_string());
+        a.merge(&b);
+        assert!(a.contains(&"x".to_string()));
+    }
+
+    #[test]
+    fn test_awset_observed_remove() {
+        let mut a = StringSet::new();
+        a.add("a".to_string(), "x".to_string());
+        let mut b = a.clone();
+        b.remove(&"x".to_string());
+        a.merge(&b);
+        assert!(!a.contains(&"x".to_string()));
+        assert!(a.is_empty());
+    }
+}
@@ -0,0 +1 @@
+mod awset_test;
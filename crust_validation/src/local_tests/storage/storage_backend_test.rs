@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::{self, OpenOptions},
+        io::Write,
+        path::{Path, PathBuf},
+    };
+
+    use crust_core::error::CrustError;
+    use crust_storage::{
+        file::FileStorage, memory::MemoryStorage, sqlite::SqliteStorage, Checkpoint, FsyncPolicy,
+        LogRecord, Storage, StorageBackend,
+    };
+    use serde_json::json;
+
+    fn data_dir(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("crust-storage-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        path
+    }
+
+    fn record(sequence: u64) -> LogRecord {
+        LogRecord {
+            sequence,
+            entry: json!({"sequence": sequence}),
+        }
+    }
+
+    fn exercise(storage: &mut dyn Storage) {
+        assert_eq!(storage.load_checkpoint().unwrap(), None);
+        assert!(storage.read_log().unwrap().is_empty());
+
+        storage.append_log(&[record(1), record(2)]).unwrap();
+        storage.append_log(&[record(3)]).unwrap();
+        assert_eq!(
+            storage.read_log().unwrap(),
+            vec![record(1), record(2), record(3)]
+        );
+
+        let checkpoint = Checkpoint {
+            sequence: 3,
+            objects: [("likes".to_string(), json!({"value": 3}))].into(),
+        };
+        storage.save_checkpoint(&checkpoint).unwrap();
+        assert_eq!(storage.load_checkpoint().unwrap(), Some(checkpoint));
+        assert!(storage.read_log().unwrap().is_empty());
+
+        storage.append_log(&[record(4)]).unwrap();
+        assert_eq!(storage.read_log().unwrap(), vec![record(4)]);
+    }
+
+    #[test]
+    fn test_memory_storage() {
+        let memory = MemoryStorage::new();
+        exercise(&mut memory.clone());
+        assert_eq!(memory.log_len(), 1);
+    }
+
+    #[test]
+    fn test_file_storage() {
+        let path = data_dir("file");
+        exercise(&mut FileStorage::open(&path, FsyncPolicy::Every(2)).unwrap());
+        let mut reopened = FileStorage::open(&path, FsyncPolicy::Always).unwrap();
+        assert_eq!(reopened.read_log().unwrap(), vec![record(4)]);
+        assert_eq!(reopened.load_checkpoint().unwrap().unwrap().sequence, 3);
+        let _ = fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_file_storage_truncates_a_torn_log_tail() {
+        let path = data_dir("torn");
+        let mut storage = FileStorage::open(&path, FsyncPolicy::Always).unwrap();
+        storage.append_log(&[record(1), record(2)]).unwrap();
+        drop(storage);
+        OpenOptions::new()
+            .append(true)
+            .open(path.join("wal.log"))
+            .unwrap()
+            .write_all(b"{\"sequence\":3,\"entry\":{\"seq")
+            .unwrap();
+
+        let mut reopened = FileStorage::open(&path, FsyncPolicy::Always).unwrap();
+        assert_eq!(reopened.read_log().unwrap(), vec![record(1), record(2)]);
+        reopened.append_log(&[record(3)]).unwrap();
+        drop(reopened);
+
+        let mut reopened = FileStorage::open(&path, FsyncPolicy::Always).unwrap();
+        assert_eq!(
+            reopened.read_log().unwrap(),
+            vec![record(1), record(2), record(3)]
+        );
+        let _ = fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_sqlite_storage() {
+        exercise(&mut SqliteStorage::open_in_memory().unwrap());
+
+        let path = data_dir("sqlite");
+        let backend = StorageBackend::new("sqlite", Some(path.clone())).unwrap();
+        exercise(backend.open(FsyncPolicy::Always).unwrap().as_mut());
+        let mut reopened = backend.open(FsyncPolicy::Never).unwrap();
+        assert_eq!(reopened.read_log().unwrap(), vec![record(4)]);
+        let _ = fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_backends_are_selected_by_name() {
+        assert!(matches!(
+            StorageBackend::new("memory", None),
+            Ok(StorageBackend::Memory(_))
+        ));
+        assert!(matches!(
+            StorageBackend::new("file", Some(PathBuf::from("data"))),
+            Ok(StorageBackend::File(path)) if path == Path::new("data")
+        ));
+        assert!(matches!(
+            StorageBackend::new("file", None),
+            Err(CrustError::Storage(_))
+        ));
+        assert!(matches!(
+            StorageBackend::new("tape", None),
+            Err(CrustError::Storage(_))
+        ));
+    }
+}
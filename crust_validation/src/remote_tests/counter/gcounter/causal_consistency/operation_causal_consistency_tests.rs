@@ -1,5 +1,5 @@
 mod operation_casual_consistency {
-    use std::{collections::HashMap, time::Duration};
+    use std::time::Duration;
 
     use crust_config::instance::{
         get_state_from_instance, send_command_to_instance, setup_remote_test_environement,
@@ -8,9 +8,8 @@ mod operation_casual_consistency {
     use crust_core::{
         command::{CounterInnerCommand, CrdtInnerCommand},
         core::counter::gcounter::GCounter,
-        r#type::{CrdtType, CrdtTypeVariant},
     };
-    use futures::future::try_join_all;
+    
 
     use crate::remote_validation::OperationBasedDistributedCausalConsistencyValidation;
 
@@ -24,13 +23,14 @@ mod operation_casual_consistency {
             let deployment_name = "crust-network";
             let replicas = 2; 
             let test_config = DeploymentConfig::new(
-                replicas.try_into().unwrap(),
+                replicas,
                 "gcounter",
                 "operation", 
                 "immediate", 
                 None,
                 None,
-            );
+            )
+            .unwrap();
 
             let service_urls = setup_remote_test_environement(&test_config).await;
             update_replicas(client, namespace, deployment_name, replicas).await;
@@ -176,7 +176,7 @@ mod operation_casual_consistency {
                             instance_id, counter_value
                         );
 
-                        if actual_value != &expected_final_value {
+                        if actual_value != expected_final_value {
                             eprintln!(
                                 "❌ Failed: Instance {} has incorrect value: {} (expected {})",
                                 instance_id, actual_value, expected_final_value
@@ -215,13 +215,14 @@ mod operation_casual_consistency {
             let deployment_name = "crust-network";
             let replicas = 3; 
             let test_config = DeploymentConfig::new(
-                replicas.try_into().unwrap(),
+                replicas,
                 "gcounter",
                 "operation", 
                 "immediate", 
                 None,
                 None,
-            );
+            )
+            .unwrap();
             let service_urls = setup_remote_test_environement(&test_config).await;
             update_replicas(client, namespace, deployment_name, replicas).await;
 
@@ -489,13 +490,14 @@ mod operation_casual_consistency {
             let deployment_name = "crust-network";
             let replicas = 3;
             let test_config = DeploymentConfig::new(
-                replicas.try_into().unwrap(),
+                replicas,
                 "gcounter",
                 "operation", 
                 "immediate", 
                 None,
                 None,
-            );
+            )
+            .unwrap();
             let service_urls = setup_remote_test_environement(&test_config).await;
             update_replicas(client, namespace, deployment_name, replicas).await;
 
@@ -814,13 +816,14 @@ mod operation_casual_consistency {
             let deployment_name = "crust-network";
             let replicas = 3;
             let test_config = DeploymentConfig::new(
-                replicas.try_into().unwrap(),
+                replicas,
                 "gcounter",
                 "operation", 
                 "immediate", 
                 None,
                 None,
-            );
+            )
+            .unwrap();
             let service_urls = setup_remote_test_environement(&test_config).await;
             update_replicas(client, namespace, deployment_name, replicas).await;
 
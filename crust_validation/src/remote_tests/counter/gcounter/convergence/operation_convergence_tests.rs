@@ -8,7 +8,7 @@ mod operation_convergence {
     use crust_core::{
         command::{CounterInnerCommand, CrdtInnerCommand},
         core::counter::gcounter::GCounter,
-        r#type::{CrdtType, CrdtTypeVariant},
+        r#type::CrdtType,
     };
     use futures::future::try_join_all;
 
@@ -24,13 +24,14 @@ mod operation_convergence {
             let deployment_name = "crust-network";
             let replicas = 3;
             let test_config = DeploymentConfig::new(
-                replicas.try_into().unwrap(),
+                replicas,
                 "gcounter",
                 "operation", 
                 "immediate", 
                 None,
                 None,
-            );
+            )
+            .unwrap();
             let service_urls = setup_remote_test_environement(&test_config).await;
             update_replicas(client, namespace, deployment_name, replicas).await;
 
@@ -134,8 +135,8 @@ mod operation_convergence {
 
             let mut all_converged = true;
             for (instance_id, state) in final_states.iter() {
-                match &state.variant {
-                    CrdtTypeVariant::GCounter(_) => {
+                match state.name().as_str() {
+                    "gcounter" => {
                         let counter_value = state.get_state();
                         let actual_value = counter_value["value"].as_str().unwrap_or("0");
 
@@ -190,13 +191,14 @@ mod operation_convergence {
             let deployment_name = "crust-network";
             let replicas = 2;
             let test_config = DeploymentConfig::new(
-                replicas.try_into().unwrap(),
+                replicas,
                 "gcounter",
                 "operation", 
                 "immediate", 
                 None,
                 None,
-            );
+            )
+            .unwrap();
             let service_urls = setup_remote_test_environement(&test_config).await;
             update_replicas(client, namespace, deployment_name, replicas).await;
 
@@ -248,7 +250,7 @@ mod operation_convergence {
                 "  → Checking if operation was applied locally to instance {}",
                 instance_1
             );
-            let state_1 =
+            let _state_1 =
                 match get_state_from_instance(instance_1, &test_config.crdt_type, &service_url_1)
                     .await
                 {
@@ -269,7 +271,7 @@ mod operation_convergence {
                 "→ Checking if instance {} has received the operation yet",
                 instance_2
             );
-            let intermediate_state_2 =
+            let _intermediate_state_2 =
                 match get_state_from_instance(instance_2, &test_config.crdt_type, &service_url_2)
                     .await
                 {
@@ -327,7 +329,7 @@ mod operation_convergence {
                 "→ Checking local state of instance {} after applying its operation",
                 instance_2
             );
-            let state_2_after_local =
+            let _state_2_after_local =
                 match get_state_from_instance(instance_2, &test_config.crdt_type, &service_url_2)
                     .await
                 {
@@ -443,13 +445,14 @@ mod operation_convergence {
             let deployment_name = "crust-network";
             let replicas = 3;
             let test_config = DeploymentConfig::new(
-                replicas.try_into().unwrap(),
+                replicas,
                 "gcounter",
                 "operation", 
                 "immediate", 
                 None,
                 None,
-            );
+            )
+            .unwrap();
             let service_urls = setup_remote_test_environement(&test_config).await;
             update_replicas(client, namespace, deployment_name, replicas).await;
 
@@ -573,8 +576,8 @@ mod operation_convergence {
 
             let mut all_converged = true;
             for (instance_id, state) in final_states.iter() {
-                match &state.variant {
-                    CrdtTypeVariant::GCounter(_) => {
+                match state.name().as_str() {
+                    "gcounter" => {
                         let counter_value = state.get_state();
                         let actual_value = counter_value["value"].as_str().unwrap_or("0");
 
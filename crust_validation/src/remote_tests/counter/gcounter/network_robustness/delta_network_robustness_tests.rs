@@ -9,10 +9,9 @@ mod delta_network_robustness {
     use crust_core::{
         command::{CounterInnerCommand, CrdtInnerCommand},
         core::counter::gcounter::GCounter,
-        r#type::{CrdtType, CrdtTypeVariant},
     };
-    use futures::future::try_join_all;
-    use rand::{seq::SliceRandom, Rng};
+    
+    
 
     use crate::remote_validation::DeltaBasedDistributedNetworkRobustnessValidation;
 
@@ -26,13 +25,14 @@ mod delta_network_robustness {
             let deployment_name = "crust-network";
             let replicas = 3;
             let test_config = DeploymentConfig::new(
-                replicas.try_into().unwrap(),
+                replicas,
                 "gcounter",
                 "delta", 
                 "immediate",
                 None,
                 None,
-            );
+            )
+            .unwrap();
             let service_urls = setup_remote_test_environement(&test_config).await;
             update_replicas(client, namespace, deployment_name, replicas).await;
 
@@ -273,13 +273,14 @@ mod delta_network_robustness {
             let deployment_name = "crust-network";
             let replicas = 3;
             let test_config = DeploymentConfig::new(
-                replicas.try_into().unwrap(),
+                replicas,
                 "gcounter",
                 "delta",     
                 "immediate", 
                 None,
                 None,
-            );
+            )
+            .unwrap();
             let service_urls = setup_remote_test_environement(&test_config).await;
             update_replicas(client, namespace, deployment_name, replicas).await;
 
@@ -306,11 +307,9 @@ mod delta_network_robustness {
 
             
             println!("\nStep 3/7: Preparing operation sequence");
-            let operations = vec![
-                ("5".to_string(), 1), 
+            let operations = [("5".to_string(), 1), 
                 ("3".to_string(), 2), 
-                ("7".to_string(), 3), 
-            ];
+                ("7".to_string(), 3)];
             println!("→ Operation sequence: increment by 5, then 3, then 7");
 
             println!("\nStep 4/7: Sending operations to source instance in reverse order");
@@ -491,13 +490,14 @@ mod delta_network_robustness {
             let deployment_name = "crust-network";
             let replicas = 3;
             let test_config = DeploymentConfig::new(
-                replicas.try_into().unwrap(),
+                replicas,
                 "gcounter",
                 "delta", 
                 "immediate",
                 None,
                 None,
-            );
+            )
+            .unwrap();
             let service_urls = setup_remote_test_environement(&test_config).await;
             update_replicas(client, namespace, deployment_name, replicas).await;
 
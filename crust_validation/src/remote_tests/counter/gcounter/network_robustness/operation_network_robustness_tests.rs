@@ -9,9 +9,8 @@ mod operation_network_robustness {
     use crust_core::{
         command::{CounterInnerCommand, CrdtInnerCommand},
         core::counter::gcounter::GCounter,
-        r#type::{CrdtType, CrdtTypeVariant},
     };
-    use futures::future::try_join_all;
+    
 
     use crate::remote_validation::OperationBasedDistributedNetworkRobustnessValidation;
 
@@ -25,13 +24,14 @@ mod operation_network_robustness {
             let deployment_name = "crust-network";
             let replicas = 3;
             let test_config = DeploymentConfig::new(
-                replicas.try_into().unwrap(),
+                replicas,
                 "gcounter",
                 "operation",
                 "immediate",
                 None,
                 None,
-            );
+            )
+            .unwrap();
             let service_urls = setup_remote_test_environement(&test_config).await;
             update_replicas(client, namespace, deployment_name, replicas).await;
 
@@ -266,13 +266,14 @@ mod operation_network_robustness {
             let deployment_name = "crust-network";
             let replicas = 3;
             let test_config = DeploymentConfig::new(
-                replicas.try_into().unwrap(),
+                replicas,
                 "gcounter",
                 "operation",
                 "immediate",
                 None,
                 None,
-            );
+            )
+            .unwrap();
             let service_urls = setup_remote_test_environement(&test_config).await;
             update_replicas(client, namespace, deployment_name, replicas).await;
 
@@ -297,11 +298,9 @@ mod operation_network_robustness {
             println!("→ Later operations will be delivered before earlier ones");
 
             println!("\nStep 3/7: Preparing operation sequence");
-            let operations = vec![
-                ("5".to_string(), 1),
+            let operations = [("5".to_string(), 1),
                 ("3".to_string(), 2),
-                ("7".to_string(), 3),
-            ];
+                ("7".to_string(), 3)];
             println!("→ Operation sequence: increment by 5, then 3, then 7");
 
             println!("\nStep 4/7: Sending operations to source instance in reverse order");
@@ -455,13 +454,14 @@ mod operation_network_robustness {
             let deployment_name = "crust-network";
             let replicas = 3;
             let test_config = DeploymentConfig::new(
-                replicas.try_into().unwrap(),
+                replicas,
                 "gcounter",
                 "operation",
                 "immediate",
                 None,
                 None,
-            );
+            )
+            .unwrap();
             let service_urls = setup_remote_test_environement(&test_config).await;
             update_replicas(client, namespace, deployment_name, replicas).await;
 